@@ -0,0 +1,106 @@
+//! mixed workload comparison of `UnrolledList`, `LinkedList` and `Vector`
+//!
+//! run with `cargo bench --bench bench_unrolled`, every structure starts with the same elements and
+//! replays the same sequence of indexed reads, inserts and removals.
+use dsa_sport::datastruct::list_struct::LinkedList;
+use dsa_sport::datastruct::unrolled_list_struct::UnrolledList;
+use dsa_sport::datastruct::vec_struct::Vector;
+use std::time::{Duration, Instant};
+
+const ELEMENTS: usize = 20_000;
+const OPERATIONS: usize = 2_000;
+
+enum Operation {
+    Read(usize),
+    Insert(usize),
+    Remove(usize),
+}
+
+fn workload() -> Vec<Operation> {
+    let mut x: u64 = 42;
+    let mut length = ELEMENTS;
+    let mut operations = Vec::with_capacity(OPERATIONS);
+    for step in 0..OPERATIONS {
+        x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let roll = (x >> 33) as usize;
+        let operation = match step % 4 {
+            0 | 1 => Operation::Read(roll % length),
+            2 => {
+                length += 1;
+                Operation::Insert(roll % length)
+            },
+            _ => {
+                length -= 1;
+                Operation::Remove(roll % (length + 1))
+            },
+        };
+        operations.push(operation);
+    }
+    operations
+}
+
+fn time<F: FnOnce() -> usize>(name: &str, run: F) -> Duration {
+    let start = Instant::now();
+    let checksum = run();
+    let elapsed = start.elapsed();
+    println!("{:<14} {:>12?}  (checksum {})", name, elapsed, checksum);
+    elapsed
+}
+
+fn main() {
+    let operations = workload();
+    println!("{} elements, {} mixed operations", ELEMENTS, OPERATIONS);
+
+    for &capacity in [8, 32, 128].iter() {
+        time(&format!("unrolled/{}", capacity), || {
+            let mut list = UnrolledList::with_block_capacity(capacity);
+            for i in 0..ELEMENTS {
+                list.push_back(i);
+            }
+            let mut checksum = 0;
+            for operation in operations.iter() {
+                match *operation {
+                    Operation::Read(index) => checksum += list[index],
+                    Operation::Insert(index) => list.insert(index, index),
+                    Operation::Remove(index) => checksum += list.remove(index).unwrap_or(0),
+                }
+            }
+            checksum
+        });
+    }
+
+    time("vector", || {
+        let mut vector = Vector::new();
+        for i in 0..ELEMENTS {
+            vector.push_back(i);
+        }
+        let mut checksum = 0;
+        for operation in operations.iter() {
+            match *operation {
+                Operation::Read(index) => checksum += vector[index],
+                Operation::Insert(index) => vector.insert_at(index, index),
+                Operation::Remove(index) => checksum += vector.remove_at(index).unwrap_or(0),
+            }
+        }
+        checksum
+    });
+
+    time("linked list", || {
+        let mut list = LinkedList::new();
+        for i in 0..ELEMENTS {
+            list.add_node(i);
+        }
+        let mut checksum = 0;
+        for operation in operations.iter() {
+            match *operation {
+                Operation::Read(index) => checksum += list.get_at(index).copied().unwrap_or(0),
+                Operation::Insert(index) => list.insert_node_at(index, index),
+                Operation::Remove(index) => {
+                    checksum += list.get_at(index).copied().unwrap_or(0);
+                    list.delete_node_at(index);
+                },
+            }
+        }
+        checksum
+    });
+}
@@ -0,0 +1,848 @@
+//! singly linked list without raw pointers written `ArenaList<T>`
+//!
+//! it offers the same operations as [`crate::datastruct::list_struct::LinkedList`] but every member
+//! lives in a slot of a [`Vector`] and links are slot indices, so the module does not contain a
+//! single `unsafe` block. Slots freed by a removal are kept in a free list and reused by the next
+//! insertion.
+//!
+//! Every insertion returns a [`Handle`] made of the slot index and the generation of the slot. The
+//! generation is bumped whenever the slot is freed, so a handle stays valid while other members
+//! are inserted, removed or relinked, and it is rejected once its own member has been removed.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+//! let mut list = ArenaList::new();
+//! list.add_node(1);
+//! let two = list.add_node(2);
+//! list.add_node(3);
+//! assert_eq!(format!("{:?}", list), format!("⎩1⎭↪⎩2⎭↪⎩3⎭↪✘"));
+//! list.insert_node_at(0, 0);
+//! assert_eq!(list.get(two), Some(&2));
+//! assert_eq!(list.remove(two), Some(2));
+//! assert_eq!(list.get(two), None);
+//! assert_eq!(format!("{:?}", list), format!("⎩0⎭↪⎩1⎭↪⎩3⎭↪✘"));
+//! ```
+#![forbid(unsafe_code)]
+
+use crate::datastruct::vec_struct::Vector;
+use core::mem;
+
+const LEFT: &str = "⎩";
+const PHI: &str = "✘";
+const RIGHT: &str = "⎭";
+const LINK: &str = "↪";
+
+/// stable reference to a member of an [`ArenaList`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: usize,
+}
+
+pub struct ArenaList<T> {
+    slots: Vector<Slot<T>>,
+    free: Option<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    length: usize,
+}
+
+enum Slot<T> {
+    Occupied { generation: usize, member: Member<T> },
+    Vacant { generation: usize, next_free: Option<usize> },
+}
+
+struct Member<T> {
+    data: T,
+    next: Option<usize>,
+}
+
+impl<T> ArenaList<T>
+where
+    T: PartialOrd + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        Self { slots: Vector::new(), free: None, head: None, tail: None, length: 0 }
+    }
+
+    /// add node from the tail position and return its handle
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// let a = list.add_node('a');
+    /// list.add_node('b');
+    /// assert_eq!(list.len(), 2);
+    /// assert_eq!(list.get(a), Some(&'a'));
+    /// ```
+    pub fn add_node(&mut self, element: T) -> Handle {
+        let index = self.allocate(element);
+        match self.tail {
+            Some(tail) => self.member_mut(tail).next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+        self.handle_of(index)
+    }
+
+    /// length of the list, kept up to date by every operation so it is O(1)
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// find the length of list using a recursive approach O(n) but slower than
+    /// [`ArenaList::len`]
+    pub fn rec_len(&self) -> usize {
+        self.rec_len_util(self.head)
+    }
+
+    /// number of slots in the arena, occupied or free
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// let a = list.add_node(1);
+    /// list.add_node(2);
+    /// list.remove(a);
+    /// list.add_node(3);
+    /// assert_eq!(list.capacity(), 2);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// get the borrow of the element at index
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// list.add_node('a');
+    /// list.add_node('b');
+    /// list.add_node('c');
+    /// assert_eq!(list.get_at(1), Some(&'b'));
+    /// assert_eq!(list.get_at(3), None);
+    /// ```
+    pub fn get_at(&self, index: usize) -> Option<&T> {
+        self.index_at(index).map(|slot| &self.member(slot).data)
+    }
+
+    /// handle of the member at index
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// let a = list.add_node('a');
+    /// assert_eq!(list.handle_at(0), Some(a));
+    /// assert_eq!(list.handle_at(1), None);
+    /// ```
+    pub fn handle_at(&self, index: usize) -> Option<Handle> {
+        self.index_at(index).map(|slot| self.handle_of(slot))
+    }
+
+    /// borrow the element of a handle, `None` if the member has been removed
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.resolve(handle).map(|slot| &self.member(slot).data)
+    }
+
+    /// mutable borrow of the element of a handle, `None` if the member has been removed
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// let a = list.add_node(1);
+    /// *list.get_mut(a).unwrap() = 10;
+    /// assert_eq!(list.get_at(0), Some(&10));
+    /// ```
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        match self.resolve(handle) {
+            Some(slot) => Some(&mut self.member_mut(slot).data),
+            None => None,
+        }
+    }
+
+    /// true while the member of the handle is part of the list
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.resolve(handle).is_some()
+    }
+
+    /// insert a node right after the member of the handle
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// let a = list.add_node(1);
+    /// list.add_node(3);
+    /// list.insert_after(a, 2);
+    /// assert_eq!(format!("{:?}", list), format!("⎩1⎭↪⎩2⎭↪⎩3⎭↪✘"));
+    /// ```
+    pub fn insert_after(&mut self, handle: Handle, element: T) -> Option<Handle> {
+        let previous = self.resolve(handle)?;
+        let index = self.allocate(element);
+        self.member_mut(index).next = self.member(previous).next;
+        self.member_mut(previous).next = Some(index);
+        if self.tail == Some(previous) {
+            self.tail = Some(index);
+        }
+        Some(self.handle_of(index))
+    }
+
+    /// unlink the member of the handle and free its slot, O(n) as the predecessor has to be found
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// list.add_node(1);
+    /// let b = list.add_node(2);
+    /// assert_eq!(list.remove(b), Some(2));
+    /// assert_eq!(list.remove(b), None);
+    /// assert_eq!(format!("{:?}", list), format!("⎩1⎭↪✘"));
+    /// ```
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let target = self.resolve(handle)?;
+        let mut previous = None;
+        let mut current = self.head;
+        while let Some(index) = current {
+            if index == target {
+                break;
+            }
+            previous = current;
+            current = self.member(index).next;
+        }
+        Some(self.unlink_after(previous))
+    }
+
+    /// return an option of mid element
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// list.add_node('a');
+    /// list.add_node('b');
+    /// list.add_node('c');
+    /// assert_eq!(list.take_mid(), Some(&'b'));
+    /// ```
+    pub fn take_mid(&self) -> Option<&T> {
+        let mut slow = self.head?;
+        let mut fast = self.head?;
+        while let Some(next) = self.member(fast).next {
+            match self.member(next).next {
+                Some(next_next) => {
+                    fast = next_next;
+                    slow = self.member(slow).next?;
+                },
+                None => break,
+            }
+        }
+        Some(&self.member(slow).data)
+    }
+
+    /// Find a node in list and return its index position if found else returns None
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// list.add_node('a');
+    /// list.add_node('b');
+    /// list.add_node('c');
+    /// assert_eq!(list.find_node('b'), Some(1));
+    /// assert_eq!(list.find_node('d'), None);
+    /// ```
+    pub fn find_node(&mut self, element: T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let mut current = self.head;
+        let mut position = 0;
+        while let Some(index) = current {
+            if self.member(index).data == element {
+                return Some(position);
+            }
+            current = self.member(index).next;
+            position += 1;
+        }
+        None
+    }
+
+    /// recursive approach for [`ArenaList::find_node`]
+    pub fn recursive_find(&self, element: T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.recursive_find_helper(self.head, &element)
+    }
+
+    /// insert node at given position, `None` if the position is past the end of the list
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// list.add_node(1);
+    /// list.add_node(2);
+    /// list.add_node(3);
+    /// list.insert_node_at(1, 4);
+    /// assert_eq!(format!("{:?}", list), format!("⎩1⎭↪⎩4⎭↪⎩2⎭↪⎩3⎭↪✘"));
+    /// assert_eq!(list.insert_node_at(9, 5), None);
+    /// ```
+    pub fn insert_node_at(&mut self, index: usize, element: T) -> Option<Handle> {
+        if index == 0 {
+            let slot = self.allocate(element);
+            self.member_mut(slot).next = self.head;
+            self.head = Some(slot);
+            if self.tail.is_none() {
+                self.tail = Some(slot);
+            }
+            return Some(self.handle_of(slot));
+        }
+        let previous = self.index_at(index - 1)?;
+        let handle = self.handle_of(previous);
+        self.insert_after(handle, element)
+    }
+
+    /// recursive approach for [`ArenaList::insert_node_at`]
+    pub fn insert_node_at_rec(&mut self, index: usize, element: T) -> Option<Handle> {
+        if index > self.length {
+            return None;
+        }
+        let slot = self.allocate(element);
+        self.head = self.insert_node_at_rec_help(index, self.head, slot);
+        if self.member(slot).next.is_none() {
+            self.tail = Some(slot);
+        }
+        Some(self.handle_of(slot))
+    }
+
+    /// delete node at given position and return its element
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// list.add_node(1);
+    /// list.add_node(2);
+    /// list.add_node(3);
+    /// assert_eq!(list.delete_node_at(1), Some(2));
+    /// assert_eq!(format!("{:?}", list), format!("⎩1⎭↪⎩3⎭↪✘"));
+    /// assert_eq!(list.delete_node_at(2), None);
+    /// ```
+    pub fn delete_node_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.length {
+            return None;
+        }
+        let previous = if index == 0 { None } else { self.index_at(index - 1) };
+        Some(self.unlink_after(previous))
+    }
+
+    /// recursive approach for [`ArenaList::delete_node_at`]
+    pub fn delete_node_at_rec(&mut self, index: usize) -> Option<T> {
+        if index >= self.length {
+            return None;
+        }
+        let mut removed = None;
+        self.head = self.delete_node_at_rec_help(index, self.head, &mut removed);
+        self.tail = self.find_tail();
+        removed
+    }
+
+    /// swap node of the list by its index value, the members are relinked so handles keep
+    /// pointing at the same element
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// let one = list.add_node(1);
+    /// list.add_node(2);
+    /// list.add_node(3);
+    /// list.swape_nodes(0, 2);
+    /// assert_eq!(format!("{:?}", list), format!("⎩3⎭↪⎩2⎭↪⎩1⎭↪✘"));
+    /// assert_eq!(list.handle_at(2), Some(one));
+    /// ```
+    pub fn swape_nodes(&mut self, i: usize, j: usize) {
+        if i == j || i >= self.length || j >= self.length {
+            return;
+        }
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        let previous_one = if i == 0 { None } else { self.index_at(i - 1) };
+        let previous_two = self.index_at(j - 1);
+        let current_one = self.index_at(i).expect("index checked against length");
+        let current_two = self.index_at(j).expect("index checked against length");
+
+        self.set_link(previous_one, current_two);
+        self.set_link(previous_two, current_one);
+        let current_one_next = self.member(current_one).next;
+        let current_two_next = self.member(current_two).next;
+        self.member_mut(current_one).next = current_two_next;
+        self.member_mut(current_two).next = current_one_next;
+        if self.tail == Some(current_two) {
+            self.tail = Some(current_one);
+        }
+    }
+
+    /// remove duplicate from the list
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// for i in [1, 2, 2, 3, 4, 4].iter() {
+    ///     list.add_node(*i);
+    /// }
+    /// list.eliminate_dup();
+    /// assert_eq!(format!("{:?}", list), format!("⎩1⎭↪⎩2⎭↪⎩3⎭↪⎩4⎭↪✘"));
+    /// ```
+    pub fn eliminate_dup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut current = self.head;
+        while let Some(index) = current {
+            match self.member(index).next {
+                Some(next) if self.member(next).data == self.member(index).data => {
+                    self.unlink_after(Some(index));
+                },
+                next => current = next,
+            }
+        }
+    }
+
+    /// returns true if the list is palindrome, the arena cannot be relinked through `&self` so
+    /// this uses O(n) extra space
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// list.add_node('w');
+    /// list.add_node('o');
+    /// list.add_node('w');
+    /// assert_eq!(list.is_palindrome(), true);
+    /// list.add_node('w');
+    /// assert_eq!(list.is_palindrome(), false);
+    /// ```
+    pub fn is_palindrome(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut stack = Vector::new();
+        let mut current = self.head;
+        while let Some(index) = current {
+            stack.push_back(&self.member(index).data);
+            current = self.member(index).next;
+        }
+        current = self.head;
+        for _ in 0..self.length / 2 {
+            let index = current.expect("list shorter than its length");
+            if stack.pop_back() != Some(&self.member(index).data) {
+                return false;
+            }
+            current = self.member(index).next;
+        }
+        true
+    }
+
+    /// reverse the list
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// for i in 1..6 {
+    ///     list.add_node(i);
+    /// }
+    /// list.reverse_iterative();
+    /// assert_eq!(format!("{:?}", list), format!("⎩5⎭↪⎩4⎭↪⎩3⎭↪⎩2⎭↪⎩1⎭↪✘"));
+    /// ```
+    pub fn reverse_iterative(&mut self) {
+        let mut previous = None;
+        let mut current = self.head;
+        while let Some(index) = current {
+            current = mem::replace(&mut self.member_mut(index).next, previous);
+            previous = Some(index);
+        }
+        self.tail = self.head;
+        self.head = previous;
+    }
+
+    /// recursive approach for [`ArenaList::reverse_iterative`]
+    pub fn reverse_recursive(&mut self) {
+        self.tail = self.head;
+        self.head = self.reverse_recursive_helper(self.head);
+    }
+
+    /// sort the list into two category where formal elements are odd number followed by all
+    /// even number elements.
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// for i in 1..6 {
+    ///     list.add_node(i);
+    /// }
+    /// list.activate_kejriwal();
+    /// assert_eq!(format!("{:?}", list), format!("⎩1⎭↪⎩3⎭↪⎩5⎭↪⎩2⎭↪⎩4⎭↪✘"));
+    /// ```
+    pub fn activate_kejriwal(&mut self)
+    where
+        T: Copy + std::ops::Rem<Output = T> + PartialEq + From<i32>,
+    {
+        let two: T = 2i32.into();
+        let zero: T = 0i32.into();
+        let (mut odd_head, mut odd_tail) = (None, None);
+        let (mut even_head, mut even_tail) = (None, None);
+        let mut current = self.head;
+        while let Some(index) = current {
+            current = self.member(index).next;
+            self.member_mut(index).next = None;
+            let (head, tail) = if self.member(index).data % two == zero {
+                (&mut even_head, &mut even_tail)
+            } else {
+                (&mut odd_head, &mut odd_tail)
+            };
+            match *tail {
+                Some(last) => self.member_mut(last).next = Some(index),
+                None => *head = Some(index),
+            }
+            *tail = Some(index);
+        }
+        match odd_tail {
+            Some(last) => {
+                self.member_mut(last).next = even_head;
+                self.head = odd_head;
+                self.tail = if even_tail.is_some() { even_tail } else { odd_tail };
+            },
+            None => {
+                self.head = even_head;
+                self.tail = even_tail;
+            },
+        }
+    }
+
+    /// take last k element and append it to the front of the list
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// for i in 1..6 {
+    ///     list.add_node(i);
+    /// }
+    /// list.rotate_from_k(2);
+    /// assert_eq!(format!("{:?}", list), format!("⎩4⎭↪⎩5⎭↪⎩1⎭↪⎩2⎭↪⎩3⎭↪✘"));
+    /// ```
+    pub fn rotate_from_k(&mut self, last_n: usize) {
+        if last_n == 0 || last_n >= self.length {
+            return;
+        }
+        let new_tail = self.index_at(self.length - last_n - 1).expect("index below length");
+        let new_head = self.member(new_tail).next;
+        let old_tail = self.tail.expect("non empty list has a tail");
+        self.member_mut(old_tail).next = self.head;
+        self.member_mut(new_tail).next = None;
+        self.head = new_head;
+        self.tail = Some(new_tail);
+    }
+
+    /// O(n^2) sorting algorithm
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// for s in ["abc", "abd", "aad", "bcd", "bac"].iter() {
+    ///     list.add_node(*s);
+    /// }
+    /// list.bubble_sort();
+    /// assert_eq!(
+    ///     format!("{:?}", list),
+    ///     format!("⎩\"aad\"⎭↪⎩\"abc\"⎭↪⎩\"abd\"⎭↪⎩\"bac\"⎭↪⎩\"bcd\"⎭↪✘")
+    /// );
+    /// ```
+    pub fn bubble_sort(&mut self) {
+        for _ in 0..self.length {
+            let mut previous: Option<usize> = None;
+            let mut current = self.head;
+            while let Some(index) = current {
+                let next = match self.member(index).next {
+                    Some(next) => next,
+                    None => break,
+                };
+                if self.member(index).data > self.member(next).data {
+                    // relink previous -> next -> index -> after
+                    self.member_mut(index).next = self.member(next).next;
+                    self.member_mut(next).next = Some(index);
+                    self.set_link(previous, next);
+                    previous = Some(next);
+                } else {
+                    previous = current;
+                    current = Some(next);
+                }
+            }
+        }
+        self.tail = self.find_tail();
+    }
+
+    /// O(n log n) sorting algorithm
+    /// ```
+    /// # use dsa_sport::datastruct::arena_list_struct::ArenaList;
+    /// let mut list = ArenaList::new();
+    /// for i in [4, 1, 5, 2, 3].iter() {
+    ///     list.add_node(*i);
+    /// }
+    /// list.merge_sort();
+    /// assert_eq!(format!("{:?}", list), format!("⎩1⎭↪⎩2⎭↪⎩3⎭↪⎩4⎭↪⎩5⎭↪✘"));
+    /// ```
+    pub fn merge_sort(&mut self) {
+        self.head = self.merge_sort_helper(self.head);
+        self.tail = self.find_tail();
+    }
+}
+
+// private associated functions
+impl<T> ArenaList<T> {
+    fn allocate(&mut self, element: T) -> usize {
+        self.length += 1;
+        let member = Member { data: element, next: None };
+        match self.free {
+            Some(index) => {
+                let generation = match self.slots[index] {
+                    Slot::Vacant { generation, next_free } => {
+                        self.free = next_free;
+                        generation
+                    },
+                    Slot::Occupied { .. } => panic!("free list points at an occupied slot"),
+                };
+                self.slots[index] = Slot::Occupied { generation, member };
+                index
+            },
+            None => {
+                self.slots.push_back(Slot::Occupied { generation: 0, member });
+                self.slots.len() - 1
+            },
+        }
+    }
+
+    fn release(&mut self, index: usize) -> T {
+        let generation = match self.slots[index] {
+            Slot::Occupied { generation, .. } => generation,
+            Slot::Vacant { .. } => panic!("slot released twice"),
+        };
+        let vacant = Slot::Vacant { generation: generation.wrapping_add(1), next_free: self.free };
+        self.free = Some(index);
+        self.length -= 1;
+        match mem::replace(&mut self.slots[index], vacant) {
+            Slot::Occupied { member, .. } => member.data,
+            Slot::Vacant { .. } => unreachable!(),
+        }
+    }
+
+    fn member(&self, index: usize) -> &Member<T> {
+        match &self.slots[index] {
+            Slot::Occupied { member, .. } => member,
+            Slot::Vacant { .. } => panic!("link points at a vacant slot"),
+        }
+    }
+
+    fn member_mut(&mut self, index: usize) -> &mut Member<T> {
+        match &mut self.slots[index] {
+            Slot::Occupied { member, .. } => member,
+            Slot::Vacant { .. } => panic!("link points at a vacant slot"),
+        }
+    }
+
+    fn handle_of(&self, index: usize) -> Handle {
+        match self.slots[index] {
+            Slot::Occupied { generation, .. } => Handle { index, generation },
+            Slot::Vacant { .. } => panic!("handle requested for a vacant slot"),
+        }
+    }
+
+    fn resolve(&self, handle: Handle) -> Option<usize> {
+        if handle.index >= self.slots.len() {
+            return None;
+        }
+        match self.slots[handle.index] {
+            Slot::Occupied { generation, .. } if generation == handle.generation => {
+                Some(handle.index)
+            },
+            _ => None,
+        }
+    }
+
+    fn index_at(&self, position: usize) -> Option<usize> {
+        let mut current = self.head;
+        for _ in 0..position {
+            current = self.member(current?).next;
+        }
+        current
+    }
+
+    fn find_tail(&self) -> Option<usize> {
+        let mut current = self.head?;
+        while let Some(next) = self.member(current).next {
+            current = next;
+        }
+        Some(current)
+    }
+
+    // make `previous.next` (or the head when `previous` is `None`) point at `index`
+    fn set_link(&mut self, previous: Option<usize>, index: usize) {
+        match previous {
+            Some(previous) => self.member_mut(previous).next = Some(index),
+            None => self.head = Some(index),
+        }
+    }
+
+    // unlink the member following `previous` (the head when `None`) and free its slot
+    fn unlink_after(&mut self, previous: Option<usize>) -> T {
+        let target = match previous {
+            Some(previous) => self.member(previous).next,
+            None => self.head,
+        }
+        .expect("no member to unlink");
+        let next = self.member(target).next;
+        match previous {
+            Some(previous) => self.member_mut(previous).next = next,
+            None => self.head = next,
+        }
+        if self.tail == Some(target) {
+            self.tail = previous;
+        }
+        self.release(target)
+    }
+
+    fn rec_len_util(&self, current: Option<usize>) -> usize {
+        match current {
+            Some(index) => 1 + self.rec_len_util(self.member(index).next),
+            None => 0,
+        }
+    }
+
+    fn recursive_find_helper(&self, current: Option<usize>, element: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let index = current?;
+        if self.member(index).data == *element {
+            return Some(0);
+        }
+        self.recursive_find_helper(self.member(index).next, element).map(|x| x + 1)
+    }
+
+    fn insert_node_at_rec_help(
+        &mut self,
+        index: usize,
+        head: Option<usize>,
+        node: usize,
+    ) -> Option<usize> {
+        match head {
+            Some(current) if index > 0 => {
+                let next = self.member(current).next;
+                let next = self.insert_node_at_rec_help(index - 1, next, node);
+                self.member_mut(current).next = next;
+                Some(current)
+            },
+            _ => {
+                self.member_mut(node).next = head;
+                Some(node)
+            },
+        }
+    }
+
+    fn delete_node_at_rec_help(
+        &mut self,
+        index: usize,
+        head: Option<usize>,
+        removed: &mut Option<T>,
+    ) -> Option<usize> {
+        let current = head?;
+        let next = self.member(current).next;
+        if index == 0 {
+            *removed = Some(self.release(current));
+            return next;
+        }
+        let next = self.delete_node_at_rec_help(index - 1, next, removed);
+        self.member_mut(current).next = next;
+        Some(current)
+    }
+
+    fn reverse_recursive_helper(&mut self, head: Option<usize>) -> Option<usize> {
+        let current = head?;
+        match self.member(current).next {
+            None => Some(current),
+            Some(next) => {
+                let res = self.reverse_recursive_helper(Some(next));
+                self.member_mut(next).next = Some(current);
+                self.member_mut(current).next = None;
+                res
+            },
+        }
+    }
+
+    fn merge_sort_helper(&mut self, head: Option<usize>) -> Option<usize>
+    where
+        T: PartialOrd,
+    {
+        let first = head?;
+        if self.member(first).next.is_none() {
+            return head;
+        }
+        // split behind the middle member found with slow and fast links
+        let mut slow = first;
+        let mut fast = first;
+        while let Some(next) = self.member(fast).next.and_then(|next| self.member(next).next) {
+            fast = next;
+            slow = self.member(slow).next.expect("slow never passes fast");
+        }
+        let second = self.member_mut(slow).next.take();
+        let half_one = self.merge_sort_helper(Some(first));
+        let half_two = self.merge_sort_helper(second);
+        self.merge_member(half_one, half_two)
+    }
+
+    fn merge_member(&mut self, mut one: Option<usize>, mut two: Option<usize>) -> Option<usize>
+    where
+        T: PartialOrd,
+    {
+        let mut head = None;
+        let mut tail: Option<usize> = None;
+        loop {
+            let pick = match (one, two) {
+                (Some(a), Some(b)) => {
+                    if self.member(a).data <= self.member(b).data {
+                        one = self.member(a).next;
+                        a
+                    } else {
+                        two = self.member(b).next;
+                        b
+                    }
+                },
+                (Some(rest), None) | (None, Some(rest)) => {
+                    match tail {
+                        Some(last) => self.member_mut(last).next = Some(rest),
+                        None => head = Some(rest),
+                    }
+                    return head;
+                },
+                (None, None) => return head,
+            };
+            match tail {
+                Some(last) => self.member_mut(last).next = Some(pick),
+                None => head = Some(pick),
+            }
+            tail = Some(pick);
+        }
+    }
+}
+
+impl<T> Default for ArenaList<T>
+where
+    T: PartialOrd + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> std::fmt::Debug for ArenaList<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut out = String::new();
+        let mut current = self.head;
+        while let Some(index) = current {
+            let member = self.member(index);
+            out += &format!("{}{:?}{}{}", LEFT, member.data, RIGHT, LINK);
+            current = member.next;
+        }
+        out += PHI;
+        write!(f, "{}", out)
+    }
+}
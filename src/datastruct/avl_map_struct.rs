@@ -0,0 +1,579 @@
+//! height balanced binary search tree map written `AvlMap<K, V>`
+//!
+//! every member keeps the height of its subtree, and the balance factor of a member is the
+//! height of its left subtree minus the height of its right subtree. After an insertion or a
+//! removal the members on the way back up to the root are checked, and one with a balance factor
+//! of `+2` or `-2` is mended by one of the four rotations named after the path to its deeper
+//! grandchild: `LL`, `RR`, `LR` and `RL`. The members, walks, searches and rotations are those of
+//! [`crate::datastruct::bst_core`], the height sits in the balance data of a member.
+//!
+//! A map started with [`AvlMap::start_rotation_log`] records every insertion, removal and
+//! rotation as an [`AvlEvent`], and a [`Replay`] performs the recorded events one at a time on a
+//! tree of keys, showing the unbalanced tree before each rotation.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::avl_map_struct::AvlMap;
+//! let mut map = AvlMap::new();
+//! map.start_rotation_log();
+//! for key in [3, 2, 1].iter() {
+//!     map.insert(*key, ());
+//! }
+//! let log: Vec<String> = map.rotation_log().iter().map(|event| event.to_string()).collect();
+//! assert_eq!(log, ["insert 3", "insert 2", "insert 1", "LL at 3"]);
+//! assert_eq!(format!("{}", map), "╚═2: () [0]\n  ╠═L 1: () [0]\n  ╚═R 3: () [0]\n");
+//! ```
+
+use crate::datastruct::bst_core::{self, Augment, Core, Counted, Member};
+use core::mem;
+use core::ops::RangeBounds;
+
+pub struct AvlMap<K, V> {
+    core: Core<K, V, usize>,
+    log: Option<RotationLog<K>>,
+}
+
+/// iterator of [`AvlMap::iter`] and [`AvlMap::range`]
+pub type Iter<'a, K, V> = bst_core::Iter<'a, K, V, usize>;
+
+/// iterator of [`AvlMap::iter_mut`]
+pub type IterMut<'a, K, V> = bst_core::IterMut<'a, K, V, usize>;
+
+// a member with the height of its subtree as balance data
+type Node<K, V> = Member<K, V, usize>;
+
+// recorded events, `copy` clones a key so that recording asks nothing of `K` after the start
+struct RotationLog<K> {
+    copy: fn(&K) -> K,
+    events: Vec<AvlEvent<K>>,
+}
+
+/// the four ways of mending a member whose balance factor reached `+2` or `-2`, named after the
+/// sides leading from the member down to its deeper grandchild
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationCase {
+    /// a right rotation at the member
+    LL,
+    /// a left rotation at the member
+    RR,
+    /// a left rotation at the left child, then a right rotation at the member
+    LR,
+    /// a right rotation at the right child, then a left rotation at the member
+    RL,
+}
+
+/// one entry of the rotation log of an [`AvlMap`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AvlEvent<K> {
+    /// a new key went in
+    Insert(K),
+    /// a key was taken out
+    Remove(K),
+    /// the member holding `pivot` was out of balance and mended by `case`
+    Rotate { case: RotationCase, pivot: K },
+}
+
+impl std::fmt::Display for RotationCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl<K> std::fmt::Display for AvlEvent<K>
+where
+    K: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AvlEvent::Insert(key) => write!(f, "insert {}", key),
+            AvlEvent::Remove(key) => write!(f, "remove {}", key),
+            AvlEvent::Rotate { case, pivot } => write!(f, "{} at {}", case, pivot),
+        }
+    }
+}
+
+// balance data holding the height of the subtree below a member, alone in an `AvlMap` and next
+// to the size of the subtree in an `OrderStatTree`
+pub(crate) trait Height {
+    fn height(&self) -> usize;
+    fn set_height(&mut self, height: usize);
+}
+
+impl Height for usize {
+    fn height(&self) -> usize {
+        *self
+    }
+
+    fn set_height(&mut self, height: usize) {
+        *self = height;
+    }
+}
+
+impl Height for Counted<usize> {
+    fn height(&self) -> usize {
+        self.balance
+    }
+
+    fn set_height(&mut self, height: usize) {
+        self.balance = height;
+    }
+}
+
+// height of a subtree, zero for a missing one
+pub(crate) unsafe fn height_of<K, V, X>(node: *mut Member<K, V, X>) -> usize
+where
+    X: Height,
+{
+    node.as_ref().map_or(0, |node| node.extra.height())
+}
+
+pub(crate) unsafe fn balance_factor<K, V, X>(node: *mut Member<K, V, X>) -> isize
+where
+    X: Height,
+{
+    height_of((*node).left) as isize - height_of((*node).right) as isize
+}
+
+unsafe fn refresh_height<K, V, X>(node: *mut Member<K, V, X>)
+where
+    X: Height,
+{
+    (*node).extra.set_height(1 + height_of((*node).left).max(height_of((*node).right)));
+}
+
+// the left child of `node` takes its place, gives back the new top of the subtree
+unsafe fn rotate_right<K, V, X>(
+    core: &mut Core<K, V, X>,
+    node: *mut Member<K, V, X>,
+) -> *mut Member<K, V, X>
+where
+    X: Height + Augment<V>,
+{
+    let top = core.rotate_right(node);
+    refresh_height(node);
+    refresh_height(top);
+    top
+}
+
+// the right child of `node` takes its place, gives back the new top of the subtree
+unsafe fn rotate_left<K, V, X>(
+    core: &mut Core<K, V, X>,
+    node: *mut Member<K, V, X>,
+) -> *mut Member<K, V, X>
+where
+    X: Height + Augment<V>,
+{
+    let top = core.rotate_left(node);
+    refresh_height(node);
+    refresh_height(top);
+    top
+}
+
+unsafe fn rotate<K, V, X>(
+    core: &mut Core<K, V, X>,
+    node: *mut Member<K, V, X>,
+    case: RotationCase,
+) -> *mut Member<K, V, X>
+where
+    X: Height + Augment<V>,
+{
+    match case {
+        RotationCase::LL => rotate_right(core, node),
+        RotationCase::RR => rotate_left(core, node),
+        RotationCase::LR => {
+            rotate_left(core, (*node).left);
+            rotate_right(core, node)
+        },
+        RotationCase::RL => {
+            rotate_right(core, (*node).right);
+            rotate_left(core, node)
+        },
+    }
+}
+
+// walk from `node` up to the root refreshing the heights and the data kept next to them, and
+// mend every member out of balance on the way when `balance`, `mend` hears of each rotation
+// before it is done
+pub(crate) unsafe fn retrace<K, V, X, F>(
+    core: &mut Core<K, V, X>,
+    mut node: *mut Member<K, V, X>,
+    balance: bool,
+    mut mend: F,
+) where
+    X: Height + Augment<V>,
+    F: FnMut(RotationCase, *mut Member<K, V, X>),
+{
+    while !node.is_null() {
+        Member::refresh(node);
+        refresh_height(node);
+        let factor = balance_factor(node);
+        if balance && factor.abs() > 1 {
+            let case = match (
+                factor > 0,
+                balance_factor(if factor > 0 { (*node).left } else { (*node).right }),
+            ) {
+                (true, lower) if lower >= 0 => RotationCase::LL,
+                (true, _) => RotationCase::LR,
+                (false, lower) if lower <= 0 => RotationCase::RR,
+                (false, _) => RotationCase::RL,
+            };
+            mend(case, node);
+            node = rotate(core, node, case);
+        }
+        node = (*node).parent;
+    }
+}
+
+impl<K, V> AvlMap<K, V> {
+    pub fn new() -> Self {
+        Self { core: Core::new(), log: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.core.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.core.len == 0
+    }
+
+    /// number of levels, the empty map has none
+    pub fn height(&self) -> usize {
+        unsafe { height_of(self.core.root) }
+    }
+
+    /// take out every entry, a running rotation log starts over with no events
+    pub fn clear(&mut self) {
+        let log = self.log.take().map(|log| RotationLog { copy: log.copy, events: Vec::new() });
+        *self = Self::new();
+        self.log = log;
+    }
+
+    /// smallest key with its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        self.core.min()
+    }
+
+    /// largest key with its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        self.core.max()
+    }
+
+    /// entries in key order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.core.iter()
+    }
+
+    /// entries in key order with mutable values
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.core.iter_mut()
+    }
+
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// start recording insertions, removals and rotations, dropping the events recorded so far
+    pub fn start_rotation_log(&mut self)
+    where
+        K: Clone,
+    {
+        self.log = Some(RotationLog { copy: K::clone, events: Vec::new() });
+    }
+
+    /// stop recording and give back the events recorded since the start
+    pub fn stop_rotation_log(&mut self) -> Vec<AvlEvent<K>> {
+        self.log.take().map_or_else(Vec::new, |log| log.events)
+    }
+
+    /// events recorded since the start, nothing when the log is not running
+    pub fn rotation_log(&self) -> &[AvlEvent<K>] {
+        self.log.as_ref().map_or(&[], |log| &log.events)
+    }
+
+    /// events recorded so far, the log keeps running with no events
+    pub fn take_rotation_log(&mut self) -> Vec<AvlEvent<K>> {
+        self.log.as_mut().map_or_else(Vec::new, |log| mem::take(&mut log.events))
+    }
+
+    fn record(&mut self, event: impl FnOnce(fn(&K) -> K) -> AvlEvent<K>) {
+        if let Some(log) = self.log.as_mut() {
+            log.events.push(event(log.copy));
+        }
+    }
+
+    // walk from `node` up to the root refreshing the heights, and mend every member out of
+    // balance on the way when `balance`
+    unsafe fn retrace(&mut self, node: *mut Node<K, V>, balance: bool) {
+        let log = &mut self.log;
+        retrace(&mut self.core, node, balance, |case, pivot| {
+            if let Some(log) = log.as_mut() {
+                log.events.push(AvlEvent::Rotate { case, pivot: (log.copy)(&(*pivot).key) });
+            }
+        });
+    }
+}
+
+impl<K, V> AvlMap<K, V>
+where
+    K: Ord,
+{
+    /// put `value` under `key` and give back the value it replaces
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert_member(key, value, true)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        unsafe { self.core.find(key).as_ref().map(|node| &node.value) }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { self.core.find(key).as_mut().map(|node| &mut node.value) }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        !self.core.find(key).is_null()
+    }
+
+    /// take `key` out of the map and rebalance on the way up from the member cut out
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::avl_map_struct::{AvlEvent, AvlMap, RotationCase};
+    /// let mut map: AvlMap<u8, ()> = [2, 1, 4, 3].iter().map(|&key| (key, ())).collect();
+    /// map.start_rotation_log();
+    /// assert_eq!(map.remove(&1), Some(()));
+    /// assert_eq!(map.rotation_log()[1], AvlEvent::Rotate { case: RotationCase::RL, pivot: 2 });
+    /// assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    /// assert_eq!(map.height(), 2);
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let target = self.core.find(key);
+        if target.is_null() {
+            return None;
+        }
+        unsafe { Some(self.remove_member(target, true)) }
+    }
+
+    /// entries with a key in `range` in key order, a range whose start is above its end is empty
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::avl_map_struct::AvlMap;
+    /// let map: AvlMap<u32, u32> = (0..100).map(|i| (i, i * i)).collect();
+    /// assert_eq!(map.range(3..6).map(|(_, square)| *square).collect::<Vec<_>>(), vec![9, 16, 25]);
+    /// assert_eq!(map.height(), 7);
+    /// ```
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        self.core.range(range)
+    }
+
+    /// check the links, the order of the keys, the stored heights and that no balance factor is
+    /// beyond `-1..=1`
+    pub fn check_invariants(&self) -> bool {
+        self.check_shape()
+            && self.iter_members().all(|node| unsafe { balance_factor(node).abs() <= 1 })
+    }
+
+    // links, key order and heights, which hold in a replay between the events too
+    fn check_shape(&self) -> bool {
+        self.core.check_shape().is_ok()
+            && self.iter_members().all(|node| unsafe {
+                (*node).extra == 1 + height_of((*node).left).max(height_of((*node).right))
+            })
+    }
+
+    fn iter_members(&self) -> impl Iterator<Item = *mut Node<K, V>> + '_ {
+        let mut walk = self.iter();
+        core::iter::from_fn(move || walk.next_member())
+    }
+
+    // plain insertion followed by a retrace, rebalancing when `balance`
+    fn insert_member(&mut self, key: K, value: V, balance: bool) -> Option<V> {
+        match self.core.search(&key) {
+            Ok(node) => unsafe { Some(mem::replace(&mut (*node).value, value)) },
+            Err(slot) => {
+                self.record(|copy| AvlEvent::Insert(copy(&key)));
+                let node = Member::allocate_memory(Member::new(key, value, slot.parent, 1));
+                unsafe {
+                    self.core.attach(node, &slot);
+                    self.retrace(slot.parent, balance);
+                }
+                None
+            },
+        }
+    }
+
+    // a member with two children takes over the entry of its successor, which is cut out
+    // instead, then the heights are retraced from the parent of the member cut out
+    unsafe fn remove_member(&mut self, target: *mut Node<K, V>, balance: bool) -> (K, V) {
+        self.record(|copy| AvlEvent::Remove(copy(&(*target).key)));
+        let cut = self.core.detach(target);
+        self.retrace(cut.parent, balance);
+        let member = Member::deallocate_memory(cut.member);
+        (member.key, member.value)
+    }
+}
+
+/// step by step rerun of a rotation log on a tree of keys. Insertions and removals are done
+/// without rebalancing, so between an event and the rotations it causes the tree shows the
+/// balance factors that call for them.
+/// # Example
+/// ```
+/// # use dsa_sport::datastruct::avl_map_struct::{AvlMap, Replay};
+/// let mut map = AvlMap::new();
+/// map.start_rotation_log();
+/// map.extend([1, 3, 2].iter().map(|&key| (key, ())));
+/// let mut replay = Replay::new(map.rotation_log());
+/// replay.step();
+/// replay.step();
+/// replay.step();
+/// assert_eq!(format!("{}", replay), "╚═1 [-2]\n  ╚═R 3 [+1]\n    ╚═L 2 [0]\n");
+/// assert_eq!(replay.step().map(|event| event.to_string()), Some(String::from("RL at 1")));
+/// assert_eq!(format!("{}", replay), "╚═2 [0]\n  ╠═L 1 [0]\n  ╚═R 3 [0]\n");
+/// assert!(replay.step().is_none());
+/// assert!(replay.tree().check_invariants());
+/// ```
+pub struct Replay<K> {
+    tree: AvlMap<K, ()>,
+    events: Vec<AvlEvent<K>>,
+    done: usize,
+}
+
+impl<K> Replay<K>
+where
+    K: Ord + Clone,
+{
+    /// replay of `events` starting on the empty tree
+    pub fn new(events: &[AvlEvent<K>]) -> Self {
+        Self { tree: AvlMap::new(), events: events.to_vec(), done: 0 }
+    }
+
+    /// perform the next event and give it back, `None` once every event is done
+    /// # Panics
+    /// when an event does not fit the tree, as with a log recorded after the map was filled
+    pub fn step(&mut self) -> Option<&AvlEvent<K>> {
+        let event = self.events.get(self.done)?;
+        match event {
+            AvlEvent::Insert(key) => {
+                let replaced = self.tree.insert_member(key.clone(), (), false);
+                assert!(replaced.is_none(), "replayed insertion of a key already in the tree");
+            },
+            AvlEvent::Remove(key) => {
+                let target = self.tree.core.find(key);
+                assert!(!target.is_null(), "replayed removal of a key not in the tree");
+                unsafe { self.tree.remove_member(target, false) };
+            },
+            AvlEvent::Rotate { case, pivot } => {
+                let node = self.tree.core.find(pivot);
+                assert!(!node.is_null(), "replayed rotation at a key not in the tree");
+                unsafe { rotate(&mut self.tree.core, node, *case) };
+                // the members above the pivot see the new height of its subtree
+                unsafe { self.tree.retrace((*node).parent, false) };
+            },
+        }
+        self.done += 1;
+        self.events.get(self.done - 1)
+    }
+
+    /// number of events performed so far
+    pub fn position(&self) -> usize {
+        self.done
+    }
+
+    /// the tree after the events performed so far
+    pub fn tree(&self) -> &AvlMap<K, ()> {
+        &self.tree
+    }
+}
+
+impl<K> std::fmt::Display for Replay<K>
+where
+    K: std::fmt::Display,
+{
+    /// the keys with their balance factors
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tree.render(|node| format!("{}", node.key)))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a AvlMap<K, V> {
+    type IntoIter = Iter<'a, K, V>;
+    type Item = (&'a K, &'a V);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> core::iter::FromIterator<(K, V)> for AvlMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AvlMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for AvlMap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> Default for AvlMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> AvlMap<K, V> {
+    // the members as drawn by the binary tree, `label` followed by the balance factor
+    fn render<L>(&self, mut label: L) -> String
+    where
+        L: FnMut(&Node<K, V>) -> String,
+    {
+        self.core.render(|candidate_ptr| {
+            let factor = unsafe { balance_factor(candidate_ptr) };
+            let factor = if factor == 0 { String::from("0") } else { format!("{:+}", factor) };
+            format!("{} [{}]", label(unsafe { &*candidate_ptr }), factor)
+        })
+    }
+}
+
+impl<K, V> std::fmt::Display for AvlMap<K, V>
+where
+    K: std::fmt::Display,
+    V: std::fmt::Debug,
+{
+    /// every member with its balance factor in brackets
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|node| format!("{}: {:?}", node.key, node.value)))
+    }
+}
+
+impl<K, V> std::fmt::Debug for AvlMap<K, V>
+where
+    K: std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,721 @@
+//! B-tree map written `BTreeMap<K, V, ORDER>`
+//!
+//! a node holds up to `ORDER - 1` entries in a [`Vector`] sorted by key, and an inner node has
+//! one child more than it has entries, the keys of child `i` lying between entries `i - 1` and
+//! `i`. Every node but the root keeps at least `⌈ORDER / 2⌉ - 1` entries and every leaf is at the
+//! same depth. An insertion that overfills a leaf splits it around its median entry, which moves
+//! up and may split the parent in turn, and a removal that leaves a node short borrows an entry
+//! through the parent from a sibling or merges with one. `ORDER` has to be at least 3, a smaller
+//! order does not compile.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::b_tree_map_struct::BTreeMap;
+//! let mut map: BTreeMap<u32, char, 3> = BTreeMap::new();
+//! for (i, c) in "abcdefg".chars().enumerate() {
+//!     map.insert(i as u32, c);
+//! }
+//! assert_eq!(format!("{}", map), "[3]\n[1] [5]\n[0] [2] [4] [6]\n");
+//! assert_eq!(map.range(2..5).map(|(_, c)| *c).collect::<String>(), "cde");
+//! map.remove(&3);
+//! assert_eq!(format!("{}", map), "[2 5]\n[0 1] [4] [6]\n");
+//! ```
+
+use crate::datastruct::vec_struct::Vector;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use core::ptr;
+use std::alloc;
+
+pub struct BTreeMap<K, V, const ORDER: usize> {
+    root: *mut Node<K, V>,
+    len: usize,
+}
+
+struct Node<K, V> {
+    entries: Vector<(K, V)>,
+    // empty in a leaf
+    children: Vector<*mut Node<K, V>>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new() -> Self {
+        Self { entries: Vector::new(), children: Vector::new() }
+    }
+
+    fn allocate_memory(candidate: Node<K, V>) -> *mut Node<K, V> {
+        let size = mem::size_of::<Node<K, V>>();
+        let align = mem::align_of::<Node<K, V>>();
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            let ptr = alloc::alloc(layout) as *mut Node<K, V>;
+            ptr.write(candidate);
+            ptr
+        }
+    }
+
+    unsafe fn deallocate_memory(node: *mut Node<K, V>) -> Node<K, V> {
+        let size = mem::size_of::<Node<K, V>>();
+        let align = mem::align_of::<Node<K, V>>();
+        let member = node.read();
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(node as *mut u8, layout);
+        member
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl<K, V> Node<K, V>
+where
+    K: Ord,
+{
+    // index of `key` or the index it would go in, by binary search
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        let (mut low, mut high) = (0, self.entries.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.entries[middle].0.cmp(key) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Greater => high = middle,
+                Ordering::Equal => return Ok(middle),
+            }
+        }
+        Err(low)
+    }
+
+    // number of entries below `key`, or not above it when `inclusive`
+    fn rank(&self, key: &K, inclusive: bool) -> usize {
+        match self.search(key) {
+            Ok(index) if inclusive => index + 1,
+            Ok(index) | Err(index) => index,
+        }
+    }
+}
+
+// move the elements from `at` on into a new vector
+fn split_off<T>(elements: &mut Vector<T>, at: usize) -> Vector<T> {
+    let mut moved = Vec::new();
+    while elements.len() > at {
+        moved.extend(elements.pop_back());
+    }
+    let mut tail = Vector::new();
+    while let Some(element) = moved.pop() {
+        tail.push_back(element);
+    }
+    tail
+}
+
+// move every element of `tail` to the back of `elements`
+fn append<T>(elements: &mut Vector<T>, tail: &mut Vector<T>) {
+    while let Some(element) = tail.pop_front() {
+        elements.push_back(element);
+    }
+}
+
+impl<K, V, const ORDER: usize> BTreeMap<K, V, ORDER> {
+    const MAX_ENTRIES: usize = ORDER - 1;
+    const MIN_ENTRIES: usize = ORDER.div_ceil(2) - 1;
+    // evaluated when `new` is instantiated, so a smaller order is a compile error
+    const VALID_ORDER: () = assert!(ORDER >= 3, "a B-tree needs an order of at least 3");
+
+    /// an `ORDER` below 3 is rejected when the map is built
+    /// ```compile_fail
+    /// # use dsa_sport::datastruct::b_tree_map_struct::BTreeMap;
+    /// let map: BTreeMap<u32, (), 2> = BTreeMap::new();
+    /// ```
+    pub fn new() -> Self {
+        let () = Self::VALID_ORDER;
+        Self { root: ptr::null_mut(), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// number of levels of nodes, the empty map has none
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut node = self.root;
+        while let Some(current) = unsafe { node.as_ref() } {
+            height += 1;
+            node = if current.is_leaf() { ptr::null_mut() } else { current.children[0] };
+        }
+        height
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// smallest key with its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// largest key with its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let (node, index) = self.last_position()?;
+        let (key, value) = unsafe { &(&*node).entries[index] };
+        Some((key, value))
+    }
+
+    /// entries in key order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut walk =
+            Iter { stack: Vector::new(), end: self.last_position(), marker: PhantomData };
+        walk.descend_first(self.root);
+        walk
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    // node and index of the largest entry
+    fn last_position(&self) -> Option<(*mut Node<K, V>, usize)> {
+        let mut node = unsafe { self.root.as_ref()? };
+        while !node.is_leaf() {
+            node = unsafe { &*node.children[node.children.len() - 1] };
+        }
+        Some((node as *const Node<K, V> as *mut Node<K, V>, node.entries.len() - 1))
+    }
+
+    // split the overfull `node` around its median entry, which is given back with the new node
+    // holding the entries and children above it
+    unsafe fn split(node: *mut Node<K, V>) -> ((K, V), *mut Node<K, V>) {
+        let middle = (*node).entries.len() / 2;
+        let mut right = Node::new();
+        right.entries = split_off(&mut (*node).entries, middle + 1);
+        if !(*node).is_leaf() {
+            right.children = split_off(&mut (*node).children, middle + 1);
+        }
+        let median = (*node).entries.pop_back().expect("an overfull node has a median");
+        (median, Node::allocate_memory(right))
+    }
+
+    // mend the short `node` below the members of `path`, borrowing from a sibling with entries
+    // to spare or merging with a sibling and going on with the parent
+    unsafe fn rebalance(
+        &mut self,
+        mut node: *mut Node<K, V>,
+        mut path: Vector<(*mut Node<K, V>, usize)>,
+    ) {
+        loop {
+            let (parent, i) = match path.pop_back() {
+                Some(step) => step,
+                None => {
+                    // the root may run empty, its only child takes over
+                    if (*node).entries.is_empty() {
+                        self.root = (*node).children.pop_back().unwrap_or(ptr::null_mut());
+                        Node::deallocate_memory(node);
+                    }
+                    return;
+                },
+            };
+            if (*node).entries.len() >= Self::MIN_ENTRIES {
+                return;
+            }
+            let parent_node = &mut *parent;
+            let current = &mut *node;
+            let left = if i > 0 { parent_node.children[i - 1] } else { ptr::null_mut() };
+            let right = if i + 1 < parent_node.children.len() {
+                parent_node.children[i + 1]
+            } else {
+                ptr::null_mut()
+            };
+            if let Some(left) = left.as_mut().filter(|left| left.entries.len() > Self::MIN_ENTRIES)
+            {
+                let moved = left.entries.pop_back().expect("a sibling with entries to spare");
+                let separator = mem::replace(&mut parent_node.entries[i - 1], moved);
+                current.entries.insert_at(0, separator);
+                if let Some(child) = left.children.pop_back() {
+                    current.children.insert_at(0, child);
+                }
+                return;
+            }
+            if let Some(right) =
+                right.as_mut().filter(|right| right.entries.len() > Self::MIN_ENTRIES)
+            {
+                let moved = right.entries.pop_front().expect("a sibling with entries to spare");
+                let separator = mem::replace(&mut parent_node.entries[i], moved);
+                current.entries.push_back(separator);
+                if let Some(child) = right.children.pop_front() {
+                    current.children.push_back(child);
+                }
+                return;
+            }
+            // both siblings are as short as allowed, the pair and their separator fill one node
+            let (kept, gone, separator_index) =
+                if left.is_null() { (node, right, i) } else { (left, node, i - 1) };
+            let separator =
+                parent_node.entries.remove_at(separator_index).expect("parent holds the separator");
+            parent_node.children.remove_at(separator_index + 1);
+            let (kept, gone_node) = (&mut *kept, &mut *gone);
+            kept.entries.push_back(separator);
+            append(&mut kept.entries, &mut gone_node.entries);
+            append(&mut kept.children, &mut gone_node.children);
+            Node::deallocate_memory(gone);
+            node = parent;
+        }
+    }
+}
+
+impl<K, V, const ORDER: usize> BTreeMap<K, V, ORDER>
+where
+    K: Ord,
+{
+    /// put `value` under `key` and give back the value it replaces
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.root.is_null() {
+            let mut root = Node::new();
+            root.entries.push_back((key, value));
+            self.root = Node::allocate_memory(root);
+            self.len = 1;
+            return None;
+        }
+        let mut path = Vector::new();
+        let mut node = self.root;
+        unsafe {
+            loop {
+                let current = &mut *node;
+                match current.search(&key) {
+                    Ok(i) => return Some(mem::replace(&mut current.entries[i].1, value)),
+                    Err(i) if current.is_leaf() => {
+                        current.entries.insert_at(i, (key, value));
+                        break;
+                    },
+                    Err(i) => {
+                        path.push_back((node, i));
+                        node = current.children[i];
+                    },
+                }
+            }
+            self.len += 1;
+            while (*node).entries.len() > Self::MAX_ENTRIES {
+                let (median, right) = Self::split(node);
+                match path.pop_back() {
+                    Some((parent, i)) => {
+                        (*parent).entries.insert_at(i, median);
+                        (*parent).children.insert_at(i + 1, right);
+                        node = parent;
+                    },
+                    None => {
+                        let mut root = Node::new();
+                        root.entries.push_back(median);
+                        root.children.push_back(node);
+                        root.children.push_back(right);
+                        self.root = Node::allocate_memory(root);
+                    },
+                }
+            }
+        }
+        None
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let (node, i) = self.find(key)?;
+        unsafe { Some(&(&*node).entries[i].1) }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let (node, i) = self.find(key)?;
+        unsafe { Some(&mut (&mut *node).entries[i].1) }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// take `key` out of the map. An entry of an inner node is replaced by its predecessor, the
+    /// largest entry of the leaf at the end of its left subtree, so the entry always leaves a
+    /// leaf, which is then mended by borrowing or merging
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::b_tree_map_struct::BTreeMap;
+    /// let mut map: BTreeMap<u32, (), 3> = (1..=7).map(|key| (key, ())).collect();
+    /// assert_eq!(format!("{}", map), "[4]\n[2] [6]\n[1] [3] [5] [7]\n");
+    /// map.remove(&4);
+    /// assert_eq!(format!("{}", map), "[3 6]\n[1 2] [5] [7]\n");
+    /// map.remove(&6);
+    /// assert_eq!(format!("{}", map), "[2 5]\n[1] [3] [7]\n");
+    /// assert!(map.check_invariants());
+    /// ```
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let mut path = Vector::new();
+        let mut node = self.root;
+        unsafe {
+            let i = loop {
+                let current = node.as_ref()?;
+                match current.search(key) {
+                    Ok(i) => break i,
+                    Err(_) if current.is_leaf() => return None,
+                    Err(i) => {
+                        path.push_back((node, i));
+                        node = current.children[i];
+                    },
+                }
+            };
+            let found = &mut *node;
+            let removed = if found.is_leaf() {
+                found.entries.remove_at(i).expect("found above")
+            } else {
+                path.push_back((node, i));
+                let mut leaf = found.children[i];
+                while !(*leaf).is_leaf() {
+                    let last = (*leaf).children.len() - 1;
+                    path.push_back((leaf, last));
+                    leaf = (&*leaf).children[last];
+                }
+                let predecessor = (*leaf).entries.pop_back().expect("a leaf holds entries");
+                let removed = mem::replace(&mut found.entries[i], predecessor);
+                node = leaf;
+                removed
+            };
+            self.len -= 1;
+            self.rebalance(node, path);
+            Some(removed)
+        }
+    }
+
+    /// entries with a key in `range` in key order, a range whose start is above its end is empty
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::b_tree_map_struct::BTreeMap;
+    /// let map: BTreeMap<u32, u32, 4> = (0..100).map(|i| (i * 2, i)).collect();
+    /// assert_eq!(map.range(9..=14).map(|(key, _)| *key).collect::<Vec<_>>(), vec![10, 12, 14]);
+    /// assert_eq!(map.range(199..).count(), 0);
+    /// ```
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let mut end = None;
+        let mut node = self.root;
+        while let Some(current) = unsafe { node.as_ref() } {
+            let below = match range.end_bound() {
+                Bound::Included(end) => current.rank(end, true),
+                Bound::Excluded(end) => current.rank(end, false),
+                Bound::Unbounded => current.entries.len(),
+            };
+            if below > 0 {
+                end = Some((node, below - 1));
+            }
+            node = if current.is_leaf() { ptr::null_mut() } else { current.children[below] };
+        }
+
+        let mut walk = Iter { stack: Vector::new(), end, marker: PhantomData };
+        let mut node = self.root;
+        while let Some(current) = unsafe { node.as_ref() } {
+            let skipped = match range.start_bound() {
+                Bound::Included(start) => current.rank(start, false),
+                Bound::Excluded(start) => current.rank(start, true),
+                Bound::Unbounded => 0,
+            };
+            walk.stack.push_back((node, skipped));
+            node = if current.is_leaf() { ptr::null_mut() } else { current.children[skipped] };
+        }
+        let crossed = match (walk.peek(), walk.end) {
+            (Some((first, _)), Some((node, i))) => *first > unsafe { &(&*node).entries[i] }.0,
+            _ => true,
+        };
+        if crossed {
+            walk.stack = Vector::new();
+        }
+        walk
+    }
+
+    /// build the map from entries sorted by key in one pass, every node is filled about evenly
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::b_tree_map_struct::BTreeMap;
+    /// let map: BTreeMap<u32, (), 5> = BTreeMap::from_sorted((0..20).map(|key| (key, ())));
+    /// assert_eq!(format!("{}", map), "[4 8 12 16]\n[0 1 2 3] [5 6 7] [9 10 11] [13 14 15] [17 18 19]\n");
+    /// assert!(map.check_invariants());
+    /// ```
+    /// # Panics
+    /// when the keys are not strictly increasing
+    pub fn from_sorted<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let entries: Vec<(K, V)> = entries.into_iter().collect();
+        assert!(
+            entries.windows(2).all(|pair| pair[0].0 < pair[1].0),
+            "bulk loading needs strictly increasing keys"
+        );
+        let mut map = Self::new();
+        if entries.is_empty() {
+            return map;
+        }
+        map.len = entries.len();
+        let mut source = entries.into_iter();
+
+        // the fewest leaves that hold the entries with one separator between each two of them
+        let leaf_count = (map.len + ORDER) / ORDER;
+        let in_leaves = map.len - (leaf_count - 1);
+        let mut nodes = Vec::new();
+        let mut separators = Vec::new();
+        for j in 0..leaf_count {
+            let mut leaf = Node::new();
+            for _ in 0..in_leaves / leaf_count + usize::from(j < in_leaves % leaf_count) {
+                leaf.entries.push_back(source.next().expect("counted above"));
+            }
+            nodes.push(Node::allocate_memory(leaf));
+            if j + 1 < leaf_count {
+                separators.push(source.next().expect("counted above"));
+            }
+        }
+        // every level groups the nodes below it under the fewest parents that hold them
+        while nodes.len() > 1 {
+            let child_count = nodes.len();
+            let parent_count = child_count.div_ceil(ORDER);
+            let mut children = nodes.into_iter();
+            let mut below = separators.into_iter();
+            nodes = Vec::new();
+            separators = Vec::new();
+            for j in 0..parent_count {
+                let mut parent = Node::new();
+                let size = child_count / parent_count + usize::from(j < child_count % parent_count);
+                for t in 0..size {
+                    parent.children.push_back(children.next().expect("counted above"));
+                    if t + 1 < size {
+                        parent.entries.push_back(below.next().expect("counted above"));
+                    }
+                }
+                nodes.push(Node::allocate_memory(parent));
+                if j + 1 < parent_count {
+                    separators.push(below.next().expect("counted above"));
+                }
+            }
+        }
+        map.root = nodes[0];
+        map
+    }
+
+    /// check the order of the keys, the number of entries and children of every node and that
+    /// every leaf is at the same depth
+    pub fn check_invariants(&self) -> bool {
+        // node, its depth and the tightest keys it has to stay between
+        let mut node_stack = Vector::new();
+        if !self.root.is_null() {
+            node_stack.push_back((self.root, 0, None::<&K>, None::<&K>));
+        }
+        let mut leaf_depth = None;
+        let mut count = 0;
+        while let Some((node_ptr, depth, low, high)) = node_stack.pop_back() {
+            let node = unsafe { &*node_ptr };
+            let entries = node.entries.len();
+            count += entries;
+            let least = if node_ptr == self.root { 1 } else { Self::MIN_ENTRIES };
+            if entries < least || entries > Self::MAX_ENTRIES {
+                return false;
+            }
+            for i in 0..entries {
+                let key = &node.entries[i].0;
+                let above_low = if i == 0 {
+                    low.is_none_or(|low| key > low)
+                } else {
+                    key > &node.entries[i - 1].0
+                };
+                if !above_low || high.is_some_and(|high| key >= high) {
+                    return false;
+                }
+            }
+            if node.is_leaf() {
+                if *leaf_depth.get_or_insert(depth) != depth {
+                    return false;
+                }
+                continue;
+            }
+            if node.children.len() != entries + 1 {
+                return false;
+            }
+            for i in 0..=entries {
+                let low = if i == 0 { low } else { Some(&node.entries[i - 1].0) };
+                let high = if i == entries { high } else { Some(&node.entries[i].0) };
+                node_stack.push_back((node.children[i], depth + 1, low, high));
+            }
+        }
+        count == self.len
+    }
+
+    fn find(&self, key: &K) -> Option<(*mut Node<K, V>, usize)> {
+        let mut node = self.root;
+        while let Some(current) = unsafe { node.as_ref() } {
+            match current.search(key) {
+                Ok(i) => return Some((node, i)),
+                Err(_) if current.is_leaf() => return None,
+                Err(i) => node = current.children[i],
+            }
+        }
+        None
+    }
+}
+
+/// iterator of [`BTreeMap::iter`] and [`BTreeMap::range`]
+pub struct Iter<'a, K, V> {
+    // nodes on the way down to the next entry with the index of their next entry
+    stack: Vector<(*mut Node<K, V>, usize)>,
+    // node and index of the last entry to give
+    end: Option<(*mut Node<K, V>, usize)>,
+    marker: PhantomData<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn descend_first(&mut self, mut node: *mut Node<K, V>) {
+        while let Some(current) = unsafe { node.as_ref() } {
+            self.stack.push_back((node, 0));
+            node = if current.is_leaf() { ptr::null_mut() } else { current.children[0] };
+        }
+    }
+
+    // drop the nodes whose entries are all given and look at the next entry
+    fn peek(&mut self) -> Option<&'a (K, V)> {
+        loop {
+            let (node, i) = self.stack[self.stack.len().checked_sub(1)?];
+            let node = unsafe { &*node };
+            if i < node.entries.len() {
+                return Some(&node.entries[i]);
+            }
+            self.stack.pop_back();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.peek()?;
+        let top = self.stack.len() - 1;
+        let (node, i) = self.stack[top];
+        if self.end == Some((node, i)) {
+            while self.stack.pop_back().is_some() {}
+        } else {
+            self.stack[top].1 = i + 1;
+            let current = unsafe { &*node };
+            if !current.is_leaf() {
+                self.descend_first(current.children[i + 1]);
+            }
+        }
+        Some((key, value))
+    }
+}
+
+impl<'a, K, V, const ORDER: usize> IntoIterator for &'a BTreeMap<K, V, ORDER> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, const ORDER: usize> core::iter::FromIterator<(K, V)> for BTreeMap<K, V, ORDER>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = BTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, const ORDER: usize> Extend<(K, V)> for BTreeMap<K, V, ORDER>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, const ORDER: usize> Default for BTreeMap<K, V, ORDER> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const ORDER: usize> Drop for BTreeMap<K, V, ORDER> {
+    fn drop(&mut self) {
+        let mut node_stack = Vector::new();
+        if !self.root.is_null() {
+            node_stack.push_back(self.root);
+        }
+        while let Some(node_ptr) = node_stack.pop_back() {
+            let mut node = unsafe { Node::deallocate_memory(node_ptr) };
+            while let Some(child) = node.children.pop_back() {
+                node_stack.push_back(child);
+            }
+        }
+    }
+}
+
+impl<K, V, const ORDER: usize> std::fmt::Display for BTreeMap<K, V, ORDER>
+where
+    K: std::fmt::Display,
+{
+    /// one line per level with the keys of every node in brackets
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut out = String::new();
+        let mut node_queue = Vector::new();
+        if !self.root.is_null() {
+            node_queue.push_back((self.root, 0));
+        }
+        let mut level = 0;
+        while let Some((node_ptr, depth)) = node_queue.pop_front() {
+            let node = unsafe { &*node_ptr };
+            if depth != level {
+                out.push('\n');
+                level = depth;
+            } else if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push('[');
+            for i in 0..node.entries.len() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                out.push_str(&node.entries[i].0.to_string());
+            }
+            out.push(']');
+            for i in 0..node.children.len() {
+                node_queue.push_back((node.children[i], depth + 1));
+            }
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        write!(f, "{}", out)
+    }
+}
+
+impl<K, V, const ORDER: usize> std::fmt::Debug for BTreeMap<K, V, ORDER>
+where
+    K: std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
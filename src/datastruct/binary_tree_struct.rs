@@ -0,0 +1,718 @@
+//! binary tree written `BinaryTree<T>`
+//!
+//! unlike [`crate::datastruct::tree_struct::Tree`] every member has an explicit left and right
+//! child, so a member with a right child only is not the same as a member with a left child only.
+//! A tree is rebuilt from two traversal sequences (preorder or postorder together with inorder),
+//! from a level order listing with `None` for the missing children, or from the tree grammar of
+//! [`crate::datastruct::tree_struct::Tree::init_tree`] with at most two children per member where
+//! an empty label stands for a missing child. Every walk uses an explicit stack or queue.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::binary_tree_struct::BinaryTree;
+//! let tree = BinaryTree::from_pre_in(&[1, 2, 4, 3], &[4, 2, 1, 3]).unwrap();
+//! assert_eq!(format!("{}", tree), "╚═1\n  ╠═L 2\n  ║ ╚═L 4\n  ╚═R 3\n");
+//! assert_eq!(format!("{:?}", tree), "1 2 3 4");
+//! let tree: BinaryTree<u8> = BinaryTree::init_tree("1;2;;3;1;4").unwrap();
+//! assert_eq!(format!("{}", tree), "╚═1\n  ╚═R 3\n    ╚═L 4\n");
+//! assert_eq!(format!("{:?}", tree), "1 ✘ 3 4");
+//! ```
+
+use crate::datastruct::tree_struct::{
+    escape, join_tokens, parse_count, render_tree, tokenize, TreeParseError, TreeParseErrorKind,
+};
+use crate::datastruct::vec_struct::Vector;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr;
+use core::str::FromStr;
+use std::alloc;
+
+const PHI: &str = "✘";
+
+pub struct BinaryTree<T> {
+    root: *mut Member<T>,
+}
+
+struct Member<T> {
+    data: T,
+    left: *mut Member<T>,
+    right: *mut Member<T>,
+}
+
+impl<T> Member<T> {
+    fn new(element: T) -> Self {
+        Self { data: element, left: ptr::null_mut(), right: ptr::null_mut() }
+    }
+
+    fn allocate_memory(candidate: Member<T>) -> *mut Member<T> {
+        let size = mem::size_of::<Member<T>>();
+        let align = mem::align_of::<Member<T>>();
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            let ptr = alloc::alloc(layout) as *mut Member<T>;
+            ptr.write(candidate);
+            ptr
+        }
+    }
+
+    unsafe fn deallocate_memory(node: *mut Member<T>) -> Member<T> {
+        let size = mem::size_of::<Member<T>>();
+        let align = mem::align_of::<Member<T>>();
+        let member = node.read();
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(node as *mut u8, layout);
+        member
+    }
+
+    // hang `node` below `parent`, on the right when `right`
+    unsafe fn attach(parent: *mut Member<T>, node: *mut Member<T>, right: bool) {
+        if right {
+            (*parent).right = node;
+        } else {
+            (*parent).left = node;
+        }
+    }
+}
+
+/// order in which [`BinaryTree::traverse`] visits the members
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryTraversal {
+    PreOrder,
+    InOrder,
+    PostOrder,
+    LevelOrder,
+}
+
+impl<T> BinaryTree<T> {
+    pub fn new() -> Self {
+        Self { root: ptr::null_mut() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_null()
+    }
+
+    pub fn root(&self) -> Option<&T> {
+        unsafe { self.root.as_ref().map(|node| &node.data) }
+    }
+
+    /// number of members
+    pub fn size(&self) -> usize {
+        self.preorder().count()
+    }
+
+    /// number of levels, the empty tree has none
+    pub fn height(&self) -> usize {
+        self.preorder().map(|(depth, _)| depth + 1).max().unwrap_or(0)
+    }
+
+    /// rebuild the tree listed by `level_order`, breadth first with `None` for every missing
+    /// child of a present member. Missing children at the end may be left out.
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::{BinaryTree, TraversalError};
+    /// let tree = BinaryTree::from_level_order(&[Some(1), None, Some(3), Some(4)]).unwrap();
+    /// assert_eq!(tree.to_level_order(), vec![Some(&1), None, Some(&3), Some(&4)]);
+    /// let error = BinaryTree::from_level_order(&[Some(1), None, None, Some(4)]).unwrap_err();
+    /// assert_eq!(error, TraversalError::Orphan { index: 3 });
+    /// ```
+    /// # Errors
+    /// [`TraversalError::Orphan`] when an entry has no parent left to hang below
+    pub fn from_level_order(level_order: &[Option<T>]) -> Result<Self, TraversalError>
+    where
+        T: Clone,
+    {
+        let mut tree = BinaryTree::new();
+        let orphan = |from: usize| {
+            (from..level_order.len())
+                .find(|&i| level_order[i].is_some())
+                .map(|index| TraversalError::Orphan { index })
+        };
+        let root = match level_order.first() {
+            Some(Some(root)) => root.clone(),
+            _ => return orphan(0).map_or(Ok(tree), Err),
+        };
+        tree.root = Member::allocate_memory(Member::new(root));
+        let mut member_queue = Vector::new();
+        member_queue.push_back(tree.root);
+        let mut i = 1;
+        while i < level_order.len() {
+            let parent = match member_queue.pop_front() {
+                Some(parent) => parent,
+                None => return orphan(i).map_or(Ok(tree), Err),
+            };
+            for side in 0..2 {
+                if let Some(Some(element)) = level_order.get(i + side) {
+                    let node = Member::allocate_memory(Member::new(element.clone()));
+                    unsafe { Member::attach(parent, node, side == 1) }
+                    member_queue.push_back(node);
+                }
+            }
+            i += 2;
+        }
+        Ok(tree)
+    }
+
+    /// breadth first listing read by [`BinaryTree::from_level_order`], without the trailing
+    /// missing children
+    pub fn to_level_order(&self) -> Vec<Option<&T>> {
+        let mut listing = Vec::new();
+        let mut member_queue = Vector::new();
+        member_queue.push_back(self.root);
+        while let Some(candidate_ptr) = member_queue.pop_front() {
+            match unsafe { candidate_ptr.as_ref() } {
+                None => listing.push(None),
+                Some(node) => {
+                    listing.push(Some(&node.data));
+                    member_queue.push_back(node.left);
+                    member_queue.push_back(node.right);
+                },
+            }
+        }
+        while let Some(None) = listing.last() {
+            listing.pop();
+        }
+        listing
+    }
+
+    /// walk the tree in the given order, every item is the depth of the member (the root is at
+    /// depth 0) and its data
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::{BinaryTraversal, BinaryTree};
+    /// let tree = BinaryTree::from_level_order(&[Some('a'), Some('b'), Some('c'), None, Some('d')]).unwrap();
+    /// let walk = |order| tree.traverse(order).map(|(_, c)| *c).collect::<String>();
+    /// assert_eq!(walk(BinaryTraversal::PreOrder), "abdc");
+    /// assert_eq!(walk(BinaryTraversal::InOrder), "bdac");
+    /// assert_eq!(walk(BinaryTraversal::PostOrder), "dbca");
+    /// assert_eq!(walk(BinaryTraversal::LevelOrder), "abcd");
+    /// ```
+    pub fn traverse(&self, order: BinaryTraversal) -> Iter<'_, T> {
+        let mut pending = Vector::new();
+        if !self.root.is_null() {
+            pending.push_back((self.root, 0, false));
+        }
+        Iter { order, pending, marker: PhantomData }
+    }
+
+    pub fn preorder(&self) -> Iter<'_, T> {
+        self.traverse(BinaryTraversal::PreOrder)
+    }
+
+    pub fn inorder(&self) -> Iter<'_, T> {
+        self.traverse(BinaryTraversal::InOrder)
+    }
+
+    pub fn postorder(&self) -> Iter<'_, T> {
+        self.traverse(BinaryTraversal::PostOrder)
+    }
+
+    pub fn level_order(&self) -> Iter<'_, T> {
+        self.traverse(BinaryTraversal::LevelOrder)
+    }
+
+    /// swap the children of every member
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::BinaryTree;
+    /// let mut tree: BinaryTree<u8> = BinaryTree::init_tree("1;2;2;3;1;4").unwrap();
+    /// tree.mirror();
+    /// assert_eq!(tree.to_tree_code(), "1;2;3;2;0;2;;4");
+    /// ```
+    pub fn mirror(&mut self) {
+        let mut member_stack = Vector::new();
+        member_stack.push_back(self.root);
+        while let Some(candidate_ptr) = member_stack.pop_back() {
+            if let Some(node) = unsafe { candidate_ptr.as_mut() } {
+                mem::swap(&mut node.left, &mut node.right);
+                member_stack.push_back(node.left);
+                member_stack.push_back(node.right);
+            }
+        }
+    }
+
+    /// tell whether the tree is its own mirror image
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::BinaryTree;
+    /// let tree: BinaryTree<u8> = BinaryTree::init_tree("1;2;2;2;1;3;2;;3").unwrap();
+    /// assert!(tree.is_symmetric());
+    /// let tree: BinaryTree<u8> = BinaryTree::init_tree("1;2;2;2;1;3;1;3").unwrap();
+    /// assert!(!tree.is_symmetric());
+    /// ```
+    pub fn is_symmetric(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        let root = match unsafe { self.root.as_ref() } {
+            None => return true,
+            Some(root) => root,
+        };
+        let mut pairs = Vector::new();
+        pairs.push_back((root.left, root.right));
+        while let Some((one, two)) = pairs.pop_back() {
+            match unsafe { (one.as_ref(), two.as_ref()) } {
+                (None, None) => continue,
+                (Some(one), Some(two)) if one.data == two.data => {
+                    pairs.push_back((one.left, two.right));
+                    pairs.push_back((one.right, two.left));
+                },
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// tell whether the heights of the two subtrees of every member differ by one at most
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::BinaryTree;
+    /// let tree: BinaryTree<u8> = BinaryTree::init_tree("1;2;2;3;1;4").unwrap();
+    /// assert!(tree.is_balanced());
+    /// let tree: BinaryTree<u8> = BinaryTree::init_tree("1;1;2;1;3").unwrap();
+    /// assert!(!tree.is_balanced());
+    /// ```
+    pub fn is_balanced(&self) -> bool {
+        // heights of the finished subtrees, the children of a member sit on top when it is reached
+        let mut heights: Vector<usize> = Vector::new();
+        let mut walk = self.postorder();
+        while let Some(candidate_ptr) = walk.next_member() {
+            let node = unsafe { &*candidate_ptr };
+            let right = if node.right.is_null() { 0 } else { heights.pop_back().expect("visited") };
+            let left = if node.left.is_null() { 0 } else { heights.pop_back().expect("visited") };
+            if left.max(right) - left.min(right) > 1 {
+                return false;
+            }
+            heights.push_back(left.max(right) + 1);
+        }
+        true
+    }
+}
+
+impl<T> BinaryTree<T>
+where
+    T: Clone + PartialEq,
+{
+    /// rebuild the tree from its preorder and inorder sequences
+    /// # Errors
+    /// [`TraversalError::LengthMismatch`] for sequences of different lengths and
+    /// [`TraversalError::Inconsistent`] when no binary tree has both sequences
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::{BinaryTree, TraversalError};
+    /// let error = BinaryTree::from_pre_in(&[1, 2, 3], &[3, 1, 2]).unwrap_err();
+    /// assert_eq!(error, TraversalError::Inconsistent);
+    /// ```
+    pub fn from_pre_in(preorder: &[T], inorder: &[T]) -> Result<Self, TraversalError> {
+        let tree = Self::from_outer_in(
+            preorder.iter(),
+            inorder.iter(),
+            preorder.len(),
+            inorder.len(),
+            false,
+        )?;
+        tree.check(BinaryTraversal::PreOrder, preorder, inorder)
+    }
+
+    /// rebuild the tree from its postorder and inorder sequences
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::BinaryTree;
+    /// let tree = BinaryTree::from_post_in(&[4, 2, 3, 1], &[2, 4, 1, 3]).unwrap();
+    /// assert_eq!(tree.preorder().map(|(_, v)| *v).collect::<Vec<_>>(), vec![1, 2, 4, 3]);
+    /// ```
+    /// # Errors
+    /// as [`BinaryTree::from_pre_in`]
+    pub fn from_post_in(postorder: &[T], inorder: &[T]) -> Result<Self, TraversalError> {
+        let tree = Self::from_outer_in(
+            postorder.iter().rev(),
+            inorder.iter().rev(),
+            postorder.len(),
+            inorder.len(),
+            true,
+        )?;
+        tree.check(BinaryTraversal::PostOrder, postorder, inorder)
+    }
+
+    // stack construction from a preorder and an inorder sequence, the reversed postorder and
+    // reversed inorder sequences are the same problem with the two sides exchanged
+    fn from_outer_in<'a, O, I>(
+        mut outer: O,
+        mut inorder: I,
+        outer_len: usize,
+        inorder_len: usize,
+        mirrored: bool,
+    ) -> Result<Self, TraversalError>
+    where
+        T: 'a,
+        O: Iterator<Item = &'a T>,
+        I: Iterator<Item = &'a T>,
+    {
+        let mut tree = BinaryTree::new();
+        if outer_len != inorder_len {
+            return Err(TraversalError::LengthMismatch);
+        }
+        let root = match outer.next() {
+            None => return Ok(tree),
+            Some(root) => root.clone(),
+        };
+        tree.root = Member::allocate_memory(Member::new(root));
+        let mut member_stack = Vector::new();
+        member_stack.push_back(tree.root);
+        let mut next_inorder = inorder.next();
+        for element in outer {
+            let node = Member::allocate_memory(Member::new(element.clone()));
+            let top = member_stack[member_stack.len() - 1];
+            unsafe {
+                if Some(&(*top).data) != next_inorder {
+                    Member::attach(top, node, mirrored);
+                } else {
+                    let mut parent = top;
+                    while let Some(last) = member_stack.len().checked_sub(1) {
+                        if Some(&(*member_stack[last]).data) != next_inorder {
+                            break;
+                        }
+                        parent = member_stack.pop_back().expect("checked above");
+                        next_inorder = inorder.next();
+                    }
+                    Member::attach(parent, node, !mirrored);
+                }
+            }
+            member_stack.push_back(node);
+        }
+        Ok(tree)
+    }
+
+    // the construction trusts the sequences, walking the result again catches the bad ones
+    fn check(
+        self,
+        order: BinaryTraversal,
+        outer: &[T],
+        inorder: &[T],
+    ) -> Result<Self, TraversalError> {
+        let same =
+            |order, expected: &[T]| self.traverse(order).map(|(_, data)| data).eq(expected.iter());
+        if !same(order, outer) || !same(BinaryTraversal::InOrder, inorder) {
+            return Err(TraversalError::Inconsistent);
+        }
+        Ok(self)
+    }
+}
+
+impl<T> BinaryTree<T>
+where
+    T: FromStr,
+{
+    /// read the grammar of [`crate::datastruct::tree_struct::Tree::init_tree`] with at most two
+    /// children per member. A single child is the left one and an empty label stands for a
+    /// missing child, so `1;2;;3` is `1` with a right child `3`.
+    /// # Errors
+    /// the errors of the tree grammar and [`TreeParseErrorKind::TooManyChildren`]
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::BinaryTree;
+    /// # use dsa_sport::datastruct::tree_struct::TreeParseErrorKind;
+    /// let error = BinaryTree::<u8>::init_tree("1;3;2;3;4").unwrap_err();
+    /// assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::TooManyChildren, 1));
+    /// ```
+    pub fn init_tree(tree_code: &str) -> Result<Self, TreeParseError> {
+        let mut tree = BinaryTree::new();
+        let mut tokens = tokenize(tree_code);
+        if tokens.is_empty() {
+            return Ok(tree);
+        }
+
+        let mut index = 0;
+        let root = match Self::parse_label(tokens.pop_front(), &mut index)? {
+            Some(root) => root,
+            None => return Err(TreeParseError::new(TreeParseErrorKind::EmptyLabel, 0)),
+        };
+        tree.root = Member::allocate_memory(Member::new(root));
+        let mut member_queue = Vector::new();
+        member_queue.push_back(tree.root);
+
+        while let Some(candidate_ptr) = member_queue.pop_front() {
+            if tokens.is_empty() {
+                break;
+            }
+            let count_index = index;
+            let stem_size = parse_count(tokens.pop_front(), &mut index)?;
+            if stem_size > 2 {
+                return Err(TreeParseError::new(TreeParseErrorKind::TooManyChildren, count_index));
+            }
+            for side in 0..stem_size {
+                let node = match Self::parse_label(tokens.pop_front(), &mut index)? {
+                    Some(node) => Member::allocate_memory(Member::new(node)),
+                    None => continue,
+                };
+                unsafe { Member::attach(candidate_ptr, node, side == 1) }
+                member_queue.push_back(node);
+            }
+        }
+        if !tokens.is_empty() {
+            return Err(TreeParseError::new(TreeParseErrorKind::TrailingTokens, index));
+        }
+        Ok(tree)
+    }
+
+    // `None` for the empty label of a missing child
+    fn parse_label(token: Option<String>, index: &mut usize) -> Result<Option<T>, TreeParseError> {
+        let label = match token {
+            None => return Err(TreeParseError::new(TreeParseErrorKind::Truncated, *index)),
+            Some(label) => label,
+        };
+        if label.is_empty() {
+            *index += 1;
+            return Ok(None);
+        }
+        let label = label
+            .parse::<T>()
+            .map_err(|_| TreeParseError::new(TreeParseErrorKind::BadLabel, *index))?;
+        *index += 1;
+        Ok(Some(label))
+    }
+}
+
+impl<T> BinaryTree<T>
+where
+    T: std::fmt::Display,
+{
+    /// write the tree back in the grammar of [`BinaryTree::init_tree`]
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::binary_tree_struct::BinaryTree;
+    /// let tree = BinaryTree::from_level_order(&[Some(1), None, Some(3), Some(4)]).unwrap();
+    /// assert_eq!(tree.to_tree_code(), "1;2;;3;1;4");
+    /// ```
+    /// # Panics
+    /// panics if a label is displayed as the empty string, the code of a missing child
+    pub fn to_tree_code(&self) -> String {
+        let mut tokens = Vector::new();
+        if !self.root.is_null() {
+            let mut member_queue = Vector::new();
+            member_queue.push_back(self.root);
+            tokens.push_back((escape(unsafe { &(*self.root).data }), false));
+            while let Some(candidate_ptr) = member_queue.pop_front() {
+                let node = unsafe { &*candidate_ptr };
+                let stem_size = if !node.right.is_null() {
+                    2
+                } else if !node.left.is_null() {
+                    1
+                } else {
+                    0
+                };
+                tokens.push_back((stem_size.to_string(), true));
+                for &child in [node.left, node.right].iter().take(stem_size) {
+                    match unsafe { child.as_ref() } {
+                        None => tokens.push_back((String::new(), false)),
+                        Some(child_node) => {
+                            tokens.push_back((escape(&child_node.data), false));
+                            member_queue.push_back(child);
+                        },
+                    }
+                }
+            }
+        }
+        join_tokens(tokens)
+    }
+}
+
+/// error of [`BinaryTree::from_pre_in`], [`BinaryTree::from_post_in`] and
+/// [`BinaryTree::from_level_order`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalError {
+    /// the two sequences do not have the same length
+    LengthMismatch,
+    /// no binary tree has both sequences
+    Inconsistent,
+    /// the level order entry at `index` has no member left to be a child of
+    Orphan { index: usize },
+}
+
+impl std::fmt::Display for TraversalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraversalError::LengthMismatch => write!(f, "traversal sequences differ in length"),
+            TraversalError::Inconsistent => {
+                write!(f, "traversal sequences do not describe one tree")
+            },
+            TraversalError::Orphan { index } => {
+                write!(f, "level order entry {} has no parent", index)
+            },
+        }
+    }
+}
+
+impl std::error::Error for TraversalError {}
+
+/// iterator of [`BinaryTree::traverse`] yielding `(depth, &data)`
+pub struct Iter<'a, T> {
+    order: BinaryTraversal,
+    // members with their depth and whether their children are already scheduled
+    pending: Vector<(*mut Member<T>, usize, bool)>,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn next_member(&mut self) -> Option<*mut Member<T>> {
+        self.next_entry().map(|(_, node)| node)
+    }
+
+    fn next_entry(&mut self) -> Option<(usize, *mut Member<T>)> {
+        if self.order == BinaryTraversal::LevelOrder {
+            let (candidate_ptr, depth, _) = self.pending.pop_front()?;
+            let node = unsafe { &*candidate_ptr };
+            self.schedule(node.left, depth + 1);
+            self.schedule(node.right, depth + 1);
+            return Some((depth, candidate_ptr));
+        }
+        loop {
+            let (candidate_ptr, depth, expanded) = self.pending.pop_back()?;
+            let node = unsafe { &*candidate_ptr };
+            match (self.order, expanded) {
+                (BinaryTraversal::PreOrder, _) => {
+                    self.schedule(node.right, depth + 1);
+                    self.schedule(node.left, depth + 1);
+                    return Some((depth, candidate_ptr));
+                },
+                (_, true) => return Some((depth, candidate_ptr)),
+                (BinaryTraversal::InOrder, false) => {
+                    self.schedule(node.right, depth + 1);
+                    self.pending.push_back((candidate_ptr, depth, true));
+                    self.schedule(node.left, depth + 1);
+                },
+                (_, false) => {
+                    self.pending.push_back((candidate_ptr, depth, true));
+                    self.schedule(node.right, depth + 1);
+                    self.schedule(node.left, depth + 1);
+                },
+            }
+        }
+    }
+
+    fn schedule(&mut self, node: *mut Member<T>, depth: usize) {
+        if !node.is_null() {
+            self.pending.push_back((node, depth, false));
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.next_entry()?;
+        Some((depth, unsafe { &(*node).data }))
+    }
+}
+
+impl<T> Default for BinaryTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for BinaryTree<T> {
+    fn drop(&mut self) {
+        let mut member_stack = Vector::new();
+        member_stack.push_back(self.root);
+        while let Some(candidate_ptr) = member_stack.pop_back() {
+            if !candidate_ptr.is_null() {
+                let member = unsafe { Member::deallocate_memory(candidate_ptr) };
+                member_stack.push_back(member.left);
+                member_stack.push_back(member.right);
+            }
+        }
+    }
+}
+
+impl<T> PartialEq for BinaryTree<T>
+where
+    T: PartialEq,
+{
+    /// same shape, same sides and same labels
+    fn eq(&self, other: &Self) -> bool {
+        let mut pairs = Vector::new();
+        pairs.push_back((self.root, other.root));
+        while let Some((one, two)) = pairs.pop_back() {
+            match unsafe { (one.as_ref(), two.as_ref()) } {
+                (None, None) => continue,
+                (Some(one), Some(two)) if one.data == two.data => {
+                    pairs.push_back((one.left, two.left));
+                    pairs.push_back((one.right, two.right));
+                },
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl<T> Eq for BinaryTree<T> where T: Eq {}
+
+impl<T> std::fmt::Display for BinaryTree<T>
+where
+    T: std::fmt::Display,
+{
+    /// the glyphs of [`crate::datastruct::tree_struct::Tree`], children are marked `L` or `R`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let root = if self.root.is_null() { None } else { Some(self.root) };
+        let out = render_binary_tree(
+            root,
+            |candidate_ptr| {
+                let node = unsafe { &*candidate_ptr };
+                (
+                    Some(node.left).filter(|left| !left.is_null()),
+                    Some(node.right).filter(|right| !right.is_null()),
+                )
+            },
+            |candidate_ptr| unsafe { (*candidate_ptr).data.to_string() },
+        );
+        write!(f, "{}", out)
+    }
+}
+
+impl<T> std::fmt::Debug for BinaryTree<T>
+where
+    T: std::fmt::Display,
+{
+    /// level order listing with `✘` for a missing child
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let listing = self.to_level_order();
+        let mut out = String::new();
+        for (i, entry) in listing.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            match entry {
+                Some(data) => out.push_str(&data.to_string()),
+                None => out.push_str(PHI),
+            }
+        }
+        write!(f, "{}", out)
+    }
+}
+
+// draw a binary tree with the glyphs of the n-ary tree, `sides` gives the left and the right child
+// of a member and every child is marked `L` or `R` before its label
+pub(crate) fn render_binary_tree<N, C, L>(root: Option<N>, mut sides: C, mut label: L) -> String
+where
+    N: Copy,
+    C: FnMut(N) -> (Option<N>, Option<N>),
+    L: FnMut(N) -> String,
+{
+    render_tree(
+        root.map(|root| (root, "")),
+        |(node, _)| {
+            let mut children = Vector::new();
+            let (left, right) = sides(node);
+            if let Some(left) = left {
+                children.push_back((left, "L "));
+            }
+            if let Some(right) = right {
+                children.push_back((right, "R "));
+            }
+            children
+        },
+        |(node, side)| format!("{}{}", side, label(node)),
+    )
+}
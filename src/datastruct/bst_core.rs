@@ -0,0 +1,526 @@
+//! parent-linked binary search tree shared by the search tree maps
+//!
+//! [`crate::datastruct::bst_map_struct::BstMap`], [`crate::datastruct::avl_map_struct::AvlMap`]
+//! and [`crate::datastruct::rb_tree_map_struct::RbTreeMap`] keep their entries in the same kind of
+//! member, a key with its value, links to both children and to the parent, and the balance data
+//! `X` of the map: nothing, a height or a colour. The searches, the in-order walks, the rotations,
+//! cutting a member out and freeing the tree are written once here, the maps add the rebalancing
+//! on top. Members keep a link to their parent, the in-order walks step from a member to the next
+//! one without any stack.
+//!
+//! Balance data wrapped in `Counted` also keeps the size of the subtree, which the rotations
+//! refresh, and [`crate::datastruct::order_stat_tree_struct::OrderStatTree`] answers its rank
+//! queries from it.
+
+use crate::datastruct::binary_tree_struct::render_binary_tree;
+use crate::datastruct::vec_struct::Vector;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use core::ptr;
+use std::alloc;
+
+pub(crate) struct Core<K, V, X> {
+    pub(crate) root: *mut Member<K, V, X>,
+    pub(crate) len: usize,
+}
+
+pub(crate) struct Member<K, V, X> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+    pub(crate) left: *mut Member<K, V, X>,
+    pub(crate) right: *mut Member<K, V, X>,
+    pub(crate) parent: *mut Member<K, V, X>,
+    pub(crate) extra: X,
+}
+
+// link to a member, null for a missing one
+pub(crate) type Link<K, V, X> = *mut Member<K, V, X>;
+
+// place for a key missing from the tree, the member to hang it below and the side to hang it on
+pub(crate) struct Slot<K, V, X> {
+    pub(crate) parent: Link<K, V, X>,
+    pub(crate) left: bool,
+}
+
+// a member unlinked by `Core::detach`, still allocated, with the child that took its place and
+// the parent it hung below
+pub(crate) struct Cut<K, V, X> {
+    pub(crate) member: Link<K, V, X>,
+    pub(crate) child: Link<K, V, X>,
+    pub(crate) parent: Link<K, V, X>,
+}
+
+// the search tree property found broken by `Core::check_shape`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ShapeFault {
+    BrokenLink,
+    OutOfOrder,
+    LengthMismatch,
+}
+
+// what the data `X` of a member keeps about its subtree, brought up to date by the rotations,
+// plain balance data keeps nothing here and the maps refresh it themselves
+pub(crate) trait Augment<V> {
+    // bring the data up to date from the value of its member and the data of both children
+    fn refresh(&mut self, _value: &V, _left: Option<&Self>, _right: Option<&Self>) {}
+}
+
+impl<V> Augment<V> for () {}
+
+impl<V> Augment<V> for usize {}
+
+// balance data `X` with the size of the subtree, the value of a member being its weight, the
+// number of entries it stands for
+pub(crate) struct Counted<X> {
+    pub(crate) balance: X,
+    pub(crate) size: usize,
+}
+
+impl<X> Augment<usize> for Counted<X> {
+    fn refresh(&mut self, weight: &usize, left: Option<&Self>, right: Option<&Self>) {
+        let size_of = |child: Option<&Self>| child.map_or(0, |child| child.size);
+        self.size = weight + size_of(left) + size_of(right);
+    }
+}
+
+impl<K, V, X> Member<K, V, X> {
+    pub(crate) fn new(key: K, value: V, parent: *mut Member<K, V, X>, extra: X) -> Self {
+        Self { key, value, left: ptr::null_mut(), right: ptr::null_mut(), parent, extra }
+    }
+
+    pub(crate) fn allocate_memory(candidate: Member<K, V, X>) -> *mut Member<K, V, X> {
+        let size = mem::size_of::<Member<K, V, X>>();
+        let align = mem::align_of::<Member<K, V, X>>();
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            let ptr = alloc::alloc(layout) as *mut Member<K, V, X>;
+            ptr.write(candidate);
+            ptr
+        }
+    }
+
+    pub(crate) unsafe fn deallocate_memory(node: *mut Member<K, V, X>) -> Member<K, V, X> {
+        let size = mem::size_of::<Member<K, V, X>>();
+        let align = mem::align_of::<Member<K, V, X>>();
+        let member = node.read();
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(node as *mut u8, layout);
+        member
+    }
+
+    // leftmost member of the subtree below `node`
+    pub(crate) unsafe fn first(mut node: *mut Member<K, V, X>) -> *mut Member<K, V, X> {
+        while !node.is_null() && !(*node).left.is_null() {
+            node = (*node).left;
+        }
+        node
+    }
+
+    // rightmost member of the subtree below `node`
+    pub(crate) unsafe fn last(mut node: *mut Member<K, V, X>) -> *mut Member<K, V, X> {
+        while !node.is_null() && !(*node).right.is_null() {
+            node = (*node).right;
+        }
+        node
+    }
+
+    // next member in key order, null after the last one
+    pub(crate) unsafe fn next(node: *mut Member<K, V, X>) -> *mut Member<K, V, X> {
+        if !(*node).right.is_null() {
+            return Member::first((*node).right);
+        }
+        let mut child = node;
+        let mut parent = (*node).parent;
+        while !parent.is_null() && (*parent).right == child {
+            child = parent;
+            parent = (*parent).parent;
+        }
+        parent
+    }
+
+    // previous member in key order, null before the first one
+    pub(crate) unsafe fn prev(node: *mut Member<K, V, X>) -> *mut Member<K, V, X> {
+        if !(*node).left.is_null() {
+            return Member::last((*node).left);
+        }
+        let mut child = node;
+        let mut parent = (*node).parent;
+        while !parent.is_null() && (*parent).left == child {
+            child = parent;
+            parent = (*parent).parent;
+        }
+        parent
+    }
+}
+
+impl<K, V, X> Member<K, V, X>
+where
+    X: Augment<V>,
+{
+    // bring the data of `node` up to date from its children
+    pub(crate) unsafe fn refresh(node: *mut Member<K, V, X>) {
+        let left = (*node).left.as_ref().map(|left| &left.extra);
+        let right = (*node).right.as_ref().map(|right| &right.extra);
+        (*node).extra.refresh(&(*node).value, left, right);
+    }
+}
+
+impl<K, V, X> Core<K, V, X> {
+    pub(crate) fn new() -> Self {
+        Self { root: ptr::null_mut(), len: 0 }
+    }
+
+    /// number of levels counted by a walk, the empty tree has none
+    pub(crate) fn height(&self) -> usize {
+        let mut height = 0;
+        let mut member_stack = Vector::new();
+        if !self.root.is_null() {
+            member_stack.push_back((self.root, 1));
+        }
+        while let Some((candidate_ptr, level)) = member_stack.pop_back() {
+            let node = unsafe { &*candidate_ptr };
+            height = height.max(level);
+            for &child in [node.left, node.right].iter() {
+                if !child.is_null() {
+                    member_stack.push_back((child, level + 1));
+                }
+            }
+        }
+        height
+    }
+
+    pub(crate) fn min(&self) -> Option<(&K, &V)> {
+        unsafe { Self::entry(Member::first(self.root)) }
+    }
+
+    pub(crate) fn max(&self) -> Option<(&K, &V)> {
+        unsafe { Self::entry(Member::last(self.root)) }
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, K, V, X> {
+        unsafe { Iter::new(Member::first(self.root), Member::last(self.root)) }
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, K, V, X> {
+        IterMut { walk: self.iter() }
+    }
+
+    pub(crate) unsafe fn entry<'a>(node: *mut Member<K, V, X>) -> Option<(&'a K, &'a V)> {
+        if node.is_null() {
+            None
+        } else {
+            Some((&(*node).key, &(*node).value))
+        }
+    }
+
+    // hang `child` where `node` hung below `parent`
+    pub(crate) unsafe fn replace_child(
+        &mut self,
+        parent: *mut Member<K, V, X>,
+        node: *mut Member<K, V, X>,
+        child: *mut Member<K, V, X>,
+    ) {
+        if let Some(child) = child.as_mut() {
+            child.parent = parent;
+        }
+        match parent.as_mut() {
+            None => self.root = child,
+            Some(parent) if parent.left == node => parent.left = child,
+            Some(parent) => parent.right = child,
+        }
+    }
+
+    // hang the new member `node` in the slot found by the search
+    pub(crate) unsafe fn attach(&mut self, node: *mut Member<K, V, X>, slot: &Slot<K, V, X>) {
+        match slot.parent.as_mut() {
+            None => self.root = node,
+            Some(parent) if slot.left => parent.left = node,
+            Some(parent) => parent.right = node,
+        }
+        self.len += 1;
+    }
+
+    // unlink `target`, a member with two children takes over the entry of its successor which is
+    // unlinked instead
+    pub(crate) unsafe fn detach(&mut self, mut target: *mut Member<K, V, X>) -> Cut<K, V, X> {
+        if !(*target).left.is_null() && !(*target).right.is_null() {
+            let successor = Member::first((*target).right);
+            mem::swap(&mut (*target).key, &mut (*successor).key);
+            mem::swap(&mut (*target).value, &mut (*successor).value);
+            target = successor;
+        }
+        let child = if (*target).left.is_null() { (*target).right } else { (*target).left };
+        let parent = (*target).parent;
+        self.replace_child(parent, target, child);
+        self.len -= 1;
+        Cut { member: target, child, parent }
+    }
+
+    // the members as drawn by the binary tree
+    pub(crate) fn render<L>(&self, label: L) -> String
+    where
+        L: FnMut(Link<K, V, X>) -> String,
+    {
+        let root = if self.root.is_null() { None } else { Some(self.root) };
+        render_binary_tree(
+            root,
+            |candidate_ptr| {
+                let node = unsafe { &*candidate_ptr };
+                (
+                    Some(node.left).filter(|left| !left.is_null()),
+                    Some(node.right).filter(|right| !right.is_null()),
+                )
+            },
+            label,
+        )
+    }
+}
+
+impl<K, V, X> Core<K, V, X>
+where
+    X: Augment<V>,
+{
+    // the left child of `node` takes its place, gives back the new top of the subtree
+    pub(crate) unsafe fn rotate_right(&mut self, node: Link<K, V, X>) -> Link<K, V, X> {
+        let top = (*node).left;
+        (*node).left = (*top).right;
+        if let Some(moved) = (*node).left.as_mut() {
+            moved.parent = node;
+        }
+        self.replace_child((*node).parent, node, top);
+        (*top).right = node;
+        (*node).parent = top;
+        Member::refresh(node);
+        Member::refresh(top);
+        top
+    }
+
+    // the right child of `node` takes its place, gives back the new top of the subtree
+    pub(crate) unsafe fn rotate_left(&mut self, node: Link<K, V, X>) -> Link<K, V, X> {
+        let top = (*node).right;
+        (*node).right = (*top).left;
+        if let Some(moved) = (*node).right.as_mut() {
+            moved.parent = node;
+        }
+        self.replace_child((*node).parent, node, top);
+        (*top).left = node;
+        (*node).parent = top;
+        Member::refresh(node);
+        Member::refresh(top);
+        top
+    }
+}
+
+impl<K, V, X> Core<K, V, X>
+where
+    K: Ord,
+{
+    pub(crate) fn find(&self, key: &K) -> *mut Member<K, V, X> {
+        match self.search(key) {
+            Ok(node) => node,
+            Err(_) => ptr::null_mut(),
+        }
+    }
+
+    // the member holding `key`, or the slot to hang it in
+    pub(crate) fn search(&self, key: &K) -> Result<Link<K, V, X>, Slot<K, V, X>> {
+        let mut parent = ptr::null_mut();
+        let mut left = false;
+        let mut candidate_ptr = self.root;
+        while let Some(node) = unsafe { candidate_ptr.as_ref() } {
+            parent = candidate_ptr;
+            match key.cmp(&node.key) {
+                Ordering::Less => {
+                    left = true;
+                    candidate_ptr = node.left;
+                },
+                Ordering::Greater => {
+                    left = false;
+                    candidate_ptr = node.right;
+                },
+                Ordering::Equal => return Ok(candidate_ptr),
+            }
+        }
+        Err(Slot { parent, left })
+    }
+
+    // closest member below `key` when `below`, above it otherwise, `key` itself counts when
+    // `inclusive`
+    pub(crate) fn bound(&self, key: &K, below: bool, inclusive: bool) -> *mut Member<K, V, X> {
+        let mut found = ptr::null_mut();
+        let mut candidate_ptr = self.root;
+        while let Some(node) = unsafe { candidate_ptr.as_ref() } {
+            let fits = match (below, inclusive) {
+                (true, true) => node.key <= *key,
+                (true, false) => node.key < *key,
+                (false, true) => node.key >= *key,
+                (false, false) => node.key > *key,
+            };
+            if fits {
+                found = candidate_ptr;
+            }
+            // a fitting member looks for a closer one on the side of `key`
+            candidate_ptr = if fits == below { node.right } else { node.left };
+        }
+        found
+    }
+
+    // entries with a key in `range`, a range whose start is above its end is empty
+    pub(crate) fn range<R>(&self, range: R) -> Iter<'_, K, V, X>
+    where
+        R: RangeBounds<K>,
+    {
+        let front = match range.start_bound() {
+            Bound::Included(start) => self.bound(start, false, true),
+            Bound::Excluded(start) => self.bound(start, false, false),
+            Bound::Unbounded => unsafe { Member::first(self.root) },
+        };
+        let back = match range.end_bound() {
+            Bound::Included(end) => self.bound(end, true, true),
+            Bound::Excluded(end) => self.bound(end, true, false),
+            Bound::Unbounded => unsafe { Member::last(self.root) },
+        };
+        let crossed = unsafe {
+            match (front.as_ref(), back.as_ref()) {
+                (Some(front), Some(back)) => front.key > back.key,
+                _ => true,
+            }
+        };
+        if crossed {
+            unsafe { Iter::new(ptr::null_mut(), ptr::null_mut()) }
+        } else {
+            unsafe { Iter::new(front, back) }
+        }
+    }
+
+    // check the links and the order of the keys, every key of a left subtree has to be below the
+    // key of its member and every key of a right subtree above it
+    pub(crate) fn check_shape(&self) -> Result<(), ShapeFault> {
+        // member with the tightest keys it has to stay between
+        let mut member_stack = Vector::new();
+        if let Some(root) = unsafe { self.root.as_ref() } {
+            if !root.parent.is_null() {
+                return Err(ShapeFault::BrokenLink);
+            }
+            member_stack.push_back((self.root, None::<&K>, None::<&K>));
+        }
+        let mut count = 0;
+        while let Some((candidate_ptr, low, high)) = member_stack.pop_back() {
+            let node = unsafe { &*candidate_ptr };
+            count += 1;
+            if low.is_some_and(|low| node.key <= *low) || high.is_some_and(|high| node.key >= *high)
+            {
+                return Err(ShapeFault::OutOfOrder);
+            }
+            for &(child, low, high) in
+                [(node.left, low, Some(&node.key)), (node.right, Some(&node.key), high)].iter()
+            {
+                if let Some(child_node) = unsafe { child.as_ref() } {
+                    if child_node.parent != candidate_ptr {
+                        return Err(ShapeFault::BrokenLink);
+                    }
+                    member_stack.push_back((child, low, high));
+                }
+            }
+        }
+        if count != self.len {
+            return Err(ShapeFault::LengthMismatch);
+        }
+        Ok(())
+    }
+}
+
+impl<K, V, X> Drop for Core<K, V, X> {
+    fn drop(&mut self) {
+        let mut member_stack = Vector::new();
+        member_stack.push_back(self.root);
+        while let Some(candidate_ptr) = member_stack.pop_back() {
+            if !candidate_ptr.is_null() {
+                let member = unsafe { Member::deallocate_memory(candidate_ptr) };
+                member_stack.push_back(member.left);
+                member_stack.push_back(member.right);
+            }
+        }
+    }
+}
+
+/// in-order iterator over the entries of a search tree map, `X` is the balance data of the map
+pub struct Iter<'a, K, V, X> {
+    front: *mut Member<K, V, X>,
+    back: *mut Member<K, V, X>,
+    marker: PhantomData<&'a Member<K, V, X>>,
+}
+
+impl<'a, K, V, X> Iter<'a, K, V, X> {
+    // entries from `front` to `back`, both null or `back` not before `front`
+    unsafe fn new(front: *mut Member<K, V, X>, back: *mut Member<K, V, X>) -> Self {
+        Self { front, back, marker: PhantomData }
+    }
+
+    pub(crate) fn next_member(&mut self) -> Option<*mut Member<K, V, X>> {
+        if self.front.is_null() {
+            return None;
+        }
+        let node = self.front;
+        if self.front == self.back {
+            self.front = ptr::null_mut();
+            self.back = ptr::null_mut();
+        } else {
+            self.front = unsafe { Member::next(node) };
+        }
+        Some(node)
+    }
+
+    pub(crate) fn next_back_member(&mut self) -> Option<*mut Member<K, V, X>> {
+        if self.back.is_null() {
+            return None;
+        }
+        let node = self.back;
+        if self.front == self.back {
+            self.front = ptr::null_mut();
+            self.back = ptr::null_mut();
+        } else {
+            self.back = unsafe { Member::prev(node) };
+        }
+        Some(node)
+    }
+}
+
+impl<'a, K, V, X> Iterator for Iter<'a, K, V, X> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next_member()?;
+        unsafe { Some((&(*node).key, &(*node).value)) }
+    }
+}
+
+impl<'a, K, V, X> DoubleEndedIterator for Iter<'a, K, V, X> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = self.next_back_member()?;
+        unsafe { Some((&(*node).key, &(*node).value)) }
+    }
+}
+
+/// in-order iterator over the entries of a search tree map with mutable values
+pub struct IterMut<'a, K, V, X> {
+    walk: Iter<'a, K, V, X>,
+}
+
+impl<'a, K, V, X> Iterator for IterMut<'a, K, V, X> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.walk.next_member()?;
+        unsafe { Some((&(*node).key, &mut (*node).value)) }
+    }
+}
+
+impl<'a, K, V, X> DoubleEndedIterator for IterMut<'a, K, V, X> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = self.walk.next_back_member()?;
+        unsafe { Some((&(*node).key, &mut (*node).value)) }
+    }
+}
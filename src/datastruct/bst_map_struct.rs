@@ -0,0 +1,251 @@
+//! binary search tree map written `BstMap<K, V>`
+//!
+//! the plain binary search tree without any rebalancing, the keys of the left subtree of a member
+//! are below its key and the keys of the right subtree above it. Its height follows the order of
+//! the insertions, sorted input gives a chain, so it is the baseline the balanced trees are
+//! measured against. The members, walks and searches are those of
+//! [`crate::datastruct::bst_core`], which the balanced maps share.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::bst_map_struct::BstMap;
+//! let mut map = BstMap::new();
+//! for (key, value) in [(5, 'e'), (2, 'b'), (8, 'h'), (6, 'f')].iter() {
+//!     map.insert(*key, *value);
+//! }
+//! assert_eq!(format!("{}", map), "╚═5: 'e'\n  ╠═L 2: 'b'\n  ╚═R 8: 'h'\n    ╚═L 6: 'f'\n");
+//! assert_eq!(format!("{:?}", map), "{2: 'b', 5: 'e', 6: 'f', 8: 'h'}");
+//! assert_eq!(map.floor(&7), Some((&6, &'f')));
+//! assert_eq!(map.range(3..=6).map(|(key, _)| *key).collect::<Vec<_>>(), vec![5, 6]);
+//! ```
+
+use crate::datastruct::bst_core::{self, Core, Member};
+use core::mem;
+use core::ops::RangeBounds;
+
+pub struct BstMap<K, V> {
+    core: Core<K, V, ()>,
+}
+
+/// iterator of [`BstMap::iter`] and [`BstMap::range`]
+pub type Iter<'a, K, V> = bst_core::Iter<'a, K, V, ()>;
+
+/// iterator of [`BstMap::iter_mut`]
+pub type IterMut<'a, K, V> = bst_core::IterMut<'a, K, V, ()>;
+
+impl<K, V> BstMap<K, V> {
+    pub fn new() -> Self {
+        Self { core: Core::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.core.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.core.len == 0
+    }
+
+    /// number of levels, the empty map has none
+    pub fn height(&self) -> usize {
+        self.core.height()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// smallest key with its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        self.core.min()
+    }
+
+    /// largest key with its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        self.core.max()
+    }
+
+    /// entries in key order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.core.iter()
+    }
+
+    /// entries in key order with mutable values
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.core.iter_mut()
+    }
+
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> BstMap<K, V>
+where
+    K: Ord,
+{
+    /// put `value` under `key` and give back the value it replaces
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.core.search(&key) {
+            Ok(node) => unsafe { Some(mem::replace(&mut (*node).value, value)) },
+            Err(slot) => {
+                let node = Member::allocate_memory(Member::new(key, value, slot.parent, ()));
+                unsafe { self.core.attach(node, &slot) };
+                None
+            },
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        unsafe { self.core.find(key).as_ref().map(|node| &node.value) }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { self.core.find(key).as_mut().map(|node| &mut node.value) }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        !self.core.find(key).is_null()
+    }
+
+    /// take `key` out of the map. A member without children is cut off, a member with one child
+    /// is replaced by that child, and a member with two children takes over the entry of its
+    /// successor, the leftmost member of its right subtree, which is then cut out instead
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::bst_map_struct::BstMap;
+    /// let mut map: BstMap<u8, ()> = [5, 2, 8, 6, 9, 7].iter().map(|&key| (key, ())).collect();
+    /// assert_eq!(map.remove(&5), Some(()));
+    /// assert_eq!(format!("{}", map), "╚═6: ()\n  ╠═L 2: ()\n  ╚═R 8: ()\n    ╠═L 7: ()\n    ╚═R 9: ()\n");
+    /// assert_eq!(map.remove(&5), None);
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let target = self.core.find(key);
+        if target.is_null() {
+            return None;
+        }
+        unsafe {
+            let cut = self.core.detach(target);
+            let member = Member::deallocate_memory(cut.member);
+            Some((member.key, member.value))
+        }
+    }
+
+    /// largest entry with a key not above `key`
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        unsafe { Core::entry(self.core.bound(key, true, true)) }
+    }
+
+    /// smallest entry with a key not below `key`
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        unsafe { Core::entry(self.core.bound(key, false, true)) }
+    }
+
+    /// largest entry with a key below `key`, which need not be in the map
+    pub fn predecessor(&self, key: &K) -> Option<(&K, &V)> {
+        unsafe { Core::entry(self.core.bound(key, true, false)) }
+    }
+
+    /// smallest entry with a key above `key`, which need not be in the map
+    pub fn successor(&self, key: &K) -> Option<(&K, &V)> {
+        unsafe { Core::entry(self.core.bound(key, false, false)) }
+    }
+
+    /// entries with a key in `range` in key order, a range whose start is above its end is empty
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::bst_map_struct::BstMap;
+    /// use std::ops::Bound;
+    /// let map: BstMap<u8, char> = (b'a'..=b'j').map(|c| (c, c as char)).collect();
+    /// let word: String = map.range((Bound::Excluded(b'b'), Bound::Included(b'f'))).map(|(_, c)| *c).rev().collect();
+    /// assert_eq!(word, "fedc");
+    /// assert_eq!(map.range(b'x'..).count(), 0);
+    /// ```
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        self.core.range(range)
+    }
+
+    /// check the links and the order of the keys, every key of a left subtree has to be below
+    /// the key of its member and every key of a right subtree above it
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::bst_map_struct::BstMap;
+    /// let map: BstMap<u32, u32> = (0..100).map(|i| (i * 37 % 101, i)).collect();
+    /// assert!(map.check_invariants());
+    /// ```
+    pub fn check_invariants(&self) -> bool {
+        self.core.check_shape().is_ok()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a BstMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> core::iter::FromIterator<(K, V)> for BstMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = BstMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for BstMap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> Default for BstMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> std::fmt::Display for BstMap<K, V>
+where
+    K: std::fmt::Display,
+    V: std::fmt::Debug,
+{
+    /// the members as drawn by [`crate::datastruct::binary_tree_struct::BinaryTree`]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let out = self.core.render(|candidate_ptr| unsafe {
+            format!("{}: {:?}", (*candidate_ptr).key, (*candidate_ptr).value)
+        });
+        write!(f, "{}", out)
+    }
+}
+
+impl<K, V> std::fmt::Debug for BstMap<K, V>
+where
+    K: std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,142 @@
+//! page cache with least recently used eviction
+//!
+//! frames live in a `Vec` and are chained from the most to the least recently used one by slot
+//! indices, a `HashMap` finds the frame of a page, so a lookup, a touch and an eviction are all
+//! O(1).
+
+use super::page::Node;
+use std::collections::HashMap;
+
+const NIL: usize = usize::MAX;
+
+/// counters of a page cache since the tree was opened
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// reads served from the cache
+    pub hits: u64,
+    /// reads that went to the page file
+    pub misses: u64,
+    /// frames dropped to make room for another page
+    pub evictions: u64,
+}
+
+struct Frame {
+    page_id: u64,
+    node: Node,
+    dirty: bool,
+    newer: usize,
+    older: usize,
+}
+
+pub(super) struct PageCache {
+    frames: Vec<Frame>,
+    slots: HashMap<u64, usize>,
+    newest: usize,
+    oldest: usize,
+    capacity: usize,
+    pub stats: CacheStats,
+}
+
+impl PageCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: Vec::with_capacity(capacity),
+            slots: HashMap::new(),
+            newest: NIL,
+            oldest: NIL,
+            capacity,
+            stats: CacheStats::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// the cached node of `page_id`, which becomes the most recently used
+    pub fn get(&mut self, page_id: u64) -> Option<&Node> {
+        match self.slots.get(&page_id) {
+            Some(&slot) => {
+                self.stats.hits += 1;
+                self.touch(slot);
+                Some(&self.frames[slot].node)
+            },
+            None => {
+                self.stats.misses += 1;
+                None
+            },
+        }
+    }
+
+    /// cache `node` as the most recently used page, giving back the evicted page when it was
+    /// dirty so that the caller writes it out
+    pub fn put(&mut self, page_id: u64, node: Node, dirty: bool) -> Option<(u64, Node)> {
+        if let Some(&slot) = self.slots.get(&page_id) {
+            let frame = &mut self.frames[slot];
+            frame.node = node;
+            frame.dirty |= dirty;
+            self.touch(slot);
+            return None;
+        }
+        let frame = Frame { page_id, node, dirty, newer: NIL, older: NIL };
+        if self.frames.len() < self.capacity {
+            self.frames.push(frame);
+            let slot = self.frames.len() - 1;
+            self.slots.insert(page_id, slot);
+            self.link_newest(slot);
+            return None;
+        }
+        let slot = self.oldest;
+        self.unlink(slot);
+        let old = std::mem::replace(&mut self.frames[slot], frame);
+        self.slots.remove(&old.page_id);
+        self.slots.insert(page_id, slot);
+        self.link_newest(slot);
+        self.stats.evictions += 1;
+        if old.dirty {
+            Some((old.page_id, old.node))
+        } else {
+            None
+        }
+    }
+
+    /// dirty pages in no particular order
+    pub fn dirty(&self) -> impl Iterator<Item = (u64, &Node)> {
+        self.frames.iter().filter(|frame| frame.dirty).map(|frame| (frame.page_id, &frame.node))
+    }
+
+    pub fn mark_clean(&mut self) {
+        for frame in self.frames.iter_mut() {
+            frame.dirty = false;
+        }
+    }
+
+    fn touch(&mut self, slot: usize) {
+        if self.newest != slot {
+            self.unlink(slot);
+            self.link_newest(slot);
+        }
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let (newer, older) = (self.frames[slot].newer, self.frames[slot].older);
+        match newer {
+            NIL => self.newest = older,
+            newer => self.frames[newer].older = older,
+        }
+        match older {
+            NIL => self.oldest = newer,
+            older => self.frames[older].newer = newer,
+        }
+    }
+
+    fn link_newest(&mut self, slot: usize) {
+        self.frames[slot].newer = NIL;
+        self.frames[slot].older = self.newest;
+        match self.newest {
+            NIL => self.oldest = slot,
+            newest => self.frames[newest].newer = slot,
+        }
+        self.newest = slot;
+    }
+}
@@ -0,0 +1,768 @@
+//! disk-backed B+ tree index written `DiskBPlusTree`
+//!
+//! it maps `u64` keys to `u64` values and keeps them in fixed-size pages of a local file, whose
+//! layout is described in [`page`]. Entries live only in the leaves, which are chained by a
+//! next-leaf link so that a range scan walks along the leaves without going back up the tree.
+//! Inner nodes only hold separator keys, they are copied up when a leaf splits and stay in place
+//! when the key itself is removed.
+//!
+//! Pages are read through a cache of [`BPlusOptions::cache_pages`] decoded nodes that evicts the
+//! least recently used one. Writes are crash safe through a write-ahead log kept next to the page
+//! file with `.wal` appended to its name, see [`wal`]: every insertion or removal appends the
+//! images of the pages it changed and of the meta page as one batch closed by a commit record,
+//! and the changed pages only reach the page file when they are evicted or at a checkpoint. A
+//! checkpoint writes every dirty page, syncs the page file and empties the log, it runs every
+//! [`BPlusOptions::checkpoint_every`] operations, on [`DiskBPlusTree::checkpoint`] and on drop.
+//! Opening a file replays the committed batches of its log, so an operation is either entirely
+//! there after a crash or not at all. An operation whose batch is in the log stays applied even
+//! when writing the page file afterwards fails, the error then says so and the checkpoint is tried
+//! again after the next operation. Pages emptied by a merge go to a free list and are reused.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::disk_b_plus_tree_struct::{BPlusOptions, DiskBPlusTree};
+//! # fn main() -> std::io::Result<()> {
+//! let path = std::env::temp_dir().join(format!("dsa_sport_doc_{}.db", std::process::id()));
+//! # let _ = std::fs::remove_file(&path);
+//! let mut index = DiskBPlusTree::open(&path, BPlusOptions::new().page_size(64))?;
+//! for key in 0..20 {
+//!     index.insert(key, key * key)?;
+//! }
+//! assert_eq!(index.get(7)?, Some(49));
+//! assert_eq!(index.remove(7)?, Some(49));
+//! let scan: Vec<(u64, u64)> = index.range(5..10).collect::<std::io::Result<_>>()?;
+//! assert_eq!(scan, [(5, 25), (6, 36), (8, 64), (9, 81)]);
+//! drop(index);
+//!
+//! let mut index = DiskBPlusTree::open(&path, BPlusOptions::new())?;
+//! assert_eq!(index.len(), 19);
+//! assert!(index.check_invariants()?);
+//! # drop(index);
+//! # std::fs::remove_file(&path)?;
+//! # std::fs::remove_file(path.with_extension("db.wal"))?;
+//! # Ok(())
+//! # }
+//! ```
+mod cache;
+pub mod page;
+pub mod wal;
+
+pub use cache::CacheStats;
+
+use cache::PageCache;
+use page::{corrupt, inner_capacity, leaf_capacity, Meta, Node, MAX_PAGE_SIZE, MIN_PAGE_SIZE};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::{Bound, RangeBounds};
+use std::path::{Path, PathBuf};
+use wal::Wal;
+
+/// settings of [`DiskBPlusTree::open`]
+/// # Example
+/// ```
+/// # use dsa_sport::datastruct::disk_b_plus_tree_struct::BPlusOptions;
+/// let options = BPlusOptions::new().page_size(512).cache_pages(16).sync(false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BPlusOptions {
+    page_size: usize,
+    cache_pages: usize,
+    checkpoint_every: usize,
+    sync: bool,
+}
+
+impl BPlusOptions {
+    /// pages of 4096 bytes, 64 cached pages, a checkpoint every 256 operations and synced writes
+    pub fn new() -> Self {
+        Self { page_size: 4096, cache_pages: 64, checkpoint_every: 256, sync: true }
+    }
+
+    /// size of the pages of a new file, an existing file keeps its own
+    /// # Panics
+    /// when `bytes` is outside [`page::MIN_PAGE_SIZE`]`..=`[`page::MAX_PAGE_SIZE`]
+    pub fn page_size(mut self, bytes: usize) -> Self {
+        assert!(
+            (MIN_PAGE_SIZE..=MAX_PAGE_SIZE).contains(&bytes),
+            "a page holds {} to {} bytes",
+            MIN_PAGE_SIZE,
+            MAX_PAGE_SIZE
+        );
+        self.page_size = bytes;
+        self
+    }
+
+    /// # Panics
+    /// when `pages` is 0
+    pub fn cache_pages(mut self, pages: usize) -> Self {
+        assert!(pages > 0, "the page cache needs room for a page");
+        self.cache_pages = pages;
+        self
+    }
+
+    /// operations between two checkpoints, which bounds the length of the log
+    /// # Panics
+    /// when `operations` is 0
+    pub fn checkpoint_every(mut self, operations: usize) -> Self {
+        assert!(operations > 0, "a checkpoint follows at least one operation");
+        self.checkpoint_every = operations;
+        self
+    }
+
+    /// whether the log and the page file are synced to the disk, without it an operation
+    /// survives a crash of the process but not of the machine
+    pub fn sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+}
+
+impl Default for BPlusOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct DiskBPlusTree {
+    file: File,
+    wal: Wal,
+    meta: Meta,
+    cache: PageCache,
+    // pages changed by the running operation, they reach the cache once it is logged
+    pending: HashMap<u64, Node>,
+    logged: usize,
+    checkpoint_every: usize,
+    sync: bool,
+}
+
+// inner node on the way down from the root, with the child that was taken
+struct Step {
+    page_id: u64,
+    keys: Vec<u64>,
+    children: Vec<u64>,
+    child: usize,
+}
+
+struct Leaf {
+    page_id: u64,
+    entries: Vec<(u64, u64)>,
+    next: u64,
+}
+
+fn wal_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".wal");
+    PathBuf::from(name)
+}
+
+fn write_page(file: &mut File, page_size: usize, page_id: u64, image: &[u8]) -> io::Result<()> {
+    file.seek(SeekFrom::Start(page_id * page_size as u64))?;
+    file.write_all(image)
+}
+
+impl DiskBPlusTree {
+    /// open the index stored at `path`, creating it when the file does not exist, and replay
+    /// the operations its log committed since the last checkpoint
+    pub fn open<P: AsRef<Path>>(path: P, options: BPlusOptions) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        let mut head = [0; 48];
+        let page_size = if file.metadata()?.len() == 0 {
+            let meta =
+                Meta { page_size: options.page_size, root: 1, page_count: 2, len: 0, free: 0 };
+            let root = Node::Leaf { entries: Vec::new(), next: 0 };
+            write_page(&mut file, meta.page_size, 1, &root.encode(meta.page_size))?;
+            write_page(&mut file, meta.page_size, 0, &meta.encode())?;
+            file.sync_all()?;
+            meta.page_size
+        } else {
+            file.seek(SeekFrom::Start(0))?;
+            file.read_exact(&mut head)?;
+            Meta::decode(&head)?.page_size
+        };
+        let (mut wal, batches) = Wal::open(&wal_path(path), page_size, options.sync)?;
+        if !batches.is_empty() {
+            for (page_id, image) in batches.iter().flatten() {
+                write_page(&mut file, page_size, *page_id, image)?;
+            }
+            file.sync_all()?;
+            wal.reset()?;
+        }
+        let mut page = vec![0; page_size];
+        file.seek(SeekFrom::Start(0))?;
+        file.read_exact(&mut page)?;
+        let meta = Meta::decode(&page)?;
+        if meta.page_size != page_size {
+            return Err(corrupt("meta page changed its page size"));
+        }
+        Ok(Self {
+            file,
+            wal,
+            meta,
+            cache: PageCache::new(options.cache_pages),
+            pending: HashMap::new(),
+            logged: 0,
+            checkpoint_every: options.checkpoint_every,
+            sync: options.sync,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.meta.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.meta.len == 0
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.meta.page_size
+    }
+
+    /// pages of the file, the meta page and the free pages included
+    pub fn page_count(&self) -> u64 {
+        self.meta.page_count
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats
+    }
+
+    /// number of levels of nodes, a lone leaf root is one level
+    pub fn height(&mut self) -> io::Result<usize> {
+        let mut height = 1;
+        let mut page_id = self.meta.root;
+        while let Node::Inner { children, .. } = self.node(page_id)? {
+            height += 1;
+            page_id = children[0];
+        }
+        Ok(height)
+    }
+
+    pub fn get(&mut self, key: u64) -> io::Result<Option<u64>> {
+        let (_, leaf) = self.descend(key)?;
+        Ok(match leaf.entries.binary_search_by_key(&key, |&(key, _)| key) {
+            Ok(index) => Some(leaf.entries[index].1),
+            Err(_) => None,
+        })
+    }
+
+    pub fn contains_key(&mut self, key: u64) -> io::Result<bool> {
+        Ok(self.get(key)?.is_some())
+    }
+
+    /// insert `value` under `key` and give back the value it replaces
+    pub fn insert(&mut self, key: u64, value: u64) -> io::Result<Option<u64>> {
+        self.logged_operation(|tree| tree.insert_pending(key, value))
+    }
+
+    /// remove `key` and give back its value
+    pub fn remove(&mut self, key: u64) -> io::Result<Option<u64>> {
+        self.logged_operation(|tree| tree.remove_pending(key))
+    }
+
+    /// entries with a key in `range` in key order, walking along the leaf links
+    pub fn range<R: RangeBounds<u64>>(&mut self, range: R) -> Range<'_> {
+        Range {
+            tree: self,
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+            entries: Vec::new().into_iter(),
+            next: None,
+            done: false,
+        }
+    }
+
+    /// every entry in key order
+    pub fn iter(&mut self) -> Range<'_> {
+        self.range(..)
+    }
+
+    /// write every changed page to the page file and empty the log
+    pub fn checkpoint(&mut self) -> io::Result<()> {
+        let page_size = self.meta.page_size;
+        for (page_id, node) in self.cache.dirty() {
+            write_page(&mut self.file, page_size, page_id, &node.encode(page_size))?;
+        }
+        write_page(&mut self.file, page_size, 0, &self.meta.encode())?;
+        if self.sync {
+            self.file.sync_all()?;
+        } else {
+            self.file.flush()?;
+        }
+        self.cache.mark_clean();
+        self.wal.reset()?;
+        self.logged = 0;
+        Ok(())
+    }
+
+    /// check every rule of the tree: sorted keys inside the bounds set by the separators, every
+    /// leaf at the same depth, nodes but the root at least half full, the leaf chain in key order,
+    /// the entry count, and every page either in the tree or in the free list
+    pub fn check_invariants(&mut self) -> io::Result<bool> {
+        let leaf_min = leaf_capacity(self.meta.page_size) / 2;
+        let inner_min = inner_capacity(self.meta.page_size) / 2;
+        let mut seen = vec![false; self.meta.page_count as usize];
+        let mut leaves = Vec::new();
+        let mut count = 0;
+        let mut leaf_depth = None;
+        // page, depth, keys at least, keys below
+        let mut stack = vec![(self.meta.root, 0, None, None)];
+        while let Some((page_id, depth, low, high)) = stack.pop() {
+            if page_id == 0 || page_id >= self.meta.page_count || seen[page_id as usize] {
+                return Ok(false);
+            }
+            seen[page_id as usize] = true;
+            let is_root = page_id == self.meta.root;
+            let inside = |key: u64| {
+                low.is_none_or(|low| key >= low) && high.is_none_or(|high| key < high)
+            };
+            match self.node(page_id)? {
+                Node::Leaf { entries, .. } => {
+                    if *leaf_depth.get_or_insert(depth) != depth
+                        || (!is_root && entries.len() < leaf_min)
+                        || !entries.windows(2).all(|pair| pair[0].0 < pair[1].0)
+                        || !entries.iter().all(|&(key, _)| inside(key))
+                    {
+                        return Ok(false);
+                    }
+                    count += entries.len() as u64;
+                    leaves.push(page_id);
+                },
+                Node::Inner { keys, children } => {
+                    if (!is_root && keys.len() < inner_min)
+                        || !keys.windows(2).all(|pair| pair[0] < pair[1])
+                        || !keys.iter().all(|&key| inside(key))
+                    {
+                        return Ok(false);
+                    }
+                    // the leftmost child is visited first
+                    for (index, &child) in children.iter().enumerate().rev() {
+                        let child_low = if index == 0 { low } else { Some(keys[index - 1]) };
+                        let child_high = keys.get(index).copied().or(high);
+                        stack.push((child, depth + 1, child_low, child_high));
+                    }
+                },
+                Node::Free { .. } => return Ok(false),
+            }
+        }
+        let mut chained = Vec::new();
+        let mut page_id = leaves[0];
+        while page_id != 0 && chained.len() < leaves.len() {
+            chained.push(page_id);
+            page_id = match self.node(page_id)? {
+                Node::Leaf { next, .. } => next,
+                _ => return Ok(false),
+            };
+        }
+        if chained != leaves || page_id != 0 || count != self.meta.len {
+            return Ok(false);
+        }
+        let mut page_id = self.meta.free;
+        while page_id != 0 {
+            if page_id >= self.meta.page_count || seen[page_id as usize] {
+                return Ok(false);
+            }
+            seen[page_id as usize] = true;
+            page_id = match self.node(page_id)? {
+                Node::Free { next } => next,
+                _ => return Ok(false),
+            };
+        }
+        Ok(seen.iter().skip(1).all(|&seen| seen))
+    }
+
+    // run `operation` on pending pages and log them with the meta page as one batch, or forget
+    // them when it fails before the batch is in the log. Once it is, the operation is committed
+    // and stays applied, a failure of the checkpoint that may follow is reported on its own
+    fn logged_operation<T, F>(&mut self, operation: F) -> io::Result<T>
+    where
+        F: FnOnce(&mut Self) -> io::Result<T>,
+    {
+        let meta = self.meta;
+        let value = match operation(self).and_then(|value| self.log().map(|_| value)) {
+            Ok(value) => value,
+            Err(err) => {
+                self.pending.clear();
+                self.meta = meta;
+                return Err(err);
+            },
+        };
+        self.settle().map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("operation committed to the log, writing the page file failed: {}", err),
+            )
+        })?;
+        Ok(value)
+    }
+
+    fn log(&mut self) -> io::Result<()> {
+        let page_size = self.meta.page_size;
+        let mut images = vec![(0, self.meta.encode())];
+        images
+            .extend(self.pending.iter().map(|(&page_id, node)| (page_id, node.encode(page_size))));
+        let pages: Vec<(u64, &[u8])> =
+            images.iter().map(|(page_id, image)| (*page_id, &image[..])).collect();
+        self.wal.append(&pages)
+    }
+
+    // move the logged pages to the cache and checkpoint when one is due, a failed checkpoint is
+    // tried again after the next operation
+    fn settle(&mut self) -> io::Result<()> {
+        let mut first_error = Ok(());
+        for (page_id, node) in std::mem::take(&mut self.pending) {
+            let cached = self.cache_page(page_id, node, true);
+            if first_error.is_ok() {
+                first_error = cached;
+            }
+        }
+        self.logged += 1;
+        first_error?;
+        if self.logged >= self.checkpoint_every {
+            self.checkpoint()?;
+        }
+        Ok(())
+    }
+
+    // a dirty page pushed out of the cache is already in the log, so it can go to the page file
+    // before the checkpoint
+    fn cache_page(&mut self, page_id: u64, node: Node, dirty: bool) -> io::Result<()> {
+        if let Some((evicted, node)) = self.cache.put(page_id, node, dirty) {
+            let page_size = self.meta.page_size;
+            write_page(&mut self.file, page_size, evicted, &node.encode(page_size))?;
+        }
+        Ok(())
+    }
+
+    fn node(&mut self, page_id: u64) -> io::Result<Node> {
+        if let Some(node) = self.pending.get(&page_id) {
+            return Ok(node.clone());
+        }
+        if let Some(node) = self.cache.get(page_id) {
+            return Ok(node.clone());
+        }
+        if page_id == 0 || page_id >= self.meta.page_count {
+            return Err(corrupt("page number out of range"));
+        }
+        let mut bytes = vec![0; self.meta.page_size];
+        self.file.seek(SeekFrom::Start(page_id * self.meta.page_size as u64))?;
+        self.file.read_exact(&mut bytes)?;
+        let node = Node::decode(&bytes)?;
+        self.cache_page(page_id, node.clone(), false)?;
+        Ok(node)
+    }
+
+    fn leaf(&mut self, page_id: u64) -> io::Result<Leaf> {
+        match self.node(page_id)? {
+            Node::Leaf { entries, next } => Ok(Leaf { page_id, entries, next }),
+            _ => Err(corrupt("expected a leaf page")),
+        }
+    }
+
+    // the inner nodes from the root down to the leaf that holds `key`, and that leaf
+    fn descend(&mut self, key: u64) -> io::Result<(Vec<Step>, Leaf)> {
+        let mut path = Vec::new();
+        let mut page_id = self.meta.root;
+        loop {
+            match self.node(page_id)? {
+                Node::Leaf { entries, next } => return Ok((path, Leaf { page_id, entries, next })),
+                Node::Inner { keys, children } => {
+                    let child = keys.partition_point(|&separator| separator <= key);
+                    let next = children[child];
+                    path.push(Step { page_id, keys, children, child });
+                    page_id = next;
+                },
+                Node::Free { .. } => return Err(corrupt("free page inside the tree")),
+            }
+        }
+    }
+
+    fn store_leaf(&mut self, leaf: Leaf) {
+        self.pending.insert(leaf.page_id, Node::Leaf { entries: leaf.entries, next: leaf.next });
+    }
+
+    fn store_inner(&mut self, step: Step) {
+        self.pending.insert(step.page_id, Node::Inner { keys: step.keys, children: step.children });
+    }
+
+    fn allocate(&mut self) -> io::Result<u64> {
+        if self.meta.free == 0 {
+            self.meta.page_count += 1;
+            return Ok(self.meta.page_count - 1);
+        }
+        let page_id = self.meta.free;
+        self.meta.free = match self.node(page_id)? {
+            Node::Free { next } => next,
+            _ => return Err(corrupt("free list holds a used page")),
+        };
+        Ok(page_id)
+    }
+
+    fn release(&mut self, page_id: u64) {
+        self.pending.insert(page_id, Node::Free { next: self.meta.free });
+        self.meta.free = page_id;
+    }
+
+    fn insert_pending(&mut self, key: u64, value: u64) -> io::Result<Option<u64>> {
+        let (mut path, mut leaf) = self.descend(key)?;
+        match leaf.entries.binary_search_by_key(&key, |&(key, _)| key) {
+            Ok(index) => {
+                let old = std::mem::replace(&mut leaf.entries[index].1, value);
+                self.store_leaf(leaf);
+                return Ok(Some(old));
+            },
+            Err(index) => leaf.entries.insert(index, (key, value)),
+        }
+        self.meta.len += 1;
+        if leaf.entries.len() <= leaf_capacity(self.meta.page_size) {
+            self.store_leaf(leaf);
+            return Ok(None);
+        }
+        // the upper half of the leaf moves to a new page and its first key is copied up
+        let right_id = self.allocate()?;
+        let right = leaf.entries.split_off(leaf.entries.len() / 2);
+        let mut separator = right[0].0;
+        let right = Leaf { page_id: right_id, entries: right, next: leaf.next };
+        leaf.next = right_id;
+        let mut new_child = right_id;
+        self.store_leaf(leaf);
+        self.store_leaf(right);
+        while let Some(mut step) = path.pop() {
+            step.keys.insert(step.child, separator);
+            step.children.insert(step.child + 1, new_child);
+            if step.keys.len() <= inner_capacity(self.meta.page_size) {
+                self.store_inner(step);
+                return Ok(None);
+            }
+            // the middle key moves up
+            let middle = step.keys.len() / 2;
+            let right_id = self.allocate()?;
+            let keys = step.keys.split_off(middle + 1);
+            let children = step.children.split_off(middle + 1);
+            separator = step.keys.pop().expect("middle key");
+            new_child = right_id;
+            self.store_inner(step);
+            self.store_inner(Step { page_id: right_id, keys, children, child: 0 });
+        }
+        let root_id = self.allocate()?;
+        let old_root = self.meta.root;
+        self.store_inner(Step {
+            page_id: root_id,
+            keys: vec![separator],
+            children: vec![old_root, new_child],
+            child: 0,
+        });
+        self.meta.root = root_id;
+        Ok(None)
+    }
+
+    fn remove_pending(&mut self, key: u64) -> io::Result<Option<u64>> {
+        let (mut path, mut leaf) = self.descend(key)?;
+        let value = match leaf.entries.binary_search_by_key(&key, |&(key, _)| key) {
+            Ok(index) => leaf.entries.remove(index).1,
+            Err(_) => return Ok(None),
+        };
+        self.meta.len -= 1;
+        let leaf_min = leaf_capacity(self.meta.page_size) / 2;
+        let mut parent = match path.pop() {
+            Some(parent) if leaf.entries.len() < leaf_min => parent,
+            _ => {
+                self.store_leaf(leaf);
+                return Ok(Some(value));
+            },
+        };
+        self.rebalance_leaf(&mut parent, leaf)?;
+        let inner_min = inner_capacity(self.meta.page_size) / 2;
+        while let Some(mut grandparent) = path.pop() {
+            if parent.keys.len() >= inner_min {
+                self.store_inner(parent);
+                return Ok(Some(value));
+            }
+            self.rebalance_inner(&mut grandparent, parent)?;
+            parent = grandparent;
+        }
+        // the root gives way to its only child
+        if parent.keys.is_empty() {
+            self.meta.root = parent.children[0];
+            self.release(parent.page_id);
+        } else {
+            self.store_inner(parent);
+        }
+        Ok(Some(value))
+    }
+
+    // refill the short `leaf` from a sibling, or merge the two when the sibling has no entry to
+    // spare
+    fn rebalance_leaf(&mut self, parent: &mut Step, mut leaf: Leaf) -> io::Result<()> {
+        let leaf_min = leaf_capacity(self.meta.page_size) / 2;
+        let index = parent.child;
+        if index > 0 {
+            let mut left = self.leaf(parent.children[index - 1])?;
+            if left.entries.len() > leaf_min {
+                let moved = left.entries.pop().expect("spare entry");
+                leaf.entries.insert(0, moved);
+                parent.keys[index - 1] = moved.0;
+                self.store_leaf(left);
+                self.store_leaf(leaf);
+            } else {
+                self.merge_leaves(parent, index - 1, left, leaf);
+            }
+        } else {
+            let mut right = self.leaf(parent.children[index + 1])?;
+            if right.entries.len() > leaf_min {
+                leaf.entries.push(right.entries.remove(0));
+                parent.keys[index] = right.entries[0].0;
+                self.store_leaf(right);
+                self.store_leaf(leaf);
+            } else {
+                self.merge_leaves(parent, index, leaf, right);
+            }
+        }
+        Ok(())
+    }
+
+    // `right` is child `index + 1` of `parent` and joins `left`
+    fn merge_leaves(&mut self, parent: &mut Step, index: usize, mut left: Leaf, right: Leaf) {
+        left.entries.extend(right.entries);
+        left.next = right.next;
+        parent.keys.remove(index);
+        parent.children.remove(index + 1);
+        self.release(right.page_id);
+        self.store_leaf(left);
+    }
+
+    // refill the short inner `node` by rotating a key through `parent`, or merge it with a
+    // sibling around their separator
+    fn rebalance_inner(&mut self, parent: &mut Step, mut node: Step) -> io::Result<()> {
+        let inner_min = inner_capacity(self.meta.page_size) / 2;
+        let index = parent.child;
+        let sibling = if index > 0 { index - 1 } else { index + 1 };
+        let (keys, children) = match self.node(parent.children[sibling])? {
+            Node::Inner { keys, children } => (keys, children),
+            _ => return Err(corrupt("expected an inner page")),
+        };
+        let mut sibling = Step { page_id: parent.children[sibling], keys, children, child: 0 };
+        if index > 0 {
+            if sibling.keys.len() > inner_min {
+                node.keys.insert(0, parent.keys[index - 1]);
+                node.children.insert(0, sibling.children.pop().expect("spare child"));
+                parent.keys[index - 1] = sibling.keys.pop().expect("spare key");
+                self.store_inner(sibling);
+                self.store_inner(node);
+            } else {
+                self.merge_inner(parent, index - 1, sibling, node);
+            }
+        } else if sibling.keys.len() > inner_min {
+            node.keys.push(parent.keys[index]);
+            node.children.push(sibling.children.remove(0));
+            parent.keys[index] = sibling.keys.remove(0);
+            self.store_inner(sibling);
+            self.store_inner(node);
+        } else {
+            self.merge_inner(parent, index, node, sibling);
+        }
+        Ok(())
+    }
+
+    fn merge_inner(&mut self, parent: &mut Step, index: usize, mut left: Step, right: Step) {
+        left.keys.push(parent.keys.remove(index));
+        left.keys.extend(right.keys);
+        left.children.extend(right.children);
+        parent.children.remove(index + 1);
+        self.release(right.page_id);
+        self.store_inner(left);
+    }
+}
+
+impl Drop for DiskBPlusTree {
+    // whatever the checkpoint misses is still in the log
+    fn drop(&mut self) {
+        let _ = self.checkpoint();
+    }
+}
+
+impl std::fmt::Debug for DiskBPlusTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiskBPlusTree")
+            .field("len", &self.meta.len)
+            .field("page_size", &self.meta.page_size)
+            .field("page_count", &self.meta.page_count)
+            .field("cached_pages", &self.cache.len())
+            .finish()
+    }
+}
+
+/// entries of a key range, see [`DiskBPlusTree::range`]
+pub struct Range<'a> {
+    tree: &'a mut DiskBPlusTree,
+    start: Bound<u64>,
+    end: Bound<u64>,
+    entries: std::vec::IntoIter<(u64, u64)>,
+    // next leaf of the chain, `None` before the first one is found
+    next: Option<u64>,
+    done: bool,
+}
+
+impl Range<'_> {
+    fn load(&mut self) -> io::Result<bool> {
+        let leaf = match self.next {
+            None => {
+                let first = match self.start {
+                    Bound::Included(key) | Bound::Excluded(key) => key,
+                    Bound::Unbounded => 0,
+                };
+                self.tree.descend(first)?.1
+            },
+            Some(0) => return Ok(false),
+            Some(page_id) => self.tree.leaf(page_id)?,
+        };
+        self.next = Some(leaf.next);
+        self.entries = leaf.entries.into_iter();
+        Ok(true)
+    }
+}
+
+impl Iterator for Range<'_> {
+    type Item = io::Result<(u64, u64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let (key, value) = match self.entries.next() {
+                Some(entry) => entry,
+                None => {
+                    match self.load() {
+                        Ok(true) => {},
+                        Ok(false) => self.done = true,
+                        Err(error) => {
+                            self.done = true;
+                            return Some(Err(error));
+                        },
+                    }
+                    continue;
+                },
+            };
+            let started = match self.start {
+                Bound::Included(start) => key >= start,
+                Bound::Excluded(start) => key > start,
+                Bound::Unbounded => true,
+            };
+            if !started {
+                continue;
+            }
+            let ended = match self.end {
+                Bound::Included(end) => key > end,
+                Bound::Excluded(end) => key >= end,
+                Bound::Unbounded => false,
+            };
+            if ended {
+                self.done = true;
+                return None;
+            }
+            return Some(Ok((key, value)));
+        }
+        None
+    }
+}
@@ -0,0 +1,176 @@
+//! layouts of the pages of the page file
+//!
+//! every number is stored little endian. Page 0 is the meta page, every other page is a node.
+//!
+//! meta page
+//! ```text
+//!  0..8   magic "DSABPT01"
+//!  8..12  format version, 1
+//! 12..16  page size in bytes
+//! 16..24  page number of the root
+//! 24..32  number of pages in the file, the meta page included
+//! 32..40  number of entries
+//! 40..48  page number of the first free page, 0 when none is free
+//! ```
+//!
+//! node page, a header of 16 bytes followed by its entries
+//! ```text
+//!  0      kind, 1 for a leaf, 2 for an inner node and 3 for a free page
+//!  1..2   unused
+//!  2..4   number of keys
+//!  4..8   unused
+//!  8..16  leaf: page number of the next leaf, 0 for the last one; free page: page number of the
+//!         next free page, 0 for the last one; inner node: unused
+//! leaf        16 + 16 i  key i, 24 + 16 i  value i
+//! inner node  16  child 0, then 24 + 16 i  key i, 32 + 16 i  child i + 1
+//! ```
+//! the keys of child `i` of an inner node are at least key `i - 1` and below key `i`.
+
+use std::convert::TryInto;
+use std::io;
+
+pub(super) const MAGIC: &[u8; 8] = b"DSABPT01";
+pub(super) const VERSION: u32 = 1;
+pub(super) const HEADER: usize = 16;
+/// the smallest page that fits three leaf entries and two inner keys
+pub const MIN_PAGE_SIZE: usize = 64;
+/// the largest page whose number of keys fits the two bytes of the header
+pub const MAX_PAGE_SIZE: usize = 65536;
+
+const LEAF: u8 = 1;
+const INNER: u8 = 2;
+const FREE: u8 = 3;
+
+pub(super) fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().expect("eight bytes"))
+}
+
+pub(super) fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().expect("four bytes"))
+}
+
+fn write_u64(bytes: &mut [u8], at: usize, number: u64) {
+    bytes[at..at + 8].copy_from_slice(&number.to_le_bytes());
+}
+
+pub(super) fn corrupt(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Meta {
+    pub page_size: usize,
+    pub root: u64,
+    pub page_count: u64,
+    pub len: u64,
+    pub free: u64,
+}
+
+impl Meta {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.page_size];
+        bytes[0..8].copy_from_slice(MAGIC);
+        bytes[8..12].copy_from_slice(&VERSION.to_le_bytes());
+        bytes[12..16].copy_from_slice(&(self.page_size as u32).to_le_bytes());
+        write_u64(&mut bytes, 16, self.root);
+        write_u64(&mut bytes, 24, self.page_count);
+        write_u64(&mut bytes, 32, self.len);
+        write_u64(&mut bytes, 40, self.free);
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() < 48 || &bytes[0..8] != MAGIC {
+            return Err(corrupt("not a B+ tree page file"));
+        }
+        if read_u32(bytes, 8) != VERSION {
+            return Err(corrupt("unknown page file version"));
+        }
+        let meta = Meta {
+            page_size: read_u32(bytes, 12) as usize,
+            root: read_u64(bytes, 16),
+            page_count: read_u64(bytes, 24),
+            len: read_u64(bytes, 32),
+            free: read_u64(bytes, 40),
+        };
+        if meta.page_size < MIN_PAGE_SIZE
+            || meta.page_size > MAX_PAGE_SIZE
+            || meta.root == 0
+            || meta.root >= meta.page_count
+            || meta.free >= meta.page_count
+        {
+            return Err(corrupt("meta page out of range"));
+        }
+        Ok(meta)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Node {
+    Leaf { entries: Vec<(u64, u64)>, next: u64 },
+    Inner { keys: Vec<u64>, children: Vec<u64> },
+    Free { next: u64 },
+}
+
+/// entries a leaf of `page_size` bytes holds
+pub(super) fn leaf_capacity(page_size: usize) -> usize {
+    (page_size - HEADER) / 16
+}
+
+/// keys an inner node of `page_size` bytes holds
+pub(super) fn inner_capacity(page_size: usize) -> usize {
+    (page_size - HEADER - 8) / 16
+}
+
+impl Node {
+    pub fn encode(&self, page_size: usize) -> Vec<u8> {
+        let mut bytes = vec![0; page_size];
+        match self {
+            Node::Leaf { entries, next } => {
+                bytes[0] = LEAF;
+                bytes[2..4].copy_from_slice(&(entries.len() as u16).to_le_bytes());
+                write_u64(&mut bytes, 8, *next);
+                for (i, &(key, value)) in entries.iter().enumerate() {
+                    write_u64(&mut bytes, HEADER + 16 * i, key);
+                    write_u64(&mut bytes, HEADER + 16 * i + 8, value);
+                }
+            },
+            Node::Inner { keys, children } => {
+                bytes[0] = INNER;
+                bytes[2..4].copy_from_slice(&(keys.len() as u16).to_le_bytes());
+                write_u64(&mut bytes, HEADER, children[0]);
+                for (i, &key) in keys.iter().enumerate() {
+                    write_u64(&mut bytes, HEADER + 8 + 16 * i, key);
+                    write_u64(&mut bytes, HEADER + 16 + 16 * i, children[i + 1]);
+                }
+            },
+            Node::Free { next } => {
+                bytes[0] = FREE;
+                write_u64(&mut bytes, 8, *next);
+            },
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        let count = u16::from_le_bytes([bytes[2], bytes[3]]) as usize;
+        match bytes[0] {
+            LEAF if count <= leaf_capacity(bytes.len()) => {
+                let entries = (0..count)
+                    .map(|i| {
+                        (read_u64(bytes, HEADER + 16 * i), read_u64(bytes, HEADER + 16 * i + 8))
+                    })
+                    .collect();
+                Ok(Node::Leaf { entries, next: read_u64(bytes, 8) })
+            },
+            INNER if count > 0 && count <= inner_capacity(bytes.len()) => {
+                let keys = (0..count).map(|i| read_u64(bytes, HEADER + 8 + 16 * i)).collect();
+                let mut children = vec![read_u64(bytes, HEADER)];
+                children.extend((0..count).map(|i| read_u64(bytes, HEADER + 16 + 16 * i)));
+                Ok(Node::Inner { keys, children })
+            },
+            FREE => Ok(Node::Free { next: read_u64(bytes, 8) }),
+            _ => Err(corrupt("bad node page")),
+        }
+    }
+}
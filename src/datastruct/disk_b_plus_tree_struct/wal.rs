@@ -0,0 +1,150 @@
+//! write-ahead log of whole page images
+//!
+//! ```text
+//! header  0..8 magic "DSAWAL01", 8..12 page size, 12..16 unused
+//! frame   page number (8 bytes) followed by the page image
+//! commit  u64::MAX, number of frames of the batch, FNV-1a hash of its frames (8 bytes each)
+//! ```
+//! a batch is the frames of one operation closed by its commit record. A batch without a commit
+//! record or with a wrong hash was torn by a crash and is dropped together with everything after
+//! it: opening the log cuts it back to the end of the last valid commit record, so the batches
+//! appended later follow the committed ones directly.
+
+use super::page::{corrupt, read_u32, read_u64};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"DSAWAL01";
+const HEADER: u64 = 16;
+const COMMIT: u64 = u64::MAX;
+
+/// page number and image of every page written by one operation
+pub(super) type Batch = Vec<(u64, Vec<u8>)>;
+
+pub(super) struct Wal {
+    file: File,
+    page_size: usize,
+    sync: bool,
+    // end of the last commit record, the next batch goes there
+    end: u64,
+}
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+impl Wal {
+    /// open or create the log, giving back the batches committed to it
+    pub fn open(path: &Path, page_size: usize, sync: bool) -> io::Result<(Self, Vec<Batch>)> {
+        let mut file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        let mut wal = Wal { file, page_size, sync, end: HEADER };
+        if (bytes.len() as u64) < HEADER {
+            wal.reset()?;
+            return Ok((wal, Vec::new()));
+        }
+        if &bytes[0..8] != MAGIC {
+            return Err(corrupt("not a write-ahead log"));
+        }
+        if read_u32(&bytes, 8) as usize != page_size {
+            return Err(corrupt("write-ahead log has another page size"));
+        }
+        let (batches, committed) = wal.committed(&bytes[HEADER as usize..]);
+        wal.end = HEADER + committed as u64;
+        if wal.end < bytes.len() as u64 {
+            // a torn batch left by a crash
+            wal.file.set_len(wal.end)?;
+            if wal.sync {
+                wal.file.sync_all()?;
+            }
+        }
+        Ok((wal, batches))
+    }
+
+    // the batches up to the first torn one, with the number of bytes they take
+    fn committed(&self, mut bytes: &[u8]) -> (Vec<Batch>, usize) {
+        let total = bytes.len();
+        let mut committed = 0;
+        let mut batches = Vec::new();
+        let mut batch = Vec::new();
+        let mut hash = FNV_OFFSET;
+        while bytes.len() >= 8 {
+            let page_id = read_u64(bytes, 0);
+            if page_id == COMMIT {
+                if bytes.len() < 24
+                    || read_u64(bytes, 8) != batch.len() as u64
+                    || read_u64(bytes, 16) != hash
+                {
+                    break;
+                }
+                batches.push(std::mem::take(&mut batch));
+                hash = FNV_OFFSET;
+                bytes = &bytes[24..];
+                committed = total - bytes.len();
+                continue;
+            }
+            let frame = 8 + self.page_size;
+            if bytes.len() < frame {
+                break;
+            }
+            hash = fnv1a(hash, &bytes[..frame]);
+            batch.push((page_id, bytes[8..frame].to_vec()));
+            bytes = &bytes[frame..];
+        }
+        (batches, committed)
+    }
+
+    /// append the pages of one operation and its commit record, the operation is durable once
+    /// this returns when the log syncs. A failed append cuts the log back to the batches before
+    /// it
+    pub fn append(&mut self, pages: &[(u64, &[u8])]) -> io::Result<()> {
+        let mut record = Vec::with_capacity(pages.len() * (8 + self.page_size) + 24);
+        let mut hash = FNV_OFFSET;
+        for &(page_id, image) in pages {
+            let start = record.len();
+            record.extend_from_slice(&page_id.to_le_bytes());
+            record.extend_from_slice(image);
+            hash = fnv1a(hash, &record[start..]);
+        }
+        record.extend_from_slice(&COMMIT.to_le_bytes());
+        record.extend_from_slice(&(pages.len() as u64).to_le_bytes());
+        record.extend_from_slice(&hash.to_le_bytes());
+        let written = self.write_at_end(&record);
+        if written.is_err() {
+            // best effort, a tail left behind is dropped as torn when the log is opened
+            let _ = self.file.set_len(self.end);
+            return written;
+        }
+        self.end += record.len() as u64;
+        Ok(())
+    }
+
+    fn write_at_end(&mut self, record: &[u8]) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start(self.end))?;
+        self.file.write_all(record)?;
+        if self.sync {
+            self.file.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// empty the log once its pages are in the page file
+    pub fn reset(&mut self) -> io::Result<()> {
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        let mut header = [0; HEADER as usize];
+        header[0..8].copy_from_slice(MAGIC);
+        header[8..12].copy_from_slice(&(self.page_size as u32).to_le_bytes());
+        self.file.write_all(&header)?;
+        if self.sync {
+            self.file.sync_all()?;
+        }
+        self.end = HEADER;
+        Ok(())
+    }
+}
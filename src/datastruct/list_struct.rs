@@ -98,6 +98,9 @@ where
     }
 
     /// get the borrow of the element at index
+    ///
+    /// it walks the list from the head, so picking the k-th smallest of a changing set of keys
+    /// is better left to [`crate::datastruct::order_stat_tree_struct::OrderStatTree::select`]
     /// # Examples
     /// ```
     /// # use dsa_sport::datastruct::list_struct::LinkedList;
@@ -1,3 +1,20 @@
 pub mod vec_struct;
 pub mod list_struct;
 pub mod tree_struct;
+pub mod binary_tree_struct;
+pub mod bst_core;
+pub mod bst_map_struct;
+pub mod avl_map_struct;
+pub mod rb_tree_map_struct;
+pub mod b_tree_map_struct;
+pub mod disk_b_plus_tree_struct;
+pub mod sized_core;
+pub mod treap_struct;
+pub mod splay_tree_struct;
+pub mod order_stat_tree_struct;
+pub mod node_arena_struct;
+pub mod arena_list_struct;
+pub(crate) mod rng;
+pub mod skip_list_struct;
+pub mod unrolled_list_struct;
+pub mod xor_list_struct;
@@ -0,0 +1,435 @@
+//! index based singly linked list written `NodeArena<T>`
+//!
+//! unlike [`crate::datastruct::list_struct::LinkedList`] every member of the arena is addressed by
+//! its slot index, so the `next` (and `random`) link of any member can be pointed at any other
+//! member. This makes it possible to build cyclic and intersecting lists which are the input of the
+//! classic cycle detection exercises (Floyd, Brent) implemented here.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+//! let mut arena = NodeArena::new();
+//! arena.add_node(1);
+//! let two = arena.add_node(2);
+//! arena.add_node(3);
+//! let four = arena.add_node(4);
+//! assert_eq!(format!("{:?}", arena), "⎩1⎭↪⎩2⎭↪⎩3⎭↪⎩4⎭↪✘");
+//! arena.set_next(four, Some(two));
+//! assert_eq!(format!("{:?}", arena), "⎩1⎭↪⎩2⎭↪⎩3⎭↪⎩4⎭↪⟲⎩2⎭");
+//! assert_eq!(arena.cycle_start(), Some(two));
+//! assert_eq!(arena.cycle_length(), Some(3));
+//! ```
+
+use crate::datastruct::vec_struct::Vector;
+
+const LEFT: &str = "⎩";
+const PHI: &str = "✘";
+const RIGHT: &str = "⎭";
+const LINK: &str = "↪";
+const LOOP: &str = "⟲";
+
+pub struct NodeArena<T> {
+    nodes: Vector<Member<T>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+struct Member<T> {
+    data: T,
+    next: Option<usize>,
+    random: Option<usize>,
+}
+
+impl<T> NodeArena<T> {
+    pub fn new() -> Self {
+        Self { nodes: Vector::new(), head: None, tail: None }
+    }
+
+    /// number of members stored in the arena, reachable from the head or not
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// add node after the last node added with this function and return its index
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+    /// let mut arena = NodeArena::new();
+    /// assert_eq!(arena.add_node('a'), 0);
+    /// assert_eq!(arena.add_node('b'), 1);
+    /// assert_eq!(arena.head(), Some(0));
+    /// assert_eq!(arena.next(0), Some(1));
+    /// ```
+    pub fn add_node(&mut self, element: T) -> usize {
+        let index = self.add_detached(element);
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+        index
+    }
+
+    /// add node which is not linked from anywhere, used to build a second list inside the same
+    /// arena
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+    /// let mut arena = NodeArena::new();
+    /// arena.add_node('a');
+    /// let b = arena.add_detached('b');
+    /// assert_eq!(arena.next(0), None);
+    /// assert_eq!(arena.get(b), Some(&'b'));
+    /// ```
+    pub fn add_detached(&mut self, element: T) -> usize {
+        self.nodes.push_back(Member { data: element, next: None, random: None });
+        self.nodes.len() - 1
+    }
+
+    pub fn head(&self) -> Option<usize> {
+        self.head
+    }
+
+    pub fn set_head(&mut self, head: Option<usize>) {
+        self.head = head;
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.nodes.len() {
+            Some(&self.nodes[index].data)
+        } else {
+            None
+        }
+    }
+
+    pub fn next(&self, index: usize) -> Option<usize> {
+        self.nodes[index].next
+    }
+
+    pub fn random(&self, index: usize) -> Option<usize> {
+        self.nodes[index].random
+    }
+
+    /// point the `next` link of member `from` at any member of the arena
+    /// # Panics
+    /// panics if `from` or `to` is not a valid index
+    pub fn set_next(&mut self, from: usize, to: Option<usize>) {
+        self.check_index(to);
+        self.nodes[from].next = to;
+    }
+
+    /// point the `random` link of member `from` at any member of the arena
+    /// # Panics
+    /// panics if `from` or `to` is not a valid index
+    pub fn set_random(&mut self, from: usize, to: Option<usize>) {
+        self.check_index(to);
+        self.nodes[from].random = to;
+    }
+
+    /// Floyd tortoise and hare, slow moves one step and fast two steps O(n) time O(1) space
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+    /// let mut arena = NodeArena::new();
+    /// let a = arena.add_node(1);
+    /// let b = arena.add_node(2);
+    /// assert!(!arena.has_cycle_floyd());
+    /// arena.set_next(b, Some(a));
+    /// assert!(arena.has_cycle_floyd());
+    /// ```
+    pub fn has_cycle_floyd(&self) -> bool {
+        self.floyd_meet(self.head).is_some()
+    }
+
+    /// Brent teleporting tortoise, same complexity as [`NodeArena::has_cycle_floyd`] but with
+    /// fewer link evaluations
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+    /// let mut arena = NodeArena::new();
+    /// let a = arena.add_node(1);
+    /// let b = arena.add_node(2);
+    /// assert!(!arena.has_cycle_brent());
+    /// arena.set_next(b, Some(b));
+    /// assert!(arena.has_cycle_brent());
+    /// ```
+    pub fn has_cycle_brent(&self) -> bool {
+        self.brent_length(self.head).is_some()
+    }
+
+    /// index of the member where the cycle re-enters the list
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+    /// let mut arena = NodeArena::new();
+    /// arena.add_node(1);
+    /// let b = arena.add_node(2);
+    /// let c = arena.add_node(3);
+    /// assert_eq!(arena.cycle_start(), None);
+    /// arena.set_next(c, Some(b));
+    /// assert_eq!(arena.cycle_start(), Some(b));
+    /// ```
+    pub fn cycle_start(&self) -> Option<usize> {
+        self.cycle_start_from(self.head)
+    }
+
+    /// number of members on the cycle, found with Brent's algorithm
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+    /// let mut arena = NodeArena::new();
+    /// let a = arena.add_node(1);
+    /// arena.add_node(2);
+    /// let c = arena.add_node(3);
+    /// assert_eq!(arena.cycle_length(), None);
+    /// arena.set_next(c, Some(a));
+    /// assert_eq!(arena.cycle_length(), Some(3));
+    /// ```
+    pub fn cycle_length(&self) -> Option<usize> {
+        self.brent_length(self.head)
+    }
+
+    /// first member shared by the lists starting at `first` and `second`, lists are allowed to
+    /// be cyclic
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+    /// let mut arena = NodeArena::new();
+    /// let a = arena.add_node('a');
+    /// let c = arena.add_node('c');
+    /// arena.add_node('d');
+    /// let b = arena.add_detached('b');
+    /// assert_eq!(arena.intersection(Some(a), Some(b)), None);
+    /// arena.set_next(b, Some(c));
+    /// assert_eq!(arena.intersection(Some(a), Some(b)), Some(c));
+    /// ```
+    pub fn intersection(&self, first: Option<usize>, second: Option<usize>) -> Option<usize> {
+        let entry_one = self.cycle_start_from(first);
+        let entry_two = self.cycle_start_from(second);
+        match (entry_one, entry_two) {
+            (None, None) => self.merge_point(first, second, None),
+            (Some(one), Some(two)) => {
+                if one == two {
+                    return match self.merge_point(first, second, Some(one)) {
+                        Some(index) => Some(index),
+                        None => Some(one),
+                    };
+                }
+                // two different entries share a member only if they lie on the same cycle
+                let mut current = self.nodes[one].next;
+                while current != Some(one) {
+                    if current == Some(two) {
+                        return Some(one);
+                    }
+                    current = current.and_then(|index| self.nodes[index].next);
+                }
+                None
+            },
+            _ => None,
+        }
+    }
+
+    /// copy the list reachable from the head through both `next` and `random` links, the copy is
+    /// compacted so the head of the list becomes index 0
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::node_arena_struct::NodeArena;
+    /// let mut arena = NodeArena::new();
+    /// let a = arena.add_node(1);
+    /// let b = arena.add_node(2);
+    /// arena.add_detached(99);
+    /// arena.set_random(a, Some(b));
+    /// arena.set_random(b, Some(b));
+    /// let copy = arena.copy_random_list();
+    /// assert_eq!(copy.len(), 2);
+    /// assert_eq!(format!("{:?}", copy), "⎩1⎭↪⎩2⎭↪✘");
+    /// assert_eq!(copy.random(0), Some(1));
+    /// assert_eq!(copy.random(1), Some(1));
+    /// ```
+    pub fn copy_random_list(&self) -> NodeArena<T>
+    where
+        T: Clone,
+    {
+        let mut copy = NodeArena::new();
+        let mut mapping: Vector<Option<usize>> = Vector::new();
+        for _ in 0..self.nodes.len() {
+            mapping.push_back(None);
+        }
+        let mut pending = Vector::new();
+        if let Some(head) = self.head {
+            pending.push_back(head);
+            mapping[head] = Some(copy.add_detached(self.nodes[head].data.clone()));
+        }
+        // breadth first over both links, a random target can be reached before the members
+        // further down the next chain so the copy does not keep the order of the list
+        while let Some(index) = pending.pop_front() {
+            for link in [self.nodes[index].next, self.nodes[index].random].iter() {
+                if let Some(target) = *link {
+                    if mapping[target].is_none() {
+                        mapping[target] = Some(copy.add_detached(self.nodes[target].data.clone()));
+                        pending.push_back(target);
+                    }
+                }
+            }
+        }
+        for index in 0..self.nodes.len() {
+            if let Some(new_index) = mapping[index] {
+                copy.nodes[new_index].next = self.nodes[index].next.and_then(|i| mapping[i]);
+                copy.nodes[new_index].random = self.nodes[index].random.and_then(|i| mapping[i]);
+            }
+        }
+        copy.head = self.head.and_then(|i| mapping[i]);
+        copy.tail = copy.head.map(|head| copy.last_before_cycle(head));
+        copy
+    }
+}
+
+// private associated functions
+impl<T> NodeArena<T> {
+    fn check_index(&self, index: Option<usize>) {
+        if let Some(index) = index {
+            if index >= self.nodes.len() {
+                panic!("Out of bounds access");
+            }
+        }
+    }
+
+    fn step(&self, index: Option<usize>) -> Option<usize> {
+        index.and_then(|index| self.nodes[index].next)
+    }
+
+    fn floyd_meet(&self, head: Option<usize>) -> Option<usize> {
+        let mut slow = head;
+        let mut fast = head;
+        loop {
+            fast = self.step(self.step(fast));
+            slow = self.step(slow);
+            fast?;
+            if slow == fast {
+                return slow;
+            }
+        }
+    }
+
+    fn brent_length(&self, head: Option<usize>) -> Option<usize> {
+        let mut power = 1;
+        let mut length = 1;
+        let mut tortoise = head;
+        let mut hare = self.step(head);
+        while hare.is_some() {
+            if tortoise == hare {
+                return Some(length);
+            }
+            if power == length {
+                tortoise = hare;
+                power *= 2;
+                length = 0;
+            }
+            hare = self.step(hare);
+            length += 1;
+        }
+        None
+    }
+
+    fn cycle_start_from(&self, head: Option<usize>) -> Option<usize> {
+        let mut meet = self.floyd_meet(head)?;
+        let mut current = head?;
+        while current != meet {
+            current = self.nodes[current].next?;
+            meet = self.nodes[meet].next?;
+        }
+        Some(current)
+    }
+
+    // length of the list until `stop` (exclusive) or the end of the list
+    fn length_until(&self, head: Option<usize>, stop: Option<usize>) -> usize {
+        let mut length = 0;
+        let mut current = head;
+        while current.is_some() && current != stop {
+            length += 1;
+            current = self.step(current);
+        }
+        length
+    }
+
+    fn merge_point(
+        &self,
+        mut first: Option<usize>,
+        mut second: Option<usize>,
+        stop: Option<usize>,
+    ) -> Option<usize> {
+        let mut len_one = self.length_until(first, stop);
+        let mut len_two = self.length_until(second, stop);
+        while len_one > len_two {
+            first = self.step(first);
+            len_one -= 1;
+        }
+        while len_two > len_one {
+            second = self.step(second);
+            len_two -= 1;
+        }
+        while first != stop && first != second {
+            first = self.step(first);
+            second = self.step(second);
+        }
+        if first == stop {
+            None
+        } else {
+            first
+        }
+    }
+
+    fn last_before_cycle(&self, head: usize) -> usize {
+        let entry = self.cycle_start_from(Some(head));
+        let mut current = head;
+        while let Some(next) = self.nodes[current].next {
+            if Some(next) == entry {
+                break;
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+impl<T> Default for NodeArena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> std::fmt::Debug for NodeArena<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut out = String::new();
+        let mut visited: Vector<bool> = Vector::new();
+        for _ in 0..self.nodes.len() {
+            visited.push_back(false);
+        }
+        let mut current = self.head;
+        loop {
+            match current {
+                None => {
+                    out += PHI;
+                    break;
+                },
+                Some(index) if visited[index] => {
+                    out += &format!("{}{}{:?}{}", LOOP, LEFT, self.nodes[index].data, RIGHT);
+                    break;
+                },
+                Some(index) => {
+                    visited[index] = true;
+                    out += &format!("{}{:?}{}{}", LEFT, self.nodes[index].data, RIGHT, LINK);
+                    current = self.nodes[index].next;
+                },
+            }
+        }
+        write!(f, "{}", out)
+    }
+}
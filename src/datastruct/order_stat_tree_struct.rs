@@ -0,0 +1,342 @@
+//! order-statistic tree written `OrderStatTree<K>`
+//!
+//! a height balanced binary search tree of keys where every member also keeps the number of
+//! keys in its subtree. Walking down from the root and adding up the sizes of the subtrees left
+//! behind answers [`OrderStatTree::rank`], the number of keys below a key, and
+//! [`OrderStatTree::select`], the key at a sorted position, in O(log n), and the difference of
+//! two ranks counts the keys of a range. The members, searches and rotations are those of
+//! [`crate::datastruct::bst_core`], whose rotations refresh the sizes, and balance is kept by the
+//! retrace of [`crate::datastruct::avl_map_struct::AvlMap`], which refreshes them along with the
+//! heights.
+//!
+//! A tree made by [`OrderStatTree::new_multiset`] keeps duplicate keys: a member holds a key
+//! with its number of copies and every copy counts in the sizes, ranks and positions.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::order_stat_tree_struct::OrderStatTree;
+//! let mut scores = OrderStatTree::new_multiset();
+//! scores.extend([70, 40, 90, 40, 60].iter().copied());
+//! assert_eq!(scores.select(1), Some(&40));
+//! assert_eq!(scores.select(2), Some(&60));
+//! assert_eq!(scores.rank(&60), 2);
+//! assert_eq!(scores.count_range(40..=70), 4);
+//! assert_eq!(
+//!     format!("{}", scores),
+//!     "╚═70 [5]\n  ╠═L 40 ×2 [3]\n  ║ ╚═R 60 [1]\n  ╚═R 90 [1]\n"
+//! );
+//! ```
+
+use crate::datastruct::avl_map_struct::{self, balance_factor, height_of};
+use crate::datastruct::bst_core::{self, Core, Counted, Member};
+use core::cmp::Ordering;
+use core::ops::{Bound, RangeBounds};
+
+pub struct OrderStatTree<K> {
+    // the value of a member is the number of copies of its key, only above one in a multiset
+    core: Core<K, usize, Counted<usize>>,
+    multiset: bool,
+}
+
+// a member with the height and the number of keys of its subtree, every copy counted
+type Node<K> = Member<K, usize, Counted<usize>>;
+
+// keys in the subtree below `node` with their copies, zero for a missing one
+unsafe fn size_of<K>(node: *mut Node<K>) -> usize {
+    node.as_ref().map_or(0, |node| node.extra.size)
+}
+
+impl<K> OrderStatTree<K> {
+    /// tree keeping each key once
+    pub fn new() -> Self {
+        Self { core: Core::new(), multiset: false }
+    }
+
+    /// tree keeping every copy of a key inserted more than once
+    pub fn new_multiset() -> Self {
+        Self { core: Core::new(), multiset: true }
+    }
+
+    pub fn is_multiset(&self) -> bool {
+        self.multiset
+    }
+
+    /// number of keys, every copy counted
+    pub fn len(&self) -> usize {
+        unsafe { size_of(self.core.root) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.core.root.is_null()
+    }
+
+    /// number of levels, the empty tree has none
+    pub fn height(&self) -> usize {
+        unsafe { height_of(self.core.root) }
+    }
+
+    pub fn clear(&mut self) {
+        self.core = Core::new();
+    }
+
+    /// key at sorted position `index`, copies taking a position each
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::order_stat_tree_struct::OrderStatTree;
+    /// let tree: OrderStatTree<u32> = [50, 10, 40, 20, 30].iter().copied().collect();
+    /// assert_eq!(tree.select(0), Some(&10));
+    /// assert_eq!(tree.select(3), Some(&40));
+    /// assert_eq!(tree.select(5), None);
+    /// ```
+    pub fn select(&self, mut index: usize) -> Option<&K> {
+        let mut node = self.core.root;
+        while let Some(current) = unsafe { node.as_ref() } {
+            let before = unsafe { size_of(current.left) };
+            if index < before {
+                node = current.left;
+            } else if index < before + current.value {
+                return Some(&current.key);
+            } else {
+                index -= before + current.value;
+                node = current.right;
+            }
+        }
+        None
+    }
+
+    pub fn min(&self) -> Option<&K> {
+        self.select(0)
+    }
+
+    pub fn max(&self) -> Option<&K> {
+        self.len().checked_sub(1).and_then(|index| self.select(index))
+    }
+
+    /// keys in sorted order, a key repeated once per copy
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter { walk: self.core.iter(), key: None, copies: 0 }
+    }
+}
+
+impl<K> OrderStatTree<K>
+where
+    K: Ord,
+{
+    /// put a copy of `key` in, which a set refuses when it holds the key already
+    pub fn insert(&mut self, key: K) -> bool {
+        match self.core.search(&key) {
+            Ok(node) if self.multiset => unsafe {
+                (*node).value += 1;
+                self.retrace(node);
+                true
+            },
+            Ok(_) => false,
+            Err(slot) => {
+                let counted = Counted { balance: 1, size: 1 };
+                let node = Member::allocate_memory(Member::new(key, 1, slot.parent, counted));
+                unsafe {
+                    self.core.attach(node, &slot);
+                    self.retrace(slot.parent);
+                }
+                true
+            },
+        }
+    }
+
+    /// take one copy of `key` out
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.take(key, 1) > 0
+    }
+
+    /// take every copy of `key` out and give back how many there were
+    pub fn remove_all(&mut self, key: &K) -> usize {
+        self.take(key, usize::MAX)
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.count(key) > 0
+    }
+
+    /// number of copies of `key`
+    pub fn count(&self, key: &K) -> usize {
+        unsafe { self.core.find(key).as_ref().map_or(0, |node| node.value) }
+    }
+
+    /// number of keys below `key`, which is the position of its first copy when it is there
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::order_stat_tree_struct::OrderStatTree;
+    /// let tree: OrderStatTree<u32> = [50, 10, 40, 20, 30].iter().copied().collect();
+    /// assert_eq!(tree.rank(&10), 0);
+    /// assert_eq!(tree.rank(&35), 3);
+    /// assert_eq!(tree.rank(&99), 5);
+    /// ```
+    pub fn rank(&self, key: &K) -> usize {
+        self.count_below(key, false)
+    }
+
+    /// number of keys in `range`, every copy counted, a range whose start is above its end is
+    /// empty
+    pub fn count_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<K>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.count_below(start, false),
+            Bound::Excluded(start) => self.count_below(start, true),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.count_below(end, true),
+            Bound::Excluded(end) => self.count_below(end, false),
+            Bound::Unbounded => self.len(),
+        };
+        end.saturating_sub(start)
+    }
+
+    /// check the key order, the stored heights and sizes, that no balance factor is beyond
+    /// `-1..=1` and that a set holds no copies
+    pub fn check_invariants(&self) -> bool {
+        if self.core.check_shape().is_err() {
+            return false;
+        }
+        let mut walk = self.core.iter();
+        core::iter::from_fn(move || walk.next_member()).all(|node| unsafe {
+            let (left, right) = ((*node).left, (*node).right);
+            (*node).extra.balance == 1 + height_of(left).max(height_of(right))
+                && (*node).extra.size == (*node).value + size_of(left) + size_of(right)
+                && balance_factor(node).abs() <= 1
+                && (*node).value > 0
+                && (self.multiset || (*node).value == 1)
+        })
+    }
+
+    // number of keys below `key`, or not above it when `inclusive`
+    fn count_below(&self, key: &K, inclusive: bool) -> usize {
+        let mut below = 0;
+        let mut node = self.core.root;
+        while let Some(current) = unsafe { node.as_ref() } {
+            let before = unsafe { size_of(current.left) };
+            match key.cmp(&current.key) {
+                Ordering::Less => node = current.left,
+                Ordering::Equal if !inclusive => return below + before,
+                Ordering::Equal => return below + before + current.value,
+                Ordering::Greater => {
+                    below += before + current.value;
+                    node = current.right;
+                },
+            }
+        }
+        below
+    }
+
+    // take up to `copies` copies of `key` out and give back the number taken, a member left with
+    // none is cut out and the tree retraced from the parent of the member cut
+    fn take(&mut self, key: &K, copies: usize) -> usize {
+        let node = self.core.find(key);
+        if node.is_null() {
+            return 0;
+        }
+        unsafe {
+            if (*node).value > copies {
+                (*node).value -= copies;
+                self.retrace(node);
+                return copies;
+            }
+            let cut = self.core.detach(node);
+            self.retrace(cut.parent);
+            Member::deallocate_memory(cut.member).value
+        }
+    }
+
+    // refresh the sizes and heights from `node` up to the root, rebalancing on the way
+    unsafe fn retrace(&mut self, node: *mut Node<K>) {
+        avl_map_struct::retrace(&mut self.core, node, true, |_, _| {});
+    }
+}
+
+/// iterator of [`OrderStatTree::iter`]
+pub struct Iter<'a, K> {
+    walk: bst_core::Iter<'a, K, usize, Counted<usize>>,
+    // the key being repeated with its copies not given yet
+    key: Option<&'a K>,
+    copies: usize,
+}
+
+impl<'a, K> Iterator for Iter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.copies == 0 {
+            let (key, &copies) = self.walk.next()?;
+            self.key = Some(key);
+            self.copies = copies;
+        }
+        self.copies -= 1;
+        self.key
+    }
+}
+
+impl<'a, K> IntoIterator for &'a OrderStatTree<K> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K> core::iter::FromIterator<K> for OrderStatTree<K>
+where
+    K: Ord,
+{
+    /// a set, duplicates past the first are dropped
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut tree = OrderStatTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K> Extend<K> for OrderStatTree<K>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<K> Default for OrderStatTree<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> std::fmt::Display for OrderStatTree<K>
+where
+    K: std::fmt::Display,
+{
+    /// every member with its number of copies when above one and its subtree size in brackets
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let drawing = self.core.render(|candidate_ptr| {
+            let node = unsafe { &*candidate_ptr };
+            match node.value {
+                1 => format!("{} [{}]", node.key, node.extra.size),
+                count => format!("{} ×{} [{}]", node.key, count, node.extra.size),
+            }
+        });
+        write!(f, "{}", drawing)
+    }
+}
+
+impl<K> std::fmt::Debug for OrderStatTree<K>
+where
+    K: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,561 @@
+//! red-black tree map written `RbTreeMap<K, V>`
+//!
+//! every member is red or black, the root is black, a red member has no red child and every path
+//! from a member down to a missing child passes the same number of black members. The longest
+//! path is then at most twice the shortest one. Insertion and removal follow the classic fixups
+//! of Cormen et al., recolouring on the way up and rotating at most twice after an insertion and
+//! three times after a removal. The members, walks, searches and rotations are those of
+//! [`crate::datastruct::bst_core`], the colour sits in the balance data of a member, and members
+//! keep a link to their parent, so no fixup needs a stack.
+//!
+//! `Display` draws the tree with the red members in red ANSI colour, `Debug` draws it with every
+//! member annotated `(R)` or `(B)`.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::rb_tree_map_struct::RbTreeMap;
+//! let mut map = RbTreeMap::new();
+//! for word in "the quick brown fox jumps over the lazy dog".split(' ') {
+//!     *map.entry(word).or_insert(0) += 1;
+//! }
+//! assert_eq!(map.get(&"the"), Some(&2));
+//! assert_eq!(map.len(), 8);
+//! assert_eq!(map.check_invariants(), Ok(2));
+//! let map: RbTreeMap<u8, char> = [(2, 'b'), (1, 'a'), (3, 'c'), (4, 'd')].iter().copied().collect();
+//! assert_eq!(format!("{:?}", map), "╚═2: 'b' (B)\n  ╠═L 1: 'a' (B)\n  ╚═R 3: 'c' (B)\n    ╚═R 4: 'd' (R)\n");
+//! assert_eq!(format!("{}", map).lines().last(), Some("    ╚═R \u{1b}[31m4: 'd'\u{1b}[0m"));
+//! ```
+
+use crate::datastruct::bst_core::{self, Augment, Core, Member, ShapeFault, Slot};
+use crate::datastruct::vec_struct::Vector;
+use core::mem;
+use core::ops::RangeBounds;
+
+const RED: &str = "\u{1b}[31m";
+const RESET: &str = "\u{1b}[0m";
+
+pub struct RbTreeMap<K, V> {
+    core: Core<K, V, Colour>,
+}
+
+/// colour of a member of a [`RbTreeMap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Black,
+}
+
+impl<V> Augment<V> for Colour {}
+
+/// iterator of [`RbTreeMap::iter`] and [`RbTreeMap::range`]
+pub type Iter<'a, K, V> = bst_core::Iter<'a, K, V, Colour>;
+
+/// iterator of [`RbTreeMap::iter_mut`]
+pub type IterMut<'a, K, V> = bst_core::IterMut<'a, K, V, Colour>;
+
+// a member with its colour as balance data
+type Node<K, V> = Member<K, V, Colour>;
+
+/// the red-black property found broken by [`RbTreeMap::check_invariants`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RbViolation {
+    /// a child does not link back to its parent
+    BrokenLink,
+    /// a key is on the wrong side of one of its ancestors
+    OutOfOrder,
+    /// the root is red
+    RedRoot,
+    /// a red member has a red child
+    RedChildOfRed,
+    /// two paths down to a missing child pass different numbers of black members
+    UnequalBlackHeight,
+    /// the number of members differs from the length of the map
+    LengthMismatch,
+}
+
+impl std::fmt::Display for RbViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self {
+            RbViolation::BrokenLink => "child does not link back to its parent",
+            RbViolation::OutOfOrder => "key is out of order",
+            RbViolation::RedRoot => "root is red",
+            RbViolation::RedChildOfRed => "red member has a red child",
+            RbViolation::UnequalBlackHeight => "paths differ in black height",
+            RbViolation::LengthMismatch => "member count differs from the length",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+impl std::error::Error for RbViolation {}
+
+// a missing child counts as black
+unsafe fn is_red<K, V>(node: *mut Node<K, V>) -> bool {
+    node.as_ref().is_some_and(|node| node.extra == Colour::Red)
+}
+
+unsafe fn paint<K, V>(node: *mut Node<K, V>, colour: Colour) {
+    if let Some(node) = node.as_mut() {
+        node.extra = colour;
+    }
+}
+
+impl<K, V> RbTreeMap<K, V> {
+    pub fn new() -> Self {
+        Self { core: Core::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.core.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.core.len == 0
+    }
+
+    /// number of levels, the empty map has none
+    pub fn height(&self) -> usize {
+        self.core.height()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// smallest key with its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        self.core.min()
+    }
+
+    /// largest key with its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        self.core.max()
+    }
+
+    /// entries in key order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.core.iter()
+    }
+
+    /// entries in key order with mutable values
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.core.iter_mut()
+    }
+
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    // hang the red member `node` in `slot` and mend a red member below a red parent,
+    // recolouring while the uncle is red and rotating once or twice when it is black
+    unsafe fn attach(&mut self, node: *mut Node<K, V>, slot: &Slot<K, V, Colour>) {
+        self.core.attach(node, slot);
+
+        let mut node = node;
+        while is_red((*node).parent) {
+            let mut parent = (*node).parent;
+            // a red parent is never the root
+            let grandparent = (*parent).parent;
+            let parent_is_left = (*grandparent).left == parent;
+            let uncle = if parent_is_left { (*grandparent).right } else { (*grandparent).left };
+            if is_red(uncle) {
+                paint(parent, Colour::Black);
+                paint(uncle, Colour::Black);
+                paint(grandparent, Colour::Red);
+                node = grandparent;
+                continue;
+            }
+            let inner =
+                if parent_is_left { (*parent).right == node } else { (*parent).left == node };
+            if inner {
+                node = parent;
+                if parent_is_left {
+                    self.core.rotate_left(node);
+                } else {
+                    self.core.rotate_right(node);
+                }
+                parent = (*node).parent;
+            }
+            paint(parent, Colour::Black);
+            paint(grandparent, Colour::Red);
+            if parent_is_left {
+                self.core.rotate_right(grandparent);
+            } else {
+                self.core.rotate_left(grandparent);
+            }
+        }
+        paint(self.core.root, Colour::Black);
+    }
+
+    // cut `target` out, a member with two children takes over the entry of its successor which
+    // is cut out instead. Cutting out a black member leaves its place one black short, which is
+    // pushed up the tree or settled by the sibling
+    unsafe fn detach(&mut self, target: *mut Node<K, V>) -> (K, V) {
+        let cut = self.core.detach(target);
+        let mut parent = cut.parent;
+        let member = Member::deallocate_memory(cut.member);
+        if member.extra == Colour::Red {
+            return (member.key, member.value);
+        }
+
+        // `node` is one black short, it may be a missing child of `parent`
+        let mut node = cut.child;
+        while node != self.core.root && !is_red(node) {
+            let node_is_left = (*parent).left == node;
+            let mut sibling = if node_is_left { (*parent).right } else { (*parent).left };
+            if is_red(sibling) {
+                paint(sibling, Colour::Black);
+                paint(parent, Colour::Red);
+                if node_is_left {
+                    self.core.rotate_left(parent);
+                } else {
+                    self.core.rotate_right(parent);
+                }
+                sibling = if node_is_left { (*parent).right } else { (*parent).left };
+            }
+            let (near, far) = if node_is_left {
+                ((*sibling).left, (*sibling).right)
+            } else {
+                ((*sibling).right, (*sibling).left)
+            };
+            if !is_red(near) && !is_red(far) {
+                paint(sibling, Colour::Red);
+                node = parent;
+                parent = (*node).parent;
+                continue;
+            }
+            if !is_red(far) {
+                paint(near, Colour::Black);
+                paint(sibling, Colour::Red);
+                if node_is_left {
+                    self.core.rotate_right(sibling);
+                } else {
+                    self.core.rotate_left(sibling);
+                }
+                sibling = if node_is_left { (*parent).right } else { (*parent).left };
+            }
+            (*sibling).extra = (*parent).extra;
+            paint(parent, Colour::Black);
+            paint(
+                if node_is_left { (*sibling).right } else { (*sibling).left },
+                Colour::Black,
+            );
+            if node_is_left {
+                self.core.rotate_left(parent);
+            } else {
+                self.core.rotate_right(parent);
+            }
+            node = self.core.root;
+        }
+        paint(node, Colour::Black);
+        (member.key, member.value)
+    }
+}
+
+impl<K, V> RbTreeMap<K, V>
+where
+    K: Ord,
+{
+    /// put `value` under `key` and give back the value it replaces
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            },
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        unsafe { self.core.find(key).as_ref().map(|node| &node.value) }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { self.core.find(key).as_mut().map(|node| &mut node.value) }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        !self.core.find(key).is_null()
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let target = self.core.find(key);
+        if target.is_null() {
+            return None;
+        }
+        unsafe { Some(self.detach(target)) }
+    }
+
+    /// place of `key` in the map, to look at, change, fill or empty it with one search
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::rb_tree_map_struct::{Entry, RbTreeMap};
+    /// let mut map: RbTreeMap<&str, Vec<u32>> = RbTreeMap::new();
+    /// map.entry("odd").or_default().push(1);
+    /// map.entry("odd").and_modify(|odd| odd.push(3)).or_default();
+    /// map.entry("even").or_insert_with(|| vec![2]);
+    /// if let Entry::Occupied(entry) = map.entry("even") {
+    ///     assert_eq!(entry.remove(), vec![2]);
+    /// }
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&"odd", &vec![1, 3])]);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.core.search(&key) {
+            Ok(node) => Entry::Occupied(OccupiedEntry { map: self, node }),
+            Err(slot) => Entry::Vacant(VacantEntry { map: self, key, slot }),
+        }
+    }
+
+    /// entries with a key in `range` in key order, a range whose start is above its end is empty
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        self.core.range(range)
+    }
+
+    /// check the links, the order of the keys and the red-black properties, and give back the
+    /// black height, the number of black members on every path from the root down
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::rb_tree_map_struct::RbTreeMap;
+    /// let mut map: RbTreeMap<u32, ()> = (0..1000).map(|key| (key, ())).collect();
+    /// assert_eq!(map.check_invariants(), Ok(9));
+    /// (0..1000).filter(|key| key % 3 != 0).for_each(|key| { map.remove(&key); });
+    /// assert!(map.check_invariants().is_ok());
+    /// ```
+    /// # Errors
+    /// the first [`RbViolation`] found
+    pub fn check_invariants(&self) -> Result<usize, RbViolation> {
+        self.core.check_shape().map_err(|fault| match fault {
+            ShapeFault::BrokenLink => RbViolation::BrokenLink,
+            ShapeFault::OutOfOrder => RbViolation::OutOfOrder,
+            ShapeFault::LengthMismatch => RbViolation::LengthMismatch,
+        })?;
+        if unsafe { is_red(self.core.root) } {
+            return Err(RbViolation::RedRoot);
+        }
+        // member with the black members above it
+        let mut member_stack = Vector::new();
+        if !self.core.root.is_null() {
+            member_stack.push_back((self.core.root, 0));
+        }
+        let mut black_height = None;
+        while let Some((candidate_ptr, blacks_above)) = member_stack.pop_back() {
+            let node = unsafe { &*candidate_ptr };
+            let blacks = blacks_above + usize::from(node.extra == Colour::Black);
+            for &child in [node.left, node.right].iter() {
+                match unsafe { child.as_ref() } {
+                    None => {
+                        if *black_height.get_or_insert(blacks) != blacks {
+                            return Err(RbViolation::UnequalBlackHeight);
+                        }
+                    },
+                    Some(child_node) => {
+                        if node.extra == Colour::Red && child_node.extra == Colour::Red {
+                            return Err(RbViolation::RedChildOfRed);
+                        }
+                        member_stack.push_back((child, blacks));
+                    },
+                }
+            }
+        }
+        Ok(black_height.unwrap_or(0))
+    }
+}
+
+/// place of a key in a [`RbTreeMap`], see [`RbTreeMap::entry`]
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// place of a key that is in the map
+pub struct OccupiedEntry<'a, K, V> {
+    map: &'a mut RbTreeMap<K, V>,
+    node: *mut Node<K, V>,
+}
+
+/// place of a key that is not in the map, it knows the member to hang the key below
+pub struct VacantEntry<'a, K, V> {
+    map: &'a mut RbTreeMap<K, V>,
+    key: K,
+    slot: Slot<K, V, Colour>,
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// value of the key, `default` goes in first when the key is missing
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// change the value when the key is in the map
+    pub fn and_modify<F>(mut self, change: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(entry) = &mut self {
+            change(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        unsafe { &(*self.node).key }
+    }
+
+    pub fn get(&self) -> &V {
+        unsafe { &(*self.node).value }
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut (*self.node).value }
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { &mut (*self.node).value }
+    }
+
+    /// put `value` in and give back the value it replaces
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    pub fn remove_entry(self) -> (K, V) {
+        unsafe { self.map.detach(self.node) }
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// put the key in with `value` and give back the value in its new place
+    pub fn insert(self, value: V) -> &'a mut V {
+        let node =
+            Member::allocate_memory(Member::new(self.key, value, self.slot.parent, Colour::Red));
+        unsafe {
+            // the fixup moves members around but never the member itself
+            self.map.attach(node, &self.slot);
+            &mut (*node).value
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a RbTreeMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> core::iter::FromIterator<(K, V)> for RbTreeMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = RbTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for RbTreeMap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> Default for RbTreeMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> std::fmt::Display for RbTreeMap<K, V>
+where
+    K: std::fmt::Display,
+    V: std::fmt::Debug,
+{
+    /// the red members are written in red ANSI colour
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let out = self.core.render(|candidate_ptr| {
+            let node = unsafe { &*candidate_ptr };
+            match node.extra {
+                Colour::Red => format!("{}{}: {:?}{}", RED, node.key, node.value, RESET),
+                Colour::Black => format!("{}: {:?}", node.key, node.value),
+            }
+        });
+        write!(f, "{}", out)
+    }
+}
+
+impl<K, V> std::fmt::Debug for RbTreeMap<K, V>
+where
+    K: std::fmt::Display,
+    V: std::fmt::Debug,
+{
+    /// every member is followed by `(R)` or `(B)`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let out = self.core.render(|candidate_ptr| {
+            let node = unsafe { &*candidate_ptr };
+            let colour = if node.extra == Colour::Red { "R" } else { "B" };
+            format!("{}: {:?} ({})", node.key, node.value, colour)
+        });
+        write!(f, "{}", out)
+    }
+}
@@ -0,0 +1,199 @@
+//! sized binary tree with pending reversals shared by the split and merge trees
+//!
+//! [`crate::datastruct::treap_struct::Treap`] and
+//! [`crate::datastruct::splay_tree_struct::SplayTree`], with their sequence flavours, keep their
+//! entries in the same kind of member: a key with its value, the size of its subtree, a pending
+//! reversal, links to both children and to the parent, and the data `X` of the tree, a priority
+//! for a treap and nothing for a splay tree. Only the splay tree keeps the parent links, a treap
+//! leaves them null. Sizes, reversals, the in-order walk reading the reversals on the way, the
+//! drawing, freeing a tree and reading a range of positions are written once here, the trees add
+//! their own `split` and `merge` on top.
+
+use crate::datastruct::binary_tree_struct::render_binary_tree;
+use crate::datastruct::vec_struct::Vector;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use core::ptr;
+use std::alloc;
+
+pub(crate) struct Member<K, V, X> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+    // members in the subtree, this one included
+    pub(crate) size: usize,
+    // the children below are still to be swapped, only set in a sequence
+    pub(crate) reversed: bool,
+    pub(crate) left: *mut Member<K, V, X>,
+    pub(crate) right: *mut Member<K, V, X>,
+    pub(crate) parent: *mut Member<K, V, X>,
+    pub(crate) extra: X,
+}
+
+impl<K, V, X> Member<K, V, X> {
+    pub(crate) fn new(key: K, value: V, extra: X) -> Self {
+        Self {
+            key,
+            value,
+            size: 1,
+            reversed: false,
+            left: ptr::null_mut(),
+            right: ptr::null_mut(),
+            parent: ptr::null_mut(),
+            extra,
+        }
+    }
+
+    pub(crate) fn allocate_memory(candidate: Member<K, V, X>) -> *mut Member<K, V, X> {
+        let size = mem::size_of::<Member<K, V, X>>();
+        let align = mem::align_of::<Member<K, V, X>>();
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            let ptr = alloc::alloc(layout) as *mut Member<K, V, X>;
+            ptr.write(candidate);
+            ptr
+        }
+    }
+
+    pub(crate) unsafe fn deallocate_memory(node: *mut Member<K, V, X>) -> Member<K, V, X> {
+        let size = mem::size_of::<Member<K, V, X>>();
+        let align = mem::align_of::<Member<K, V, X>>();
+        let member = node.read();
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(node as *mut u8, layout);
+        member
+    }
+
+    // size of a subtree, zero for a missing one
+    pub(crate) unsafe fn size_of(node: *mut Member<K, V, X>) -> usize {
+        node.as_ref().map_or(0, |node| node.size)
+    }
+
+    pub(crate) unsafe fn refresh_size(node: *mut Member<K, V, X>) {
+        (*node).size = 1 + Member::size_of((*node).left) + Member::size_of((*node).right);
+    }
+
+    // carry out a pending reversal of `node` and hand it down to its children
+    pub(crate) unsafe fn push(node: *mut Member<K, V, X>) {
+        if (*node).reversed {
+            mem::swap(&mut (*node).left, &mut (*node).right);
+            for &child in [(*node).left, (*node).right].iter() {
+                if let Some(child) = child.as_mut() {
+                    child.reversed = !child.reversed;
+                }
+            }
+            (*node).reversed = false;
+        }
+    }
+
+    pub(crate) unsafe fn height_of(node: *mut Member<K, V, X>) -> usize {
+        let mut height = 0;
+        let mut member_stack = Vector::new();
+        member_stack.push_back((node, 1));
+        while let Some((candidate_ptr, depth)) = member_stack.pop_back() {
+            if let Some(node) = candidate_ptr.as_ref() {
+                height = height.max(depth);
+                member_stack.push_back((node.left, depth + 1));
+                member_stack.push_back((node.right, depth + 1));
+            }
+        }
+        height
+    }
+
+    pub(crate) unsafe fn free(root: *mut Member<K, V, X>) {
+        let mut member_stack = Vector::new();
+        member_stack.push_back(root);
+        while let Some(candidate_ptr) = member_stack.pop_back() {
+            if !candidate_ptr.is_null() {
+                let member = Member::deallocate_memory(candidate_ptr);
+                member_stack.push_back(member.left);
+                member_stack.push_back(member.right);
+            }
+        }
+    }
+
+    // the members as drawn by the binary tree, in the order the pending reversals give them
+    pub(crate) fn render<L>(root: *mut Member<K, V, X>, mut label: L) -> String
+    where
+        L: FnMut(&Member<K, V, X>) -> String,
+    {
+        let root = if root.is_null() { None } else { Some((root, false)) };
+        render_binary_tree(
+            root,
+            |(candidate_ptr, flip)| {
+                let node = unsafe { &*candidate_ptr };
+                let flip = flip ^ node.reversed;
+                let (first, second) =
+                    if flip { (node.right, node.left) } else { (node.left, node.right) };
+                (
+                    Some((first, flip)).filter(|(first, _)| !first.is_null()),
+                    Some((second, flip)).filter(|(second, _)| !second.is_null()),
+                )
+            },
+            |(candidate_ptr, _)| label(unsafe { &*candidate_ptr }),
+        )
+    }
+}
+
+// start and end of `range` within a sequence of `len` elements
+pub(crate) fn bounds<R>(range: R, len: usize) -> (usize, usize)
+where
+    R: RangeBounds<usize>,
+{
+    // `None` stands for one past `usize::MAX`
+    let start = match range.start_bound() {
+        Bound::Included(&start) => Some(start),
+        Bound::Excluded(&start) => start.checked_add(1),
+        Bound::Unbounded => Some(0),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1),
+        Bound::Excluded(&end) => Some(end),
+        Bound::Unbounded => Some(len),
+    };
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end && end <= len => (start, end),
+        _ => {
+            let show = |bound: Option<usize>| {
+                bound
+                    .map_or_else(|| (usize::MAX as u128 + 1).to_string(), |bound| bound.to_string())
+            };
+            panic!("range {}..{} out of 0..{}", show(start), show(end), len)
+        },
+    }
+}
+
+/// in-order iterator over the entries of a split and merge tree, reading pending reversals on
+/// the way, `X` is the data of the tree
+pub struct Iter<'a, K, V, X> {
+    // members whose left side is done, each with whether its children are swapped
+    stack: Vector<(*mut Member<K, V, X>, bool)>,
+    marker: PhantomData<&'a Member<K, V, X>>,
+}
+
+impl<'a, K, V, X> Iter<'a, K, V, X> {
+    pub(crate) fn new(root: *mut Member<K, V, X>) -> Self {
+        let mut walk = Self { stack: Vector::new(), marker: PhantomData };
+        walk.descend(root, false);
+        walk
+    }
+
+    fn descend(&mut self, mut node: *mut Member<K, V, X>, mut flip: bool) {
+        while let Some(current) = unsafe { node.as_ref() } {
+            flip ^= current.reversed;
+            self.stack.push_back((node, flip));
+            node = if flip { current.right } else { current.left };
+        }
+    }
+}
+
+impl<'a, K, V, X> Iterator for Iter<'a, K, V, X> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, flip) = self.stack.pop_back()?;
+        let node = unsafe { &*node };
+        self.descend(if flip { node.left } else { node.right }, flip);
+        Some((&node.key, &node.value))
+    }
+}
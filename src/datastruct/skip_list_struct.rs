@@ -0,0 +1,580 @@
+//! probabilistic ordered map written `SkipList<K, V>` and its set flavour `SkipSet<K>`
+//!
+//! every member carries a tower of forward links, the bottom link is the plain sorted linked list
+//! of [`crate::datastruct::list_struct::LinkedList`] and each upper link skips over the members
+//! whose tower is not as high. A member reaches level `l + 1` with the configured probability, so
+//! search, insertion and removal take O(log n) expected time.
+//!
+//! Each link also remembers how many members it jumps over, which turns the structure into an
+//! indexable skip list answering rank and select queries in O(log n).
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::skip_list_struct::SkipList;
+//! let mut list = SkipList::with_seed(7);
+//! list.insert(3, 'c');
+//! list.insert(1, 'a');
+//! list.insert(2, 'b');
+//! assert_eq!(list.get(&2), Some(&'b'));
+//! assert_eq!(list.rank(&3), 2);
+//! assert_eq!(list.select(0), Some((&1, &'a')));
+//! assert!(format!("{:?}", list).ends_with("⎩1⎭↪⎩2⎭↪⎩3⎭↪✘"));
+//! ```
+
+use crate::datastruct::rng::XorShift;
+use crate::datastruct::vec_struct::Vector;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Bound;
+use core::ops::RangeBounds;
+use core::ptr;
+use std::alloc;
+
+const LEFT: &str = "⎩";
+const PHI: &str = "✘";
+const RIGHT: &str = "⎭";
+const LINK: &str = "↪";
+const SKIP: &str = "─";
+
+const MAX_LEVEL: usize = 32;
+const DEFAULT_SEED: u64 = 0x5EED;
+
+pub struct SkipList<K, V> {
+    head: Vector<*mut Member<K, V>>,
+    head_width: Vector<usize>,
+    length: usize,
+    probability: f64,
+    rng: XorShift,
+}
+
+struct Member<K, V> {
+    key: K,
+    value: V,
+    next: Vector<*mut Member<K, V>>,
+    // number of bottom level steps covered by the link at the same level
+    width: Vector<usize>,
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// skip list with level probability `0.5` and a fixed seed
+    pub fn new() -> Self {
+        Self::with_probability(0.5, DEFAULT_SEED)
+    }
+
+    /// skip list with level probability `0.5` whose levels are drawn from `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_probability(0.5, seed)
+    }
+
+    /// skip list where a member is promoted to the next level with `probability`
+    /// # Panics
+    /// panics unless `0.0 < probability < 1.0`
+    /// ```should_panic
+    /// # use dsa_sport::datastruct::skip_list_struct::SkipList;
+    /// let list: SkipList<u8, u8> = SkipList::with_probability(1.0, 1);
+    /// ```
+    pub fn with_probability(probability: f64, seed: u64) -> Self {
+        if !(probability > 0.0 && probability < 1.0) {
+            panic!("level probability must lie strictly between 0 and 1");
+        }
+        let mut head = Vector::new();
+        let mut head_width = Vector::new();
+        for _ in 0..MAX_LEVEL {
+            head.push_back(ptr::null_mut());
+            head_width.push_back(1);
+        }
+        Self { head, head_width, length: 0, probability, rng: XorShift::new(seed) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// insert a key, returns the previous value if the key was already present
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::skip_list_struct::SkipList;
+    /// let mut list = SkipList::new();
+    /// assert_eq!(list.insert("a", 1), None);
+    /// assert_eq!(list.insert("a", 2), Some(1));
+    /// assert_eq!(list.len(), 1);
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (update, rank) = self.search(&key);
+        let found = self.link(update[0], 0);
+        unsafe {
+            if !found.is_null() && (*found).key == key {
+                return Some(mem::replace(&mut (*found).value, value));
+            }
+        }
+        let position = rank[0];
+        let level = self.random_level();
+        let mut member = Member { key, value, next: Vector::new(), width: Vector::new() };
+        for l in 0..level {
+            member.next.push_back(self.link(update[l], l));
+            member.width.push_back(self.width(update[l], l) - (position - rank[l]));
+        }
+        let node = Member::allocate_memory(member);
+        for l in 0..MAX_LEVEL {
+            if l < level {
+                self.set_link(update[l], l, node, position - rank[l] + 1);
+            } else {
+                let width = self.width(update[l], l);
+                self.set_link(update[l], l, self.link(update[l], l), width + 1);
+            }
+        }
+        self.length += 1;
+        None
+    }
+
+    /// remove a key and return its value
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::skip_list_struct::SkipList;
+    /// let mut list = SkipList::new();
+    /// list.insert(1, 'a');
+    /// list.insert(2, 'b');
+    /// assert_eq!(list.remove(&1), Some('a'));
+    /// assert_eq!(list.remove(&1), None);
+    /// assert_eq!(list.select(0), Some((&2, &'b')));
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (update, _) = self.search(key);
+        let target = self.link(update[0], 0);
+        unsafe {
+            if target.is_null() || (*target).key != *key {
+                return None;
+            }
+            for (l, &previous) in update.iter().enumerate() {
+                let width = self.width(previous, l);
+                if self.link(previous, l) == target {
+                    let next = (&(*target).next)[l];
+                    let skipped = (&(*target).width)[l];
+                    self.set_link(previous, l, next, width + skipped - 1);
+                } else {
+                    self.set_link(previous, l, self.link(previous, l), width - 1);
+                }
+            }
+            self.length -= 1;
+            Some(Member::deallocate_memory(target).1)
+        }
+    }
+
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::skip_list_struct::SkipList;
+    /// let mut list = SkipList::new();
+    /// list.insert(1, 'a');
+    /// assert_eq!(list.get(&1), Some(&'a'));
+    /// assert_eq!(list.get(&2), None);
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let node = self.lower_bound(key, false).0;
+        unsafe {
+            if !node.is_null() && (*node).key == *key {
+                return Some(&(*node).value);
+            }
+        }
+        None
+    }
+
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::skip_list_struct::SkipList;
+    /// let mut list = SkipList::new();
+    /// list.insert(1, 10);
+    /// *list.get_mut(&1).unwrap() += 1;
+    /// assert_eq!(list.get(&1), Some(&11));
+    /// ```
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let node = self.lower_bound(key, false).0;
+        unsafe {
+            if !node.is_null() && (*node).key == *key {
+                return Some(&mut (*node).value);
+            }
+        }
+        None
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// number of keys strictly smaller than `key`
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::skip_list_struct::SkipList;
+    /// let mut list = SkipList::new();
+    /// for k in [10, 20, 30].iter() {
+    ///     list.insert(*k, ());
+    /// }
+    /// assert_eq!(list.rank(&5), 0);
+    /// assert_eq!(list.rank(&20), 1);
+    /// assert_eq!(list.rank(&25), 2);
+    /// ```
+    pub fn rank(&self, key: &K) -> usize {
+        self.lower_bound(key, false).1
+    }
+
+    /// key and value at sorted position `index`
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::skip_list_struct::SkipList;
+    /// let mut list = SkipList::new();
+    /// for k in [30, 10, 20].iter() {
+    ///     list.insert(*k, *k / 10);
+    /// }
+    /// assert_eq!(list.select(1), Some((&20, &2)));
+    /// assert_eq!(list.select(3), None);
+    /// ```
+    pub fn select(&self, index: usize) -> Option<(&K, &V)> {
+        if index >= self.length {
+            return None;
+        }
+        let target = index + 1;
+        let mut node: *mut Member<K, V> = ptr::null_mut();
+        let mut position = 0;
+        for l in (0..MAX_LEVEL).rev() {
+            while !self.link(node, l).is_null() && position + self.width(node, l) <= target {
+                position += self.width(node, l);
+                node = self.link(node, l);
+            }
+        }
+        unsafe { Some((&(*node).key, &(*node).value)) }
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.select(0)
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.length.checked_sub(1).and_then(|index| self.select(index))
+    }
+
+    /// in order iterator over all the entries
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { current: self.head[0], remaining: self.length, marker: PhantomData }
+    }
+
+    /// in order iterator over the entries whose key lies in `range`
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::skip_list_struct::SkipList;
+    /// let mut list = SkipList::new();
+    /// for k in 1..10 {
+    ///     list.insert(k, ());
+    /// }
+    /// let keys: Vec<i32> = list.range(3..6).map(|(k, _)| *k).collect();
+    /// assert_eq!(keys, vec![3, 4, 5]);
+    /// let keys: Vec<i32> = list.range(8..).map(|(k, _)| *k).collect();
+    /// assert_eq!(keys, vec![8, 9]);
+    /// assert_eq!(list.range(6..3).count(), 0);
+    /// ```
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let (start, start_rank) = match range.start_bound() {
+            Bound::Included(key) => self.lower_bound(key, false),
+            Bound::Excluded(key) => self.lower_bound(key, true),
+            Bound::Unbounded => (self.head[0], 0),
+        };
+        let stop_rank = match range.end_bound() {
+            Bound::Included(key) => self.lower_bound(key, true).1,
+            Bound::Excluded(key) => self.lower_bound(key, false).1,
+            Bound::Unbounded => self.length,
+        };
+        Iter { current: start, remaining: stop_rank.saturating_sub(start_rank), marker: PhantomData }
+    }
+}
+
+// private associated functions
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    // a null member stands for the head tower
+    fn link(&self, node: *mut Member<K, V>, level: usize) -> *mut Member<K, V> {
+        if node.is_null() {
+            self.head[level]
+        } else {
+            unsafe { (&(*node).next)[level] }
+        }
+    }
+
+    fn width(&self, node: *mut Member<K, V>, level: usize) -> usize {
+        if node.is_null() {
+            self.head_width[level]
+        } else {
+            unsafe { (&(*node).width)[level] }
+        }
+    }
+
+    fn set_link(
+        &mut self,
+        node: *mut Member<K, V>,
+        level: usize,
+        target: *mut Member<K, V>,
+        width: usize,
+    ) {
+        if node.is_null() {
+            self.head[level] = target;
+            self.head_width[level] = width;
+        } else {
+            unsafe {
+                (&mut (*node).next)[level] = target;
+                (&mut (*node).width)[level] = width;
+            }
+        }
+    }
+
+    // last member before `key` on every level and its position (head is position 0)
+    fn search(&self, key: &K) -> ([*mut Member<K, V>; MAX_LEVEL], [usize; MAX_LEVEL]) {
+        let mut update = [ptr::null_mut(); MAX_LEVEL];
+        let mut rank = [0; MAX_LEVEL];
+        let mut node: *mut Member<K, V> = ptr::null_mut();
+        let mut position = 0;
+        for l in (0..MAX_LEVEL).rev() {
+            loop {
+                let next = self.link(node, l);
+                if next.is_null() || unsafe { (*next).key >= *key } {
+                    break;
+                }
+                position += self.width(node, l);
+                node = next;
+            }
+            update[l] = node;
+            rank[l] = position;
+        }
+        (update, rank)
+    }
+
+    // first member with a key `>= key` (`> key` when strict) and its sorted position
+    fn lower_bound(&self, key: &K, strict: bool) -> (*mut Member<K, V>, usize) {
+        let mut node: *mut Member<K, V> = ptr::null_mut();
+        let mut position = 0;
+        for l in (0..MAX_LEVEL).rev() {
+            loop {
+                let next = self.link(node, l);
+                if next.is_null() {
+                    break;
+                }
+                let before = unsafe { if strict { (*next).key <= *key } else { (*next).key < *key } };
+                if !before {
+                    break;
+                }
+                position += self.width(node, l);
+                node = next;
+            }
+        }
+        (self.link(node, 0), position)
+    }
+
+    fn random_level(&mut self) -> usize {
+        let mut level = 1;
+        while level < MAX_LEVEL && self.rng.next_f64() < self.probability {
+            level += 1;
+        }
+        level
+    }
+}
+
+impl<K, V> Member<K, V> {
+    fn allocate_memory(candidate: Member<K, V>) -> *mut Member<K, V> {
+        let size = mem::size_of::<Member<K, V>>();
+        let align = mem::align_of::<Member<K, V>>();
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            let ptr = alloc::alloc(layout) as *mut Member<K, V>;
+            ptr.write(candidate);
+            ptr
+        }
+    }
+
+    /// # Safety
+    /// `node` must come from [`Member::allocate_memory`] and be unlinked from the list
+    unsafe fn deallocate_memory(node: *mut Member<K, V>) -> (K, V) {
+        let size = mem::size_of::<Member<K, V>>();
+        let align = mem::align_of::<Member<K, V>>();
+        let member = node.read();
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(node as *mut u8, layout);
+        (member.key, member.value)
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    current: *mut Member<K, V>,
+    remaining: usize,
+    marker: PhantomData<&'a Member<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 || self.current.is_null() {
+            return None;
+        }
+        self.remaining -= 1;
+        unsafe {
+            let node = &*self.current;
+            self.current = node.next[0];
+            Some((&node.key, &node.value))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> Drop for SkipList<K, V> {
+    fn drop(&mut self) {
+        let mut current = self.head[0];
+        while !current.is_null() {
+            unsafe {
+                let node = current;
+                current = (&(*node).next)[0];
+                let _ = Member::deallocate_memory(node);
+            }
+        }
+    }
+}
+
+impl<K, V> Default for SkipList<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// one lane per level, top level first. A member taller than the lane prints `⎩key⎭↪`, the
+/// others are padded with `─` so the members stay aligned with the bottom lane
+impl<K, V> std::fmt::Debug for SkipList<K, V>
+where
+    K: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut cells = Vec::new();
+        let mut height = 1;
+        let mut current = self.head[0];
+        while !current.is_null() {
+            let node = unsafe { &*current };
+            let cell = format!("{}{:?}{}{}", LEFT, node.key, RIGHT, LINK);
+            height = height.max(node.next.len());
+            cells.push((cell, node.next.len()));
+            current = node.next[0];
+        }
+        let mut out = String::new();
+        for level in (0..height).rev() {
+            for (cell, tower) in &cells {
+                if *tower > level {
+                    out += cell;
+                } else {
+                    out += &SKIP.repeat(cell.chars().count());
+                }
+            }
+            out += PHI;
+            if level > 0 {
+                out += "\n";
+            }
+        }
+        write!(f, "{}", out)
+    }
+}
+
+/// ordered set on top of [`SkipList`]
+/// # Examples
+/// ```
+/// # use dsa_sport::datastruct::skip_list_struct::SkipSet;
+/// let mut set = SkipSet::new();
+/// assert!(set.insert('b'));
+/// assert!(set.insert('a'));
+/// assert!(!set.insert('a'));
+/// assert_eq!(set.iter().collect::<String>(), "ab");
+/// ```
+pub struct SkipSet<K> {
+    list: SkipList<K, ()>,
+}
+
+impl<K> SkipSet<K>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        Self { list: SkipList::new() }
+    }
+
+    pub fn with_probability(probability: f64, seed: u64) -> Self {
+        Self { list: SkipList::with_probability(probability, seed) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// true if the key was not present
+    pub fn insert(&mut self, key: K) -> bool {
+        self.list.insert(key, ()).is_none()
+    }
+
+    /// true if the key was present
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.list.remove(key).is_some()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.list.contains_key(key)
+    }
+
+    pub fn rank(&self, key: &K) -> usize {
+        self.list.rank(key)
+    }
+
+    pub fn select(&self, index: usize) -> Option<&K> {
+        self.list.select(index).map(|(key, _)| key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
+        self.list.iter().map(|(key, _)| key)
+    }
+
+    pub fn range<R>(&self, range: R) -> impl Iterator<Item = &K>
+    where
+        R: RangeBounds<K>,
+    {
+        self.list.range(range).map(|(key, _)| key)
+    }
+}
+
+impl<K> Default for SkipSet<K>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> std::fmt::Debug for SkipSet<K>
+where
+    K: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.list)
+    }
+}
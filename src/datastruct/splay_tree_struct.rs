@@ -0,0 +1,639 @@
+//! self-adjusting binary search tree written `SplayTree<K, V>` and its sequence flavour
+//! `ImplicitSplayTree<T>`
+//!
+//! every access splays the member it reaches up to the root by pairs of rotations: a zig when
+//! its parent is the root, a zig-zig when the member and its parent lean the same way and a
+//! zig-zag otherwise. The tree keeps no balance information, yet any sequence of `m` operations
+//! takes O(m log n) time, so each costs O(log n) amortized, and keys used often stay close to
+//! the root. Because a lookup reshapes the tree, [`SplayTree::get`] takes `&mut self`.
+//!
+//! `split` splays the boundary to the root and cuts one of its links, and `merge` splays the
+//! largest member of the lower tree, which then has no right child, and hangs the other tree
+//! there.
+//!
+//! An [`ImplicitSplayTree`] has no keys: each member keeps the size of its subtree and is found
+//! by its position, so the same `split` and `merge` cut a sequence at an index and concatenate
+//! two sequences. A reversal of a range marks the root of the cut out piece and the mark is
+//! pushed down to the children when a later walk goes through it. The members, sizes, reversals
+//! and walks are those of [`crate::datastruct::sized_core`].
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::splay_tree_struct::{ImplicitSplayTree, SplayTree};
+//! let mut map: SplayTree<u32, char> = (0..6).zip("abcdef".chars()).collect();
+//! assert_eq!(map.get(&4), Some(&'e'));
+//! assert_eq!(map.root(), Some((&4, &'e')));
+//! let (low, high) = map.split(&2);
+//! assert_eq!(low.values().collect::<String>(), "ab");
+//! assert_eq!(high.values().collect::<String>(), "cdef");
+//!
+//! let mut line: ImplicitSplayTree<char> = "abcdef".chars().collect();
+//! line.reverse(..3);
+//! assert_eq!(line.remove(5), Some('f'));
+//! assert_eq!(line.iter().collect::<String>(), "cbade");
+//! ```
+
+use crate::datastruct::sized_core::{self, bounds, Member};
+use crate::datastruct::vec_struct::Vector;
+use core::cmp::Ordering;
+use core::mem;
+use core::ops::RangeBounds;
+use core::ptr;
+
+pub struct SplayTree<K, V> {
+    root: *mut Node<K, V>,
+}
+
+/// sequence kept in a splay tree whose members are ordered by position, see the
+/// [module](self)
+pub struct ImplicitSplayTree<T> {
+    root: *mut Node<(), T>,
+}
+
+/// iterator of [`SplayTree::iter`]
+pub type Iter<'a, K, V> = sized_core::Iter<'a, K, V, ()>;
+
+// a member with no tree data, the parent links kept
+type Node<K, V> = Member<K, V, ()>;
+
+unsafe fn set_left<K, V>(node: *mut Node<K, V>, child: *mut Node<K, V>) {
+    (*node).left = child;
+    if let Some(child) = child.as_mut() {
+        child.parent = node;
+    }
+    Member::refresh_size(node);
+}
+
+unsafe fn set_right<K, V>(node: *mut Node<K, V>, child: *mut Node<K, V>) {
+    (*node).right = child;
+    if let Some(child) = child.as_mut() {
+        child.parent = node;
+    }
+    Member::refresh_size(node);
+}
+
+// lift `node` above its parent, the reversals on the way having been pushed down already
+unsafe fn rotate<K, V>(node: *mut Node<K, V>) {
+    let parent = (*node).parent;
+    let grandparent = (*parent).parent;
+    if (*parent).left == node {
+        set_left(parent, (*node).right);
+        set_right(node, parent);
+    } else {
+        set_right(parent, (*node).left);
+        set_left(node, parent);
+    }
+    (*node).parent = grandparent;
+    if let Some(grandparent) = grandparent.as_mut() {
+        if grandparent.left == parent {
+            grandparent.left = node;
+        } else {
+            grandparent.right = node;
+        }
+    }
+}
+
+// rotate `node` up until it is the root of its tree
+unsafe fn splay<K, V>(node: *mut Node<K, V>) -> *mut Node<K, V> {
+    while !(*node).parent.is_null() {
+        let parent = (*node).parent;
+        let grandparent = (*parent).parent;
+        if !grandparent.is_null() {
+            let zig_zig = ((*grandparent).left == parent) == ((*parent).left == node);
+            rotate(if zig_zig { parent } else { node });
+        }
+        rotate(node);
+    }
+    node
+}
+
+// splay the member at `index` of the tree below `root`, pushing reversals on the way down
+unsafe fn select<K, V>(root: *mut Node<K, V>, mut index: usize) -> *mut Node<K, V> {
+    let mut node = root;
+    loop {
+        Member::push(node);
+        let before = Member::size_of((*node).left);
+        match index.cmp(&before) {
+            Ordering::Less => node = (*node).left,
+            Ordering::Equal => return splay(node),
+            Ordering::Greater => {
+                index -= before + 1;
+                node = (*node).right;
+            },
+        }
+    }
+}
+
+// join two trees, every member of `left` going before every member of `right`
+unsafe fn merge<K, V>(left: *mut Node<K, V>, right: *mut Node<K, V>) -> *mut Node<K, V> {
+    if left.is_null() {
+        return right;
+    }
+    let last = select(left, Member::size_of(left) - 1);
+    set_right(last, right);
+    last
+}
+
+// cut the first `count` members off the tree below `root`
+unsafe fn split_at<K, V>(
+    root: *mut Node<K, V>,
+    count: usize,
+) -> (*mut Node<K, V>, *mut Node<K, V>) {
+    if count == Member::size_of(root) {
+        return (root, ptr::null_mut());
+    }
+    let high = select(root, count);
+    let low = (*high).left;
+    if let Some(low) = low.as_mut() {
+        low.parent = ptr::null_mut();
+    }
+    set_left(high, ptr::null_mut());
+    (low, high)
+}
+
+// parent links and sizes
+unsafe fn check_links<K, V>(root: *mut Node<K, V>) -> bool {
+    if root.as_ref().is_some_and(|root| !root.parent.is_null()) {
+        return false;
+    }
+    let mut member_stack = Vector::new();
+    member_stack.push_back(root);
+    while let Some(candidate_ptr) = member_stack.pop_back() {
+        if let Some(node) = candidate_ptr.as_ref() {
+            if node.size != 1 + Member::size_of(node.left) + Member::size_of(node.right) {
+                return false;
+            }
+            for &child in [node.left, node.right].iter() {
+                if child.as_ref().is_some_and(|child| child.parent != candidate_ptr) {
+                    return false;
+                }
+                member_stack.push_back(child);
+            }
+        }
+    }
+    true
+}
+
+impl<K, V> SplayTree<K, V> {
+    pub fn new() -> Self {
+        Self { root: ptr::null_mut() }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { Member::size_of(self.root) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_null()
+    }
+
+    /// number of levels, the empty tree has none
+    pub fn height(&self) -> usize {
+        unsafe { Member::height_of(self.root) }
+    }
+
+    pub fn clear(&mut self) {
+        unsafe { Member::free(mem::replace(&mut self.root, ptr::null_mut())) };
+    }
+
+    /// entry at the root, which is the last one reached
+    pub fn root(&self) -> Option<(&K, &V)> {
+        unsafe { self.root.as_ref().map(|node| (&node.key, &node.value)) }
+    }
+
+    /// smallest key with its value, without splaying
+    pub fn min(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// largest key with its value, without splaying
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = unsafe { self.root.as_ref()? };
+        while let Some(right) = unsafe { node.right.as_ref() } {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    /// entries in key order, without splaying
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.root)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> SplayTree<K, V>
+where
+    K: Ord,
+{
+    /// put `value` under `key` and give back the value it replaces, the entry ends at the root
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.splay_to(&key) {
+            return Some(mem::replace(unsafe { &mut (*self.root).value }, value));
+        }
+        let node = Member::allocate_memory(Member::new(key, value, ()));
+        unsafe {
+            if let Some(root) = self.root.as_mut() {
+                root.parent = ptr::null_mut();
+                if root.key < (*node).key {
+                    set_right(node, root.right);
+                    set_right(root, ptr::null_mut());
+                    set_left(node, root);
+                } else {
+                    set_left(node, root.left);
+                    set_left(root, ptr::null_mut());
+                    set_right(node, root);
+                }
+            }
+        }
+        self.root = node;
+        None
+    }
+
+    /// value of `key`, whose member is splayed to the root
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::splay_tree_struct::SplayTree;
+    /// let mut map: SplayTree<u32, u32> = (0..100).map(|key| (key, key * 2)).collect();
+    /// assert_eq!(map.get(&21), Some(&42));
+    /// assert_eq!(map.root(), Some((&21, &42)));
+    /// assert_eq!(map.get(&200), None);
+    /// assert_eq!(map.root(), Some((&99, &198)));
+    /// ```
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        if self.splay_to(key) {
+            unsafe { Some(&(*self.root).value) }
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if self.splay_to(key) {
+            unsafe { Some(&mut (*self.root).value) }
+        } else {
+            None
+        }
+    }
+
+    pub fn contains_key(&mut self, key: &K) -> bool {
+        self.splay_to(key)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// splay `key` to the root, take it out and merge its two subtrees
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        if !self.splay_to(key) {
+            return None;
+        }
+        unsafe {
+            let member = Member::deallocate_memory(self.root);
+            for &child in [member.left, member.right].iter() {
+                if let Some(child) = child.as_mut() {
+                    child.parent = ptr::null_mut();
+                }
+            }
+            self.root = merge(member.left, member.right);
+            Some((member.key, member.value))
+        }
+    }
+
+    /// cut the tree into the entries with a key below `key` and the others
+    pub fn split(mut self, key: &K) -> (Self, Self) {
+        if self.is_empty() {
+            return (self, Self::new());
+        }
+        self.splay_to(key);
+        unsafe {
+            let root = self.root;
+            let high = if (*root).key < *key {
+                let high = (*root).right;
+                set_right(root, ptr::null_mut());
+                high
+            } else {
+                self.root = (*root).left;
+                set_left(root, ptr::null_mut());
+                root
+            };
+            if let Some(node) = self.root.as_mut() {
+                node.parent = ptr::null_mut();
+            }
+            if let Some(node) = high.as_mut() {
+                node.parent = ptr::null_mut();
+            }
+            (self, Self { root: high })
+        }
+    }
+
+    /// join two trees whose keys do not interleave, in either order
+    /// # Panics
+    /// when a key of one tree lies between two keys of the other, or both hold the same key
+    pub fn merge(mut self, mut other: Self) -> Self {
+        let (low, high) = match (self.max(), other.min(), other.max(), self.min()) {
+            (Some((max, _)), Some((min, _)), _, _) if max < min => (self.root, other.root),
+            (_, _, Some((max, _)), Some((min, _))) if max < min => (other.root, self.root),
+            (None, ..) | (_, None, ..) => (self.root, other.root),
+            _ => panic!("the keys of the two splay trees interleave"),
+        };
+        other.root = ptr::null_mut();
+        self.root = unsafe { merge(low, high) };
+        self
+    }
+
+    /// check the parent links, the key order and the stored sizes
+    pub fn check_invariants(&self) -> bool {
+        let mut member_stack = Vector::new();
+        member_stack.push_back((self.root, None::<&K>, None::<&K>));
+        while let Some((candidate_ptr, low, high)) = member_stack.pop_back() {
+            if let Some(node) = unsafe { candidate_ptr.as_ref() } {
+                if node.reversed
+                    || low.is_some_and(|low| node.key <= *low)
+                    || high.is_some_and(|high| node.key >= *high)
+                {
+                    return false;
+                }
+                member_stack.push_back((node.left, low, Some(&node.key)));
+                member_stack.push_back((node.right, Some(&node.key), high));
+            }
+        }
+        unsafe { check_links(self.root) }
+    }
+
+    // splay the member of `key`, or the last member met looking for it, and tell whether the key
+    // was found
+    fn splay_to(&mut self, key: &K) -> bool {
+        let mut node = self.root;
+        if node.is_null() {
+            return false;
+        }
+        unsafe {
+            loop {
+                let next = match key.cmp(&(*node).key) {
+                    Ordering::Less => (*node).left,
+                    Ordering::Greater => (*node).right,
+                    Ordering::Equal => break,
+                };
+                if next.is_null() {
+                    break;
+                }
+                node = next;
+            }
+            self.root = splay(node);
+            (*node).key == *key
+        }
+    }
+}
+
+impl<T> ImplicitSplayTree<T> {
+    pub fn new() -> Self {
+        Self { root: ptr::null_mut() }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { Member::size_of(self.root) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_null()
+    }
+
+    /// number of levels, the empty sequence has none
+    pub fn height(&self) -> usize {
+        unsafe { Member::height_of(self.root) }
+    }
+
+    pub fn clear(&mut self) {
+        unsafe { Member::free(mem::replace(&mut self.root, ptr::null_mut())) };
+    }
+
+    /// element at `index`, whose member is splayed to the root
+    pub fn get(&mut self, index: usize) -> Option<&T> {
+        self.get_mut(index).map(|value| &*value)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        unsafe {
+            self.root = select(self.root, index);
+            Some(&mut (*self.root).value)
+        }
+    }
+
+    /// put `value` at position `index`, shifting the elements from there one place on
+    /// # Panics
+    /// when `index` is beyond the length
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "insertion index {} beyond length {}", index, self.len());
+        let node = Member::allocate_memory(Member::new((), value, ()));
+        unsafe {
+            let (low, high) = split_at(self.root, index);
+            set_left(node, low);
+            set_right(node, high);
+        }
+        self.root = node;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        self.insert(0, value);
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.insert(self.len(), value);
+    }
+
+    /// take out the element at `index`
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        unsafe {
+            let member = Member::deallocate_memory(select(self.root, index));
+            for &child in [member.left, member.right].iter() {
+                if let Some(child) = child.as_mut() {
+                    child.parent = ptr::null_mut();
+                }
+            }
+            self.root = merge(member.left, member.right);
+            Some(member.value)
+        }
+    }
+
+    /// reverse the order of the elements in `range` in O(log n) amortized time
+    /// # Panics
+    /// when the range ends beyond the length or starts after its end
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::splay_tree_struct::ImplicitSplayTree;
+    /// let mut line: ImplicitSplayTree<u8> = (0..8).collect();
+    /// line.reverse(2..=5);
+    /// line.reverse(6..);
+    /// assert_eq!(line.iter().copied().collect::<Vec<_>>(), vec![0, 1, 5, 4, 3, 2, 7, 6]);
+    /// ```
+    pub fn reverse<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = bounds(range, self.len());
+        unsafe {
+            let (low, rest) = split_at(self.root, start);
+            let (middle, high) = split_at(rest, end - start);
+            if let Some(middle) = middle.as_mut() {
+                middle.reversed = !middle.reversed;
+            }
+            self.root = merge(merge(low, middle), high);
+        }
+    }
+
+    /// cut the sequence into its first `at` elements and the others
+    /// # Panics
+    /// when `at` is beyond the length
+    pub fn split(mut self, at: usize) -> (Self, Self) {
+        assert!(at <= self.len(), "split index {} beyond length {}", at, self.len());
+        let (low, high) = unsafe { split_at(mem::replace(&mut self.root, ptr::null_mut()), at) };
+        self.root = low;
+        (self, Self { root: high })
+    }
+
+    /// the elements of `self` followed by those of `other`
+    pub fn merge(mut self, mut other: Self) -> Self {
+        let high = mem::replace(&mut other.root, ptr::null_mut());
+        self.root = unsafe { merge(self.root, high) };
+        self
+    }
+
+    /// elements in sequence order, without splaying
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        Iter::new(self.root).map(|(_, value)| value)
+    }
+
+    /// check the parent links and the stored sizes
+    pub fn check_invariants(&self) -> bool {
+        unsafe { check_links(self.root) }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SplayTree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> core::iter::FromIterator<(K, V)> for SplayTree<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SplayTree::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for SplayTree<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<T> core::iter::FromIterator<T> for ImplicitSplayTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut line = ImplicitSplayTree::new();
+        line.extend(iter);
+        line
+    }
+}
+
+impl<T> Extend<T> for ImplicitSplayTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<K, V> Default for SplayTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Default for ImplicitSplayTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for SplayTree<K, V> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Drop for ImplicitSplayTree<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V> std::fmt::Display for SplayTree<K, V>
+where
+    K: std::fmt::Display,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Member::render(self.root, |node| format!("{}: {:?}", node.key, node.value)))
+    }
+}
+
+impl<K, V> std::fmt::Debug for SplayTree<K, V>
+where
+    K: std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T> std::fmt::Display for ImplicitSplayTree<T>
+where
+    T: std::fmt::Debug,
+{
+    /// the tree in sequence order, a member drawn with its subtree size in brackets
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            Member::render(self.root, |node| format!("{:?} [{}]", node.value, node.size))
+        )
+    }
+}
+
+impl<T> std::fmt::Debug for ImplicitSplayTree<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,590 @@
+//! randomised binary search tree written `Treap<K, V>` and its sequence flavour
+//! `ImplicitTreap<T>`
+//!
+//! every member draws a random priority and the tree is a binary search tree on the keys and a
+//! max-heap on the priorities at once, which gives it the shape of a binary search tree built by
+//! inserting the keys in a random order, so its height is O(log n) expected whatever the order of
+//! the insertions. Priorities come from a seedable generator and a seed always builds the same
+//! tree.
+//!
+//! Every operation is made of two primitives: `split` cuts a tree in two around a key and `merge`
+//! joins two trees when every key of the first is below every key of the second. Both walk a
+//! single path and take O(log n) expected time.
+//!
+//! An [`ImplicitTreap`] has no keys: each member keeps the size of its subtree and the position
+//! of a member is the number of members before it, so the same `split` and `merge` cut a sequence
+//! at an index and concatenate two sequences. A reversal of a range marks the root of the cut out
+//! piece and the mark is pushed down to the children when a later walk goes through it. The
+//! members, sizes, reversals and walks are those of [`crate::datastruct::sized_core`], the
+//! priority sits in the tree data of a member.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::treap_struct::{ImplicitTreap, Treap};
+//! let map: Treap<u32, char> = "abcdef".chars().enumerate().map(|(i, c)| (i as u32, c)).collect();
+//! let (low, high) = map.split(&3);
+//! assert_eq!(low.values().collect::<String>(), "abc");
+//! assert_eq!(high.values().collect::<String>(), "def");
+//! let map = high.merge(low);
+//! assert_eq!(map.len(), 6);
+//!
+//! let mut line: ImplicitTreap<char> = "abcdef".chars().collect();
+//! line.reverse(1..5);
+//! line.insert(0, '>');
+//! assert_eq!(line.iter().collect::<String>(), ">aedcbf");
+//! ```
+
+use crate::datastruct::rng::XorShift;
+use crate::datastruct::sized_core::{self, bounds, Member};
+use crate::datastruct::vec_struct::Vector;
+use core::cmp::Ordering;
+use core::mem;
+use core::ops::RangeBounds;
+use core::ptr;
+
+const DEFAULT_SEED: u64 = 0x7EA9;
+
+pub struct Treap<K, V> {
+    root: *mut Node<K, V>,
+    rng: XorShift,
+}
+
+/// sequence kept in a treap whose members are ordered by position, see the [module](self)
+pub struct ImplicitTreap<T> {
+    root: *mut Node<(), T>,
+    rng: XorShift,
+}
+
+/// iterator of [`Treap::iter`]
+pub type Iter<'a, K, V> = sized_core::Iter<'a, K, V, u64>;
+
+// a member with its priority as tree data
+type Node<K, V> = Member<K, V, u64>;
+
+// join two treaps, every member of `left` going before every member of `right`
+unsafe fn merge<K, V>(left: *mut Node<K, V>, right: *mut Node<K, V>) -> *mut Node<K, V> {
+    if left.is_null() {
+        return right;
+    }
+    if right.is_null() {
+        return left;
+    }
+    if (*left).extra > (*right).extra {
+        Member::push(left);
+        (*left).right = merge((*left).right, right);
+        Member::refresh_size(left);
+        left
+    } else {
+        Member::push(right);
+        (*right).left = merge(left, (*right).left);
+        Member::refresh_size(right);
+        right
+    }
+}
+
+// cut the first `count` members off the treap below `node`
+unsafe fn split_at<K, V>(
+    node: *mut Node<K, V>,
+    count: usize,
+) -> (*mut Node<K, V>, *mut Node<K, V>) {
+    if node.is_null() {
+        return (ptr::null_mut(), ptr::null_mut());
+    }
+    Member::push(node);
+    let before = Member::size_of((*node).left);
+    if count <= before {
+        let (low, high) = split_at((*node).left, count);
+        (*node).left = high;
+        Member::refresh_size(node);
+        (low, node)
+    } else {
+        let (low, high) = split_at((*node).right, count - before - 1);
+        (*node).right = low;
+        Member::refresh_size(node);
+        (node, high)
+    }
+}
+
+// cut the treap below `node` into the members below `key` and the others
+unsafe fn split<K, V>(node: *mut Node<K, V>, key: &K) -> (*mut Node<K, V>, *mut Node<K, V>)
+where
+    K: Ord,
+{
+    if node.is_null() {
+        return (ptr::null_mut(), ptr::null_mut());
+    }
+    if (*node).key < *key {
+        let (low, high) = split((*node).right, key);
+        (*node).right = low;
+        Member::refresh_size(node);
+        (node, high)
+    } else {
+        let (low, high) = split((*node).left, key);
+        (*node).left = high;
+        Member::refresh_size(node);
+        (low, node)
+    }
+}
+
+// the member at `index` of the treap below `node`, reading the pending reversals on the way
+// without carrying them out
+unsafe fn at<K, V>(mut node: *mut Node<K, V>, mut index: usize) -> *mut Node<K, V> {
+    let mut flip = false;
+    while let Some(current) = node.as_ref() {
+        flip ^= current.reversed;
+        let (first, second) =
+            if flip { (current.right, current.left) } else { (current.left, current.right) };
+        let before = Member::size_of(first);
+        match index.cmp(&before) {
+            Ordering::Less => node = first,
+            Ordering::Equal => return node,
+            Ordering::Greater => {
+                index -= before + 1;
+                node = second;
+            },
+        }
+    }
+    ptr::null_mut()
+}
+
+// sizes and heap order of the priorities
+unsafe fn check_heap<K, V>(root: *mut Node<K, V>) -> bool {
+    let mut member_stack = Vector::new();
+    member_stack.push_back((root, u64::MAX));
+    while let Some((candidate_ptr, ceiling)) = member_stack.pop_back() {
+        if let Some(node) = candidate_ptr.as_ref() {
+            if node.extra > ceiling
+                || node.size != 1 + Member::size_of(node.left) + Member::size_of(node.right)
+            {
+                return false;
+            }
+            member_stack.push_back((node.left, node.extra));
+            member_stack.push_back((node.right, node.extra));
+        }
+    }
+    true
+}
+
+impl<K, V> Treap<K, V> {
+    /// treap with a fixed seed
+    pub fn new() -> Self {
+        Self::with_seed(DEFAULT_SEED)
+    }
+
+    /// treap whose priorities are drawn from `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        Self { root: ptr::null_mut(), rng: XorShift::new(seed) }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { Member::size_of(self.root) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_null()
+    }
+
+    /// number of levels, the empty treap has none
+    pub fn height(&self) -> usize {
+        unsafe { Member::height_of(self.root) }
+    }
+
+    pub fn clear(&mut self) {
+        unsafe { Member::free(mem::replace(&mut self.root, ptr::null_mut())) };
+    }
+
+    /// smallest key with its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// largest key with its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = unsafe { self.root.as_ref()? };
+        while let Some(right) = unsafe { node.right.as_ref() } {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    /// entries in key order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.root)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    // the same generator carried on by the other half of a split
+    fn sibling(&mut self, root: *mut Node<K, V>) -> Self {
+        Self { root, rng: XorShift::new(self.rng.next_u64()) }
+    }
+}
+
+impl<K, V> Treap<K, V>
+where
+    K: Ord,
+{
+    /// put `value` under `key` and give back the value it replaces
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(mem::replace(old, value));
+        }
+        let node = Member::allocate_memory(Member::new(key, value, self.rng.next_u64()));
+        unsafe {
+            let (low, high) = split(self.root, &(*node).key);
+            self.root = merge(merge(low, node), high);
+        }
+        None
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        unsafe { self.find(key).as_ref().map(|node| &node.value) }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        unsafe { self.find(key).as_mut().map(|node| &mut node.value) }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        !self.find(key).is_null()
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// take `key` out by merging the two subtrees of its member in its place
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        unsafe {
+            let mut link: *mut *mut Node<K, V> = &mut self.root;
+            loop {
+                let node = *link;
+                match key.cmp(&(*node).key) {
+                    Ordering::Less => link = &mut (*node).left,
+                    Ordering::Greater => link = &mut (*node).right,
+                    Ordering::Equal => {
+                        *link = merge((*node).left, (*node).right);
+                        let member = Member::deallocate_memory(node);
+                        return Some((member.key, member.value));
+                    },
+                }
+                (*node).size -= 1;
+            }
+        }
+    }
+
+    /// cut the treap into the entries with a key below `key` and the others
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::treap_struct::Treap;
+    /// let map: Treap<u32, ()> = (0..10).map(|key| (key, ())).collect();
+    /// let (low, high) = map.split(&4);
+    /// assert_eq!(low.keys().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    /// assert_eq!(high.min(), Some((&4, &())));
+    /// ```
+    pub fn split(mut self, key: &K) -> (Self, Self) {
+        let (low, high) = unsafe { split(mem::replace(&mut self.root, ptr::null_mut()), key) };
+        let high = self.sibling(high);
+        self.root = low;
+        (self, high)
+    }
+
+    /// join two treaps whose keys do not interleave, in either order
+    /// # Panics
+    /// when a key of one treap lies between two keys of the other, or both hold the same key
+    /// ```should_panic
+    /// # use dsa_sport::datastruct::treap_struct::Treap;
+    /// let odd: Treap<u32, ()> = [1, 3].iter().map(|&key| (key, ())).collect();
+    /// let even: Treap<u32, ()> = [2, 4].iter().map(|&key| (key, ())).collect();
+    /// odd.merge(even);
+    /// ```
+    pub fn merge(mut self, mut other: Self) -> Self {
+        let (low, high) = match (self.max(), other.min(), other.max(), self.min()) {
+            (Some((max, _)), Some((min, _)), _, _) if max < min => (self.root, other.root),
+            (_, _, Some((max, _)), Some((min, _))) if max < min => (other.root, self.root),
+            (None, ..) | (_, None, ..) => (self.root, other.root),
+            _ => panic!("the keys of the two treaps interleave"),
+        };
+        other.root = ptr::null_mut();
+        self.root = unsafe { merge(low, high) };
+        self
+    }
+
+    /// check the key order, the heap order of the priorities and the stored sizes
+    pub fn check_invariants(&self) -> bool {
+        let mut member_stack = Vector::new();
+        member_stack.push_back((self.root, None::<&K>, None::<&K>));
+        while let Some((candidate_ptr, low, high)) = member_stack.pop_back() {
+            if let Some(node) = unsafe { candidate_ptr.as_ref() } {
+                if node.reversed
+                    || low.is_some_and(|low| node.key <= *low)
+                    || high.is_some_and(|high| node.key >= *high)
+                {
+                    return false;
+                }
+                member_stack.push_back((node.left, low, Some(&node.key)));
+                member_stack.push_back((node.right, Some(&node.key), high));
+            }
+        }
+        unsafe { check_heap(self.root) }
+    }
+
+    fn find(&self, key: &K) -> *mut Node<K, V> {
+        let mut node = self.root;
+        while let Some(current) = unsafe { node.as_ref() } {
+            match key.cmp(&current.key) {
+                Ordering::Less => node = current.left,
+                Ordering::Greater => node = current.right,
+                Ordering::Equal => return node,
+            }
+        }
+        ptr::null_mut()
+    }
+}
+
+impl<T> ImplicitTreap<T> {
+    /// empty sequence with a fixed seed
+    pub fn new() -> Self {
+        Self::with_seed(DEFAULT_SEED)
+    }
+
+    /// empty sequence whose priorities are drawn from `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        Self { root: ptr::null_mut(), rng: XorShift::new(seed) }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { Member::size_of(self.root) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_null()
+    }
+
+    /// number of levels, the empty sequence has none
+    pub fn height(&self) -> usize {
+        unsafe { Member::height_of(self.root) }
+    }
+
+    pub fn clear(&mut self) {
+        unsafe { Member::free(mem::replace(&mut self.root, ptr::null_mut())) };
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        unsafe { at(self.root, index).as_ref().map(|node| &node.value) }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        unsafe { at(self.root, index).as_mut().map(|node| &mut node.value) }
+    }
+
+    /// put `value` at position `index`, shifting the elements from there one place on
+    /// # Panics
+    /// when `index` is beyond the length
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "insertion index {} beyond length {}", index, self.len());
+        let node = Member::allocate_memory(Member::new((), value, self.rng.next_u64()));
+        unsafe {
+            let (low, high) = split_at(self.root, index);
+            self.root = merge(merge(low, node), high);
+        }
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        self.insert(0, value);
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.insert(self.len(), value);
+    }
+
+    /// take out the element at `index`
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        unsafe {
+            let (low, rest) = split_at(self.root, index);
+            let (node, high) = split_at(rest, 1);
+            self.root = merge(low, high);
+            Some(Member::deallocate_memory(node).value)
+        }
+    }
+
+    /// reverse the order of the elements in `range` in O(log n) expected time
+    /// # Panics
+    /// when the range ends beyond the length or starts after its end
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::treap_struct::ImplicitTreap;
+    /// let mut line: ImplicitTreap<u8> = (0..8).collect();
+    /// line.reverse(2..6);
+    /// line.reverse(..);
+    /// assert_eq!(line.iter().copied().collect::<Vec<_>>(), vec![7, 6, 2, 3, 4, 5, 1, 0]);
+    /// ```
+    pub fn reverse<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = bounds(range, self.len());
+        unsafe {
+            let (low, rest) = split_at(self.root, start);
+            let (middle, high) = split_at(rest, end - start);
+            if let Some(middle) = middle.as_mut() {
+                middle.reversed = !middle.reversed;
+            }
+            self.root = merge(merge(low, middle), high);
+        }
+    }
+
+    /// cut the sequence into its first `at` elements and the others
+    /// # Panics
+    /// when `at` is beyond the length
+    pub fn split(mut self, at: usize) -> (Self, Self) {
+        assert!(at <= self.len(), "split index {} beyond length {}", at, self.len());
+        let (low, high) = unsafe { split_at(mem::replace(&mut self.root, ptr::null_mut()), at) };
+        let high = Self { root: high, rng: XorShift::new(self.rng.next_u64()) };
+        self.root = low;
+        (self, high)
+    }
+
+    /// the elements of `self` followed by those of `other`
+    pub fn merge(mut self, mut other: Self) -> Self {
+        let high = mem::replace(&mut other.root, ptr::null_mut());
+        self.root = unsafe { merge(self.root, high) };
+        self
+    }
+
+    /// elements in sequence order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        Iter::new(self.root).map(|(_, value)| value)
+    }
+
+    /// check the heap order of the priorities and the stored sizes
+    pub fn check_invariants(&self) -> bool {
+        unsafe { check_heap(self.root) }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Treap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> core::iter::FromIterator<(K, V)> for Treap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Treap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for Treap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<T> core::iter::FromIterator<T> for ImplicitTreap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut line = ImplicitTreap::new();
+        line.extend(iter);
+        line
+    }
+}
+
+impl<T> Extend<T> for ImplicitTreap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<K, V> Default for Treap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Default for ImplicitTreap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for Treap<K, V> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Drop for ImplicitTreap<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V> std::fmt::Display for Treap<K, V>
+where
+    K: std::fmt::Display,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Member::render(self.root, |node| format!("{}: {:?}", node.key, node.value)))
+    }
+}
+
+impl<K, V> std::fmt::Debug for Treap<K, V>
+where
+    K: std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T> std::fmt::Display for ImplicitTreap<T>
+where
+    T: std::fmt::Debug,
+{
+    /// the tree in sequence order, a member drawn with its subtree size in brackets
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            Member::render(self.root, |node| format!("{:?} [{}]", node.value, node.size))
+        )
+    }
+}
+
+impl<T> std::fmt::Debug for ImplicitTreap<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
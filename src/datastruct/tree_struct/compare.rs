@@ -0,0 +1,299 @@
+//! comparisons between two [`Tree`]s
+//!
+//! `==` compares labels and the order of children. [`Tree::same_shape`] drops the labels,
+//! [`Tree::is_isomorphic`] and [`Tree::is_labelled_isomorphic`] also drop the order of children:
+//! every subtree gets a canonical number from its label and the sorted numbers of its children
+//! (AHU), so two trees are isomorphic when their roots get the same number. [`Tree::diff`] lists
+//! the edits turning one revision of a tree into the next one.
+use super::{NodeId, Traversal, Tree};
+use crate::datastruct::vec_struct::Vector;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// one step of [`Tree::diff`], members are addressed by label paths (see [`Tree::label_path`]),
+/// in the old tree for `Delete`, `Relabel` and the source of `Move`, in the new tree otherwise
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeEdit {
+    /// the subtree at `path` is new
+    Insert { path: String },
+    /// the subtree at `path` is gone
+    Delete { path: String },
+    /// the member at `path` is now labelled `label`
+    Relabel { path: String, label: String },
+    /// the subtree at `from` now hangs at `to`
+    Move { from: String, to: String },
+}
+
+impl std::fmt::Display for TreeEdit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TreeEdit::Insert { path } => write!(f, "+ {}", path),
+            TreeEdit::Delete { path } => write!(f, "- {}", path),
+            TreeEdit::Relabel { path, label } => write!(f, "~ {} -> {}", path, label),
+            TreeEdit::Move { from, to } => write!(f, "> {} -> {}", from, to),
+        }
+    }
+}
+
+impl<T> Tree<T> {
+    /// same shape and same order of children, labels are ignored
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let one: Tree<char> = Tree::init_tree("a;2;b;c;1;d").unwrap();
+    /// let two: Tree<u8> = Tree::init_tree("1;2;2;3;1;4").unwrap();
+    /// let three: Tree<u8> = Tree::init_tree("1;2;2;3;0;1;4").unwrap();
+    /// assert!(one.same_shape(&two));
+    /// assert!(!one.same_shape(&three));
+    /// ```
+    pub fn same_shape<U>(&self, other: &Tree<U>) -> bool {
+        let mut pairs = Vector::new();
+        match (self.root(), other.root()) {
+            (None, None) => return true,
+            (Some(one), Some(two)) => pairs.push_back((one, two)),
+            _ => return false,
+        }
+        while let Some((one, two)) = pairs.pop_back() {
+            let count = self.child_count(one);
+            if count != other.child_count(two) {
+                return false;
+            }
+            for i in 0..count {
+                pairs.push_back((
+                    self.child(one, i).expect("i < count"),
+                    other.child(two, i).expect("i < count"),
+                ));
+            }
+        }
+        true
+    }
+
+    /// same shape once the children of every member may be reordered, labels are ignored
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let one: Tree<char> = Tree::init_tree("a;2;b;c;1;d").unwrap();
+    /// let two: Tree<char> = Tree::init_tree("x;2;y;z;0;1;w").unwrap();
+    /// assert!(!one.same_shape(&two));
+    /// assert!(one.is_isomorphic(&two));
+    /// ```
+    pub fn is_isomorphic<U>(&self, other: &Tree<U>) -> bool {
+        let mut names = HashMap::new();
+        let one = canonical_name(self, |_| (), &mut names);
+        let two = canonical_name(other, |_| (), &mut names);
+        one == two
+    }
+}
+
+impl<T> Tree<T>
+where
+    T: Hash + Eq,
+{
+    /// same labels and shape once the children of every member may be reordered
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let one: Tree<char> = Tree::init_tree("a;2;b;c;1;d").unwrap();
+    /// let two: Tree<char> = Tree::init_tree("a;2;c;b;0;1;d").unwrap();
+    /// let three: Tree<char> = Tree::init_tree("a;2;b;c;0;1;d").unwrap();
+    /// assert!(one.is_labelled_isomorphic(&two));
+    /// assert!(!one.is_labelled_isomorphic(&three));
+    /// assert!(one.is_isomorphic(&three));
+    /// ```
+    pub fn is_labelled_isomorphic(&self, other: &Tree<T>) -> bool {
+        let mut names = HashMap::new();
+        let one = canonical_name(self, |data| data, &mut names);
+        let two = canonical_name(other, |data| data, &mut names);
+        one == two
+    }
+
+    /// fingerprint that does not depend on the order of children, trees that are labelled
+    /// isomorphic share it. It is stable within one build of the program only.
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let one: Tree<char> = Tree::init_tree("a;2;b;c;1;d").unwrap();
+    /// let two: Tree<char> = Tree::init_tree("a;2;c;b;0;1;d").unwrap();
+    /// assert_eq!(one.canonical_hash(), two.canonical_hash());
+    /// ```
+    pub fn canonical_hash(&self) -> u64 {
+        let mut hashes: HashMap<NodeId<T>, u64> = HashMap::new();
+        for (_, node) in self.nodes(Traversal::PostOrder) {
+            let mut children = Vector::new();
+            for i in 0..self.child_count(node) {
+                children.push_back(hashes[&self.child(node, i).expect("i < count")]);
+            }
+            sort(&mut children);
+            let mut hasher = DefaultHasher::new();
+            self.get(node).hash(&mut hasher);
+            for i in 0..children.len() {
+                children[i].hash(&mut hasher);
+            }
+            hashes.insert(node, hasher.finish());
+        }
+        self.root().map_or(0, |root| hashes[&root])
+    }
+}
+
+impl<T> Tree<T>
+where
+    T: Hash + Eq + std::fmt::Display,
+{
+    /// edits turning `self` into `new`. Members are matched by label, preferring a candidate
+    /// whose parent is already matched, and an unmatched member is taken as relabelled when it
+    /// sits at the same place as an unmatched member of the other tree. Only the top of an
+    /// inserted or deleted subtree is listed and siblings changing order is not an edit.
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::{Tree, TreeEdit};
+    /// let old: Tree<String> =
+    ///     Tree::init_tree("Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower").unwrap();
+    /// let new: Tree<String> =
+    ///     Tree::init_tree("Plants;2;spores;seed;2;algae;mosses;3;Conifers;sunflower;ferns").unwrap();
+    /// let edits: Vec<String> = old.diff(&new).iter().map(|edit| edit.to_string()).collect();
+    /// assert_eq!(
+    ///     edits,
+    ///     [
+    ///         "~ Plants/no seed -> spores",
+    ///         "~ Plants/no seed/Mosses -> mosses",
+    ///         "> Plants/no seed/ferns -> Plants/seed/ferns",
+    ///     ]
+    /// );
+    /// ```
+    pub fn diff(&self, new: &Tree<T>) -> Vec<TreeEdit> {
+        let mut edits = Vec::new();
+        let (old_nodes, new_nodes) = (preorder_nodes(self), preorder_nodes(new));
+        // new member -> old member and back
+        let mut forward: HashMap<NodeId<T>, NodeId<T>> = HashMap::new();
+        let mut backward: HashMap<NodeId<T>, NodeId<T>> = HashMap::new();
+
+        let mut by_label: HashMap<&T, Vector<NodeId<T>>> = HashMap::new();
+        for i in 0..old_nodes.len() {
+            by_label
+                .entry(self.data(old_nodes[i]))
+                .or_insert_with(Vector::new)
+                .push_back(old_nodes[i]);
+        }
+        for i in 0..new_nodes.len() {
+            let node = new_nodes[i];
+            let candidates = match by_label.get(new.data(node)) {
+                Some(candidates) => candidates,
+                None => continue,
+            };
+            let wanted_parent = new.parent(node).and_then(|parent| forward.get(&parent).copied());
+            let free = |j: &usize| !backward.contains_key(&candidates[*j]);
+            let pick = (0..candidates.len())
+                .filter(free)
+                .find(|&j| self.parent(candidates[j]) == wanted_parent)
+                .or_else(|| (0..candidates.len()).find(free));
+            if let Some(j) = pick {
+                forward.insert(node, candidates[j]);
+                backward.insert(candidates[j], node);
+            }
+        }
+        // an unmatched member in the place of an unmatched member was relabelled
+        for i in 0..new_nodes.len() {
+            let node = new_nodes[i];
+            if forward.contains_key(&node) {
+                continue;
+            }
+            let twin = match new.parent(node) {
+                None => self.root(),
+                Some(parent) => forward.get(&parent).and_then(|&old_parent| {
+                    let index = (0..new.child_count(parent))
+                        .position(|k| new.child(parent, k) == Some(node))
+                        .expect("a parent lists its children");
+                    self.child(old_parent, index)
+                }),
+            };
+            if let Some(twin) = twin.filter(|twin| !backward.contains_key(twin)) {
+                forward.insert(node, twin);
+                backward.insert(twin, node);
+                edits.push(TreeEdit::Relabel {
+                    path: self.label_path(twin),
+                    label: new.data(node).to_string(),
+                });
+            }
+        }
+
+        let mut deletes = Vec::new();
+        for i in 0..old_nodes.len() {
+            let node = old_nodes[i];
+            let parent_kept = self.parent(node).is_none_or(|parent| backward.contains_key(&parent));
+            if !backward.contains_key(&node) && parent_kept {
+                deletes.push(TreeEdit::Delete { path: self.label_path(node) });
+            }
+        }
+        for i in 0..new_nodes.len() {
+            let node = new_nodes[i];
+            let matched_parent = new.parent(node).map(|parent| forward.get(&parent).copied());
+            match forward.get(&node) {
+                None => {
+                    if matched_parent.is_none_or(|parent| parent.is_some()) {
+                        edits.push(TreeEdit::Insert { path: new.label_path(node) });
+                    }
+                },
+                Some(&old) => {
+                    if let Some(new_parent) = matched_parent {
+                        if new_parent != self.parent(old) {
+                            edits.push(TreeEdit::Move {
+                                from: self.label_path(old),
+                                to: new.label_path(node),
+                            });
+                        }
+                    }
+                },
+            }
+        }
+        deletes.append(&mut edits);
+        deletes
+    }
+}
+
+fn preorder_nodes<T>(tree: &Tree<T>) -> Vector<NodeId<T>> {
+    let mut nodes = Vector::new();
+    for (_, node) in tree.nodes(Traversal::PreOrder) {
+        nodes.push_back(node);
+    }
+    nodes
+}
+
+// AHU numbering shared by every tree named with the same `names`, `None` for the empty tree
+fn canonical_name<'a, T, K, F>(
+    tree: &'a Tree<T>,
+    key: F,
+    names: &mut HashMap<(K, Vec<usize>), usize>,
+) -> Option<usize>
+where
+    K: Hash + Eq,
+    F: Fn(&'a T) -> K,
+{
+    let mut numbers: HashMap<NodeId<T>, usize> = HashMap::new();
+    for (_, node) in tree.nodes(Traversal::PostOrder) {
+        let mut children = Vector::new();
+        for i in 0..tree.child_count(node) {
+            children.push_back(numbers[&tree.child(node, i).expect("i < count")]);
+        }
+        sort(&mut children);
+        let children: Vec<usize> = (0..children.len()).map(|i| children[i]).collect();
+        let fresh = names.len();
+        let number = *names.entry((key(tree.data(node)), children)).or_insert(fresh);
+        numbers.insert(node, number);
+    }
+    tree.root().map(|root| numbers[&root])
+}
+
+// insertion sort, children lists are short
+fn sort<K>(keys: &mut Vector<K>)
+where
+    K: Ord,
+{
+    for i in 1..keys.len() {
+        let mut j = i;
+        while j > 0 && keys[j - 1] > keys[j] {
+            keys.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! directory listing as a `Tree<FsEntry>`
+//!
+//! [`Tree::from_dir`] walks a local directory with an explicit stack and keeps one member per
+//! entry, children are sorted by name. Symbolic links are listed but never followed. The size of
+//! a directory is the sum of the sizes of every file below it, so the root holds the size of the
+//! whole walk. `Display` of an entry is its name with a trailing `/` for directories, which makes
+//! `Display` of the tree a `tree(1)` style listing:
+//! ``` text
+//! ╚═project/
+//!   ╠═Cargo.toml
+//!   ╚═src/
+//!     ╚═lib.rs
+//! ```
+use super::{NodeId, Tree};
+use crate::datastruct::vec_struct::Vector;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsKind {
+    File,
+    Dir,
+    Symlink,
+}
+
+/// one entry of a directory walk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsEntry {
+    name: String,
+    kind: FsKind,
+    size: u64,
+}
+
+impl FsEntry {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> FsKind {
+        self.kind
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.kind == FsKind::Dir
+    }
+
+    /// length in bytes of a file or link, total length of the files below a directory
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl std::fmt::Display for FsEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_dir() {
+            write!(f, "{}/", self.name)
+        } else {
+            write!(f, "{}", self.name)
+        }
+    }
+}
+
+/// settings of [`Tree::from_dir`]
+/// # Example
+/// ```
+/// # use dsa_sport::datastruct::tree_struct::WalkOptions;
+/// let options = WalkOptions::new().max_depth(2).exclude("target").exclude("*.o");
+/// ```
+pub struct WalkOptions {
+    max_depth: Option<usize>,
+    excludes: Vector<String>,
+}
+
+impl WalkOptions {
+    /// unlimited depth and no excludes
+    pub fn new() -> Self {
+        Self { max_depth: None, excludes: Vector::new() }
+    }
+
+    /// list entries at most `depth` levels below the root, the files beyond the limit still count
+    /// in the size of their listed ancestor
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// skip every entry matching `pattern`, excluded entries are neither listed nor counted.
+    /// `*` matches any run of characters except `/`, `**` any run including `/` and `?` a single
+    /// character. A pattern with a `/` is matched against the path relative to the root, any
+    /// other pattern against the entry name.
+    pub fn exclude(mut self, pattern: &str) -> Self {
+        self.excludes.push_back(pattern.to_string());
+        self
+    }
+
+    fn is_excluded(&self, name: &str, relative: &str) -> bool {
+        (0..self.excludes.len()).any(|i| {
+            let pattern = &self.excludes[i];
+            let text = if pattern.contains('/') { relative } else { name };
+            glob_match(pattern, text)
+        })
+    }
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tree<FsEntry> {
+    /// walk the directory at `path`
+    /// # Errors
+    /// the first error met while reading an entry or a directory
+    pub fn from_dir<P>(path: P, options: &WalkOptions) -> io::Result<Tree<FsEntry>>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let name = match path.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => path.display().to_string(),
+        };
+        let mut tree = Tree::new();
+        let root = tree.set_root(entry_of(name, &fs::symlink_metadata(path)?));
+        // directories still to read with the deepest listed member holding them, their path
+        // relative to the root and their depth
+        let mut pending = Vector::new();
+        if tree.data(root).is_dir() {
+            pending.push_back((root, path.to_path_buf(), String::new(), 0));
+        }
+        while let Some((owner, dir, relative, depth)) = pending.pop_back() {
+            let mut children = Vector::new();
+            for item in fs::read_dir(&dir)? {
+                let item = item?;
+                let name = item.file_name().to_string_lossy().into_owned();
+                let relative =
+                    if relative.is_empty() { name.clone() } else { format!("{}/{}", relative, name) };
+                if !options.is_excluded(&name, &relative) {
+                    let entry = entry_of(name, &item.metadata()?);
+                    children.push_back((entry, item.path(), relative));
+                }
+            }
+            sort_by_name(&mut children);
+            let listed = options.max_depth.is_none_or(|limit| depth < limit);
+            while let Some((entry, child_path, child_relative)) = children.pop_front() {
+                let (is_dir, size) = (entry.is_dir(), entry.size);
+                let holder = if listed { tree.push_child(owner, entry) } else { owner };
+                if is_dir {
+                    pending.push_back((holder, child_path, child_relative, depth + 1));
+                } else {
+                    charge(&mut tree, holder, size, !listed);
+                }
+            }
+        }
+        Ok(tree)
+    }
+}
+
+// add the size of a file to the directories above it, and to `holder` itself when the file is
+// not listed
+fn charge(tree: &mut Tree<FsEntry>, holder: NodeId<FsEntry>, size: u64, itself: bool) {
+    let mut directories = Vector::new();
+    if itself {
+        directories.push_back(holder);
+    }
+    for ancestor in tree.ancestors(holder) {
+        directories.push_back(ancestor);
+    }
+    while let Some(directory) = directories.pop_back() {
+        tree.data_mut(directory).size += size;
+    }
+}
+
+fn entry_of(name: String, metadata: &fs::Metadata) -> FsEntry {
+    let file_type = metadata.file_type();
+    let kind = if file_type.is_symlink() {
+        FsKind::Symlink
+    } else if file_type.is_dir() {
+        FsKind::Dir
+    } else {
+        FsKind::File
+    };
+    let size = if kind == FsKind::Dir { 0 } else { metadata.len() };
+    FsEntry { name, kind, size }
+}
+
+// insertion sort by name, a directory rarely holds enough entries to need better
+fn sort_by_name(entries: &mut Vector<(FsEntry, PathBuf, String)>) {
+    for i in 1..entries.len() {
+        let mut j = i;
+        while j > 0 && entries[j - 1].0.name > entries[j].0.name {
+            entries.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+// dynamic programming over (pattern position, text position)
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text) = (chars_of(pattern), chars_of(text));
+    // matches[j] tells whether the pattern read so far matches the first j characters of text
+    let mut matches = falses(text.len() + 1);
+    matches[0] = true;
+    let mut i = 0;
+    while i < pattern.len() {
+        let mut next = falses(text.len() + 1);
+        if pattern[i] == '*' {
+            let cross = i + 1 < pattern.len() && pattern[i + 1] == '*';
+            for j in 0..=text.len() {
+                next[j] = matches[j] || (j > 0 && next[j - 1] && (cross || text[j - 1] != '/'));
+            }
+            i += if cross { 2 } else { 1 };
+        } else {
+            for j in 1..=text.len() {
+                let same = pattern[i] == text[j - 1] || (pattern[i] == '?' && text[j - 1] != '/');
+                next[j] = matches[j - 1] && same;
+            }
+            i += 1;
+        }
+        matches = next;
+    }
+    matches[text.len()]
+}
+
+fn chars_of(text: &str) -> Vector<char> {
+    let mut chars = Vector::new();
+    for c in text.chars() {
+        chars.push_back(c);
+    }
+    chars
+}
+
+fn falses(len: usize) -> Vector<bool> {
+    let mut flags = Vector::new();
+    for _ in 0..len {
+        flags.push_back(false);
+    }
+    flags
+}
@@ -0,0 +1,850 @@
+//! nested JSON, S-expression and Newick readers and writers for [`Tree`]
+//!
+//! ``` text
+//! JSON         {"label":"Plants","children":[{"label":"no seed"},{"label":"seed"}]}
+//! S-expression (Plants ("no seed") (seed))
+//! Newick       ('no seed',seed:0.5)Plants;
+//! ```
+//! Readers and writers walk the text and the tree with an explicit stack. A reader first records
+//! every member with the index of its parent and only then builds the tree, because JSON keys
+//! come in any order and Newick writes a label after the children. Errors carry the byte offset,
+//! the line and the column of the offending character.
+use super::{NodeId, Tree};
+use crate::datastruct::vec_struct::Vector;
+use core::str::FromStr;
+
+/// label and branch length of a Newick member, an unnamed member has an empty name
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewickNode {
+    pub name: String,
+    pub length: Option<f64>,
+}
+
+impl NewickNode {
+    pub fn new(name: &str, length: Option<f64>) -> Self {
+        Self { name: name.to_string(), length }
+    }
+}
+
+impl std::fmt::Display for NewickNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.length {
+            Some(length) => write!(f, "{}:{}", self.name, length),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl<T> Tree<T>
+where
+    T: FromStr,
+{
+    /// read nested JSON objects with a `label` string or number and an optional `children` array,
+    /// `null` is the empty tree
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let json = r#"{"label": "Plants", "children": [{"label": "no seed"}, {"label": "seed"}]}"#;
+    /// let tree: Tree<String> = Tree::from_json(json).unwrap();
+    /// assert_eq!(tree.to_tree_code(), "Plants;2;no seed;seed");
+    /// let numbers: Tree<u8> = Tree::from_json(r#"{"children":[{"label":2}],"label":1}"#).unwrap();
+    /// assert_eq!(numbers.to_tree_code(), "1;1;2");
+    /// ```
+    /// # Errors
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::{Tree, TreeFormatErrorKind};
+    /// let error = Tree::<String>::from_json("{\"label\":\"a\",\n \"kids\":[]}").unwrap_err();
+    /// assert_eq!(error.kind(), TreeFormatErrorKind::UnknownKey);
+    /// assert_eq!((error.position(), error.line(), error.column()), (15, 2, 2));
+    /// ```
+    pub fn from_json(text: &str) -> Result<Tree<T>, TreeFormatError> {
+        let drafts = read_json(text)?;
+        assemble(&drafts, |draft| parse_label(text, draft))
+    }
+
+    /// read S-expressions, a member is a list holding its label followed by its children and a
+    /// bare label is a leaf. Labels with spaces, parentheses or quotes are written in `"` quotes
+    /// with `\` escapes.
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> =
+    ///     Tree::from_sexpr("(Plants (no_seed algae Mosses) (seed Conifers (\"sun flower\")))").unwrap();
+    /// assert_eq!(tree.to_tree_code(), "Plants;2;no_seed;seed;2;algae;Mosses;2;Conifers;sun flower");
+    /// ```
+    pub fn from_sexpr(text: &str) -> Result<Tree<T>, TreeFormatError> {
+        let drafts = read_sexpr(text)?;
+        assemble(&drafts, |draft| parse_label(text, draft))
+    }
+}
+
+impl<T> Tree<T>
+where
+    T: std::fmt::Display,
+{
+    /// write the tree in the format of [`Tree::from_json`], leaves have no `children` key
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> = Tree::init_tree("a;2;b \"quoted\";c").unwrap();
+    /// assert_eq!(
+    ///     tree.to_json(),
+    ///     r#"{"label":"a","children":[{"label":"b \"quoted\""},{"label":"c"}]}"#
+    /// );
+    /// assert_eq!(Tree::<String>::new().to_json(), "null");
+    /// ```
+    pub fn to_json(&self) -> String {
+        if self.is_empty() {
+            return "null".to_string();
+        }
+        write_nested(
+            self,
+            |out, data, has_children| {
+                out.push_str("{\"label\":");
+                out.push_str(&json_string(&data.to_string()));
+                if has_children {
+                    out.push_str(",\"children\":[");
+                }
+            },
+            ",",
+            |out, _, has_children| out.push_str(if has_children { "]}" } else { "}" }),
+        )
+    }
+
+    /// write the tree in the format of [`Tree::from_sexpr`], every member is a list
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> = Tree::init_tree("Plants;2;no seed;seed;1;algae").unwrap();
+    /// assert_eq!(tree.to_sexpr(), "(Plants (\"no seed\" (algae)) (seed))");
+    /// ```
+    pub fn to_sexpr(&self) -> String {
+        write_nested(
+            self,
+            |out, data, has_children| {
+                out.push('(');
+                out.push_str(&sexpr_atom(&data.to_string()));
+                if has_children {
+                    out.push(' ');
+                }
+            },
+            " ",
+            |out, _, _| out.push(')'),
+        )
+    }
+}
+
+impl Tree<NewickNode> {
+    /// read a Newick tree, `_` in an unquoted name stands for a space and `[...]` comments are
+    /// skipped
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::{NewickNode, Tree};
+    /// let tree = Tree::<NewickNode>::from_newick("((algae:1.5,'Mosses':2)no_seed,seed:0.25)Plants;").unwrap();
+    /// let no_seed = tree.first_child(tree.root().unwrap()).unwrap();
+    /// assert_eq!(tree.get(no_seed).unwrap().name, "no seed");
+    /// let algae = tree.first_child(no_seed).unwrap();
+    /// assert_eq!(tree.get(algae), Some(&NewickNode::new("algae", Some(1.5))));
+    /// assert_eq!(tree.to_newick(), "((algae:1.5,Mosses:2)no_seed,seed:0.25)Plants;");
+    /// ```
+    /// # Errors
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::{NewickNode, Tree, TreeFormatErrorKind};
+    /// let error = Tree::<NewickNode>::from_newick("(a:1,b:x)c;").unwrap_err();
+    /// assert_eq!((error.kind(), error.column()), (TreeFormatErrorKind::BadLength, 8));
+    /// let error = Tree::<NewickNode>::from_newick("(a,b)c").unwrap_err();
+    /// assert_eq!((error.kind(), error.position()), (TreeFormatErrorKind::UnexpectedEnd, 6));
+    /// ```
+    pub fn from_newick(text: &str) -> Result<Tree<NewickNode>, TreeFormatError> {
+        let drafts = read_newick(text)?;
+        assemble(&drafts, |draft| {
+            let name = draft.label.as_ref().map_or(String::new(), |(name, _)| name.clone());
+            let length = match &draft.length {
+                None => None,
+                Some((length, position)) => Some(length.parse::<f64>().map_err(|_| {
+                    TreeFormatError::new(text, TreeFormatErrorKind::BadLength, *position)
+                })?),
+            };
+            Ok(NewickNode { name, length })
+        })
+    }
+
+    /// write the tree in the format of [`Tree::from_newick`], the empty tree gives an empty text
+    pub fn to_newick(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut out = write_nested(
+            self,
+            |out, _, has_children| {
+                if has_children {
+                    out.push('(');
+                }
+            },
+            ",",
+            |out, data, has_children| {
+                if has_children {
+                    out.push(')');
+                }
+                out.push_str(&newick_name(&data.name));
+                if let Some(length) = data.length {
+                    out.push_str(&format!(":{}", length));
+                }
+            },
+        );
+        out.push(';');
+        out
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeFormatErrorKind {
+    /// a character that does not fit the grammar at this place
+    UnexpectedCharacter,
+    /// the text ends inside a member
+    UnexpectedEnd,
+    /// characters are left after the root is closed
+    TrailingCharacters,
+    /// a member has no label
+    MissingLabel,
+    /// a JSON object has a key other than `label` and `children`
+    UnknownKey,
+    /// a JSON object repeats a key
+    DuplicateKey,
+    /// a string holds an escape sequence that does not exist
+    BadEscape,
+    /// a label cannot be parsed into the data type of the tree
+    BadLabel,
+    /// a Newick branch length is not a number
+    BadLength,
+}
+
+/// error of the readers of [`Tree::from_json`], [`Tree::from_sexpr`] and [`Tree::from_newick`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeFormatError {
+    kind: TreeFormatErrorKind,
+    position: usize,
+    line: usize,
+    column: usize,
+}
+
+impl TreeFormatError {
+    fn new(text: &str, kind: TreeFormatErrorKind, position: usize) -> Self {
+        let before = &text[..position];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        Self { kind, position, line, column }
+    }
+
+    pub fn kind(&self) -> TreeFormatErrorKind {
+        self.kind
+    }
+
+    /// byte offset of the offending character
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// line of the offending character, counted from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// column of the offending character in characters, counted from 1
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl std::fmt::Display for TreeFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.kind {
+            TreeFormatErrorKind::UnexpectedCharacter => "unexpected character",
+            TreeFormatErrorKind::UnexpectedEnd => "text ends inside the tree",
+            TreeFormatErrorKind::TrailingCharacters => "unexpected characters after the root",
+            TreeFormatErrorKind::MissingLabel => "member has no label",
+            TreeFormatErrorKind::UnknownKey => "unknown key",
+            TreeFormatErrorKind::DuplicateKey => "repeated key",
+            TreeFormatErrorKind::BadEscape => "invalid escape sequence",
+            TreeFormatErrorKind::BadLabel => "label cannot be parsed",
+            TreeFormatErrorKind::BadLength => "branch length is not a number",
+        };
+        write!(f, "{} at line {} column {}", reason, self.line, self.column)
+    }
+}
+
+impl std::error::Error for TreeFormatError {}
+
+// a member read from the text, the label and the length keep the byte offset they start at
+struct Draft {
+    label: Option<(String, usize)>,
+    length: Option<(String, usize)>,
+    parent: Option<usize>,
+    position: usize,
+}
+
+impl Draft {
+    fn new(parent: Option<usize>, position: usize) -> Self {
+        Self { label: None, length: None, parent, position }
+    }
+}
+
+// members in the order they are opened, so a parent always comes before its children
+struct Drafts(Vector<Draft>);
+
+impl Drafts {
+    fn open(&mut self, parent: Option<usize>, position: usize) -> usize {
+        self.0.push_back(Draft::new(parent, position));
+        self.0.len() - 1
+    }
+}
+
+fn assemble<T, F>(drafts: &Drafts, mut make: F) -> Result<Tree<T>, TreeFormatError>
+where
+    F: FnMut(&Draft) -> Result<T, TreeFormatError>,
+{
+    let mut tree = Tree::new();
+    let mut ids: Vector<NodeId<T>> = Vector::new();
+    for i in 0..drafts.0.len() {
+        let draft = &drafts.0[i];
+        let data = make(draft)?;
+        let id = match draft.parent {
+            None => tree.set_root(data),
+            Some(parent) => tree.push_child(ids[parent], data),
+        };
+        ids.push_back(id);
+    }
+    Ok(tree)
+}
+
+fn parse_label<T>(text: &str, draft: &Draft) -> Result<T, TreeFormatError>
+where
+    T: FromStr,
+{
+    let (label, position) = match &draft.label {
+        Some(label) => label,
+        None => {
+            return Err(TreeFormatError::new(
+                text,
+                TreeFormatErrorKind::MissingLabel,
+                draft.position,
+            ))
+        },
+    };
+    label
+        .parse::<T>()
+        .map_err(|_| TreeFormatError::new(text, TreeFormatErrorKind::BadLabel, *position))
+}
+
+// cursor over the text, `position` is a byte offset
+struct Scanner<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, position: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            return true;
+        }
+        false
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.position == self.text.len()
+    }
+
+    fn error(&self, kind: TreeFormatErrorKind) -> TreeFormatError {
+        TreeFormatError::new(self.text, kind, self.position)
+    }
+
+    // error for the character under the cursor
+    fn unexpected(&self) -> TreeFormatError {
+        if self.at_end() {
+            return self.error(TreeFormatErrorKind::UnexpectedEnd);
+        }
+        self.error(TreeFormatErrorKind::UnexpectedCharacter)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TreeFormatError> {
+        if self.eat(expected) {
+            return Ok(());
+        }
+        Err(self.unexpected())
+    }
+}
+
+// object of the JSON text still open, `in_children` is set inside its `children` array and
+// `first` until the first key or child is read
+#[derive(Clone, Copy)]
+struct JsonFrame {
+    draft: usize,
+    in_children: bool,
+    first: bool,
+    seen_children: bool,
+}
+
+fn read_json(text: &str) -> Result<Drafts, TreeFormatError> {
+    let mut drafts = Drafts(Vector::new());
+    let mut scanner = Scanner::new(text);
+    scanner.skip_whitespace();
+    if scanner.text[scanner.position..].starts_with("null") {
+        scanner.position += 4;
+        scanner.skip_whitespace();
+        if !scanner.at_end() {
+            return Err(scanner.error(TreeFormatErrorKind::TrailingCharacters));
+        }
+        return Ok(drafts);
+    }
+    let root = drafts.open(None, scanner.position);
+    scanner.expect('{')?;
+    let mut frames = Vector::new();
+    frames.push_back(JsonFrame {
+        draft: root,
+        in_children: false,
+        first: true,
+        seen_children: false,
+    });
+
+    while !frames.is_empty() {
+        let top = frames.len() - 1;
+        let frame = frames[top];
+        scanner.skip_whitespace();
+        if frame.in_children {
+            if scanner.eat(']') {
+                // the object has read its `children` key, whether the array held any or not
+                frames[top].in_children = false;
+                frames[top].first = false;
+                continue;
+            }
+            if !frame.first {
+                scanner.expect(',')?;
+                scanner.skip_whitespace();
+            }
+            let child = drafts.open(Some(frame.draft), scanner.position);
+            scanner.expect('{')?;
+            frames[top].first = false;
+            frames.push_back(JsonFrame {
+                draft: child,
+                in_children: false,
+                first: true,
+                seen_children: false,
+            });
+            continue;
+        }
+        if scanner.peek() == Some('}') {
+            if drafts.0[frame.draft].label.is_none() {
+                return Err(scanner.error(TreeFormatErrorKind::MissingLabel));
+            }
+            scanner.bump();
+            frames.pop_back();
+            continue;
+        }
+        if !frame.first {
+            scanner.expect(',')?;
+            scanner.skip_whitespace();
+        }
+        frames[top].first = false;
+        let key_position = scanner.position;
+        let key = read_json_string(&mut scanner)?;
+        scanner.skip_whitespace();
+        scanner.expect(':')?;
+        scanner.skip_whitespace();
+        match key.as_str() {
+            "label" => {
+                if drafts.0[frame.draft].label.is_some() {
+                    return Err(TreeFormatError::new(
+                        text,
+                        TreeFormatErrorKind::DuplicateKey,
+                        key_position,
+                    ));
+                }
+                let position = scanner.position;
+                let label = match scanner.peek() {
+                    Some('"') => read_json_string(&mut scanner)?,
+                    _ => read_json_number(&mut scanner)?,
+                };
+                drafts.0[frame.draft].label = Some((label, position));
+            },
+            "children" => {
+                if frame.seen_children {
+                    return Err(TreeFormatError::new(
+                        text,
+                        TreeFormatErrorKind::DuplicateKey,
+                        key_position,
+                    ));
+                }
+                scanner.expect('[')?;
+                frames[top] =
+                    JsonFrame { in_children: true, first: true, seen_children: true, ..frame };
+            },
+            _ => {
+                return Err(TreeFormatError::new(
+                    text,
+                    TreeFormatErrorKind::UnknownKey,
+                    key_position,
+                ))
+            },
+        }
+    }
+    scanner.skip_whitespace();
+    if !scanner.at_end() {
+        return Err(scanner.error(TreeFormatErrorKind::TrailingCharacters));
+    }
+    Ok(drafts)
+}
+
+fn read_json_string(scanner: &mut Scanner) -> Result<String, TreeFormatError> {
+    scanner.expect('"')?;
+    let mut out = String::new();
+    loop {
+        let escape_position = scanner.position;
+        match scanner.bump() {
+            None => return Err(scanner.error(TreeFormatErrorKind::UnexpectedEnd)),
+            Some('"') => return Ok(out),
+            Some('\\') => {
+                let bad_escape = TreeFormatError::new(
+                    scanner.text,
+                    TreeFormatErrorKind::BadEscape,
+                    escape_position,
+                );
+                let c = match scanner.bump() {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('/') => '/',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('u') => {
+                        let high = read_hex4(scanner).ok_or(bad_escape)?;
+                        let code = if (0xd800..0xdc00).contains(&high) {
+                            if !(scanner.eat('\\') && scanner.eat('u')) {
+                                return Err(bad_escape);
+                            }
+                            let low = read_hex4(scanner).ok_or(bad_escape)?;
+                            if !(0xdc00..0xe000).contains(&low) {
+                                return Err(bad_escape);
+                            }
+                            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                        } else {
+                            high
+                        };
+                        char::from_u32(code).ok_or(bad_escape)?
+                    },
+                    _ => return Err(bad_escape),
+                };
+                out.push(c);
+            },
+            Some(c) if (c as u32) < 0x20 => {
+                return Err(TreeFormatError::new(
+                    scanner.text,
+                    TreeFormatErrorKind::UnexpectedCharacter,
+                    escape_position,
+                ))
+            },
+            Some(c) => out.push(c),
+        }
+    }
+}
+
+fn read_hex4(scanner: &mut Scanner) -> Option<u32> {
+    let mut code = 0;
+    for _ in 0..4 {
+        code = code * 16 + scanner.bump()?.to_digit(16)?;
+    }
+    Some(code)
+}
+
+fn read_json_number(scanner: &mut Scanner) -> Result<String, TreeFormatError> {
+    let start = scanner.position;
+    while scanner.peek().is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(c)) {
+        scanner.bump();
+    }
+    if scanner.position == start {
+        return Err(scanner.unexpected());
+    }
+    Ok(scanner.text[start..scanner.position].to_string())
+}
+
+fn read_sexpr(text: &str) -> Result<Drafts, TreeFormatError> {
+    let mut drafts = Drafts(Vector::new());
+    let mut scanner = Scanner::new(text);
+    scanner.skip_whitespace();
+    if scanner.at_end() {
+        return Ok(drafts);
+    }
+    // lists still open
+    let mut open = Vector::new();
+    let root = drafts.open(None, scanner.position);
+    scanner.expect('(')?;
+    read_sexpr_label(&mut scanner, &mut drafts, root)?;
+    open.push_back(root);
+
+    while let Some(top) = open.len().checked_sub(1) {
+        scanner.skip_whitespace();
+        let parent = open[top];
+        match scanner.peek() {
+            Some(')') => {
+                scanner.bump();
+                open.pop_back();
+            },
+            Some('(') => {
+                let child = drafts.open(Some(parent), scanner.position);
+                scanner.bump();
+                read_sexpr_label(&mut scanner, &mut drafts, child)?;
+                open.push_back(child);
+            },
+            Some(_) => {
+                let leaf = drafts.open(Some(parent), scanner.position);
+                read_sexpr_label(&mut scanner, &mut drafts, leaf)?;
+            },
+            None => return Err(scanner.error(TreeFormatErrorKind::UnexpectedEnd)),
+        }
+    }
+    scanner.skip_whitespace();
+    if !scanner.at_end() {
+        return Err(scanner.error(TreeFormatErrorKind::TrailingCharacters));
+    }
+    Ok(drafts)
+}
+
+fn read_sexpr_label(
+    scanner: &mut Scanner,
+    drafts: &mut Drafts,
+    draft: usize,
+) -> Result<(), TreeFormatError> {
+    scanner.skip_whitespace();
+    let position = scanner.position;
+    let label = match scanner.peek() {
+        Some('"') => {
+            scanner.bump();
+            let mut label = String::new();
+            loop {
+                match scanner.bump() {
+                    None => return Err(scanner.error(TreeFormatErrorKind::UnexpectedEnd)),
+                    Some('"') => break,
+                    Some('\\') => match scanner.bump() {
+                        Some(c) => label.push(c),
+                        None => return Err(scanner.error(TreeFormatErrorKind::UnexpectedEnd)),
+                    },
+                    Some(c) => label.push(c),
+                }
+            }
+            label
+        },
+        Some(c) if is_sexpr_atom(c) => {
+            while scanner.peek().is_some_and(is_sexpr_atom) {
+                scanner.bump();
+            }
+            scanner.text[position..scanner.position].to_string()
+        },
+        Some(')') | Some('(') => return Err(scanner.error(TreeFormatErrorKind::MissingLabel)),
+        _ => return Err(scanner.unexpected()),
+    };
+    drafts.0[draft].label = Some((label, position));
+    Ok(())
+}
+
+fn is_sexpr_atom(c: char) -> bool {
+    !c.is_whitespace() && !"()\";".contains(c)
+}
+
+fn read_newick(text: &str) -> Result<Drafts, TreeFormatError> {
+    let mut drafts = Drafts(Vector::new());
+    let mut scanner = Scanner::new(text);
+    skip_newick_blank(&mut scanner)?;
+    if scanner.at_end() {
+        return Ok(drafts);
+    }
+    // members whose `(` is read but not yet their `)`
+    let mut open: Vector<usize> = Vector::new();
+    let mut expect_subtree = true;
+    loop {
+        skip_newick_blank(&mut scanner)?;
+        let parent = open.len().checked_sub(1).map(|top| open[top]);
+        if expect_subtree {
+            let draft = drafts.open(parent, scanner.position);
+            if scanner.eat('(') {
+                open.push_back(draft);
+                continue;
+            }
+            read_newick_suffix(&mut scanner, &mut drafts, draft)?;
+            expect_subtree = false;
+            continue;
+        }
+        match scanner.peek() {
+            Some(',') if parent.is_some() => {
+                scanner.bump();
+                expect_subtree = true;
+            },
+            Some(')') if parent.is_some() => {
+                scanner.bump();
+                open.pop_back();
+                read_newick_suffix(&mut scanner, &mut drafts, parent.expect("checked above"))?;
+            },
+            Some(';') if parent.is_none() => {
+                scanner.bump();
+                break;
+            },
+            _ => return Err(scanner.unexpected()),
+        }
+    }
+    skip_newick_blank(&mut scanner)?;
+    if !scanner.at_end() {
+        return Err(scanner.error(TreeFormatErrorKind::TrailingCharacters));
+    }
+    Ok(drafts)
+}
+
+// optional name and optional `:length` after a leaf or a `)`
+fn read_newick_suffix(
+    scanner: &mut Scanner,
+    drafts: &mut Drafts,
+    draft: usize,
+) -> Result<(), TreeFormatError> {
+    skip_newick_blank(scanner)?;
+    let position = scanner.position;
+    let mut name = String::new();
+    if scanner.eat('\'') {
+        loop {
+            match scanner.bump() {
+                None => return Err(scanner.error(TreeFormatErrorKind::UnexpectedEnd)),
+                Some('\'') if scanner.eat('\'') => name.push('\''),
+                Some('\'') => break,
+                Some(c) => name.push(c),
+            }
+        }
+    } else {
+        while let Some(c) = scanner.peek().filter(|&c| is_newick_plain(c)) {
+            scanner.bump();
+            name.push(if c == '_' { ' ' } else { c });
+        }
+    }
+    if scanner.position > position {
+        drafts.0[draft].label = Some((name, position));
+    }
+    skip_newick_blank(scanner)?;
+    if scanner.eat(':') {
+        skip_newick_blank(scanner)?;
+        let start = scanner.position;
+        while scanner.peek().is_some_and(is_newick_plain) {
+            scanner.bump();
+        }
+        drafts.0[draft].length = Some((scanner.text[start..scanner.position].to_string(), start));
+    }
+    Ok(())
+}
+
+// whitespace and `[...]` comments
+fn skip_newick_blank(scanner: &mut Scanner) -> Result<(), TreeFormatError> {
+    loop {
+        scanner.skip_whitespace();
+        if !scanner.eat('[') {
+            return Ok(());
+        }
+        while scanner.bump().ok_or_else(|| scanner.error(TreeFormatErrorKind::UnexpectedEnd))?
+            != ']'
+        {}
+    }
+}
+
+fn is_newick_plain(c: char) -> bool {
+    !c.is_whitespace() && !"()[]':;,".contains(c)
+}
+
+// depth first walk with an explicit stack, `open` writes a member before its children,
+// `separator` goes between two siblings and `close` writes after the children
+fn write_nested<T, O, C>(tree: &Tree<T>, mut open: O, separator: &str, mut close: C) -> String
+where
+    O: FnMut(&mut String, &T, bool),
+    C: FnMut(&mut String, &T, bool),
+{
+    let mut out = String::new();
+    let mut member_stack = Vector::new();
+    if let Some(root) = tree.root() {
+        open(&mut out, tree.data(root), tree.child_count(root) > 0);
+        member_stack.push_back((root, 0));
+    }
+    while let Some(top) = member_stack.len().checked_sub(1) {
+        let (node, visited) = member_stack[top];
+        let has_children = tree.child_count(node) > 0;
+        match tree.child(node, visited) {
+            Some(child) => {
+                if visited > 0 {
+                    out.push_str(separator);
+                }
+                member_stack[top].1 += 1;
+                open(&mut out, tree.data(child), tree.child_count(child) > 0);
+                member_stack.push_back((child, 0));
+            },
+            None => {
+                close(&mut out, tree.data(node), has_children);
+                member_stack.pop_back();
+            },
+        }
+    }
+    out
+}
+
+fn json_string(label: &str) -> String {
+    let mut out = String::from("\"");
+    for c in label.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn sexpr_atom(label: &str) -> String {
+    if !label.is_empty() && label.chars().all(is_sexpr_atom) {
+        return label.to_string();
+    }
+    let mut out = String::from("\"");
+    for c in label.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    out
+}
+
+// plain when possible, spaces turned into `_`, quoted otherwise
+fn newick_name(name: &str) -> String {
+    if name.chars().all(|c| c == ' ' || (is_newick_plain(c) && c != '_')) {
+        return name.replace(' ', "_");
+    }
+    format!("'{}'", name.replace('\'', "''"))
+}
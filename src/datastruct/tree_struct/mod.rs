@@ -0,0 +1,1404 @@
+//! minimal tree structure written `Tree<T>`
+//!
+//! formula for tree type sada storage functionality
+//! it is implemented as n type tree and support any general case of tree like bst, every member
+//! keeps the data and a [`Vector`] stem holding the address of its children. Heights, depths and
+//! diameters are counted in edges, the root is at depth 0.
+//! # Example
+//! ```
+//! # use dsa_sport::datastruct::tree_struct::Tree;
+//! let tree: Tree<String> =
+//!     Tree::init_tree("Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower").unwrap();
+//! assert_eq!(
+//!     format!("{}", tree),
+//!     "╚═Plants\n  ╠═no seed\n  ║ ╠═algae\n  ║ ╠═Mosses\n  ║ ╚═ferns\n  ╚═seed\n    ╠═Conifers\n    ╚═sunflower\n"
+//! );
+//! ```
+mod compare;
+mod fs_entry;
+mod interchange;
+mod transform;
+mod zipper;
+
+pub use compare::TreeEdit;
+pub use fs_entry::{FsEntry, FsKind, WalkOptions};
+pub use interchange::{NewickNode, TreeFormatError, TreeFormatErrorKind};
+pub use zipper::TreeZipper;
+
+use crate::datastruct::vec_struct::Vector;
+use core::cell::{Cell, RefCell};
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr;
+use core::str::FromStr;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::alloc;
+use std::collections::HashMap;
+
+const EDGE: &str = "╠═";
+const LINE: &str = "║ ";
+const CORNER: &str = "╚═";
+const BLANK: &str = "  ";
+
+// serial number of the next member or stamp, shared by every tree so that no two members and no
+// two stamps ever get the same one
+static NEXT_SERIAL: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // address of every live member of the thread by serial number, a handle is looked up here
+    // before its member is touched. A tree never leaves its thread, it holds raw pointers
+    static LIVE_MEMBERS: RefCell<HashMap<usize, usize>> = RefCell::new(HashMap::new());
+}
+
+fn next_serial() -> usize {
+    NEXT_SERIAL.fetch_add(1, Ordering::Relaxed)
+}
+
+struct Member<T> {
+    data: T,
+    stem: Vector<*mut Member<T>>,
+    // null for the root
+    parent: *mut Member<T>,
+    serial: usize,
+    // stamp of the tree the member was last seen in, see `Tree::member`
+    stamp: Cell<usize>,
+}
+
+impl<T> Member<T> {
+    fn new(element: T, parent: *mut Member<T>, stamp: usize) -> Self {
+        let serial = next_serial();
+        Self { data: element, stem: Vector::new(), parent, serial, stamp: Cell::new(stamp) }
+    }
+
+    fn allocate_memory(candidate: Member<T>) -> *mut Member<T> {
+        let size = mem::size_of::<Member<T>>();
+        let align = mem::align_of::<Member<T>>();
+        let serial = candidate.serial;
+        let ptr = unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            let ptr = alloc::alloc(layout) as *mut Member<T>;
+            ptr.write(candidate);
+            ptr
+        };
+        LIVE_MEMBERS.with(|live| live.borrow_mut().insert(serial, ptr as usize));
+        ptr
+    }
+
+    unsafe fn deallocate_memory(node: *mut Member<T>) -> Member<T> {
+        let size = mem::size_of::<Member<T>>();
+        let align = mem::align_of::<Member<T>>();
+        let member = node.read();
+        // the table is gone already when a tree is dropped by another thread local destructor
+        let _ = LIVE_MEMBERS.try_with(|live| live.borrow_mut().remove(&member.serial));
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(node as *mut u8, layout);
+        member
+    }
+}
+
+pub struct Tree<T> {
+    root: *mut Member<T>,
+    // renewed whenever a subtree is cut out, a member carrying the current stamp is known to
+    // belong to the tree
+    stamp: usize,
+}
+
+/// handle on a member of a [`Tree`], it stays valid while the member is in the tree it was
+/// handed out by, even when the member is moved around inside that tree
+///
+/// a handle holds the serial number of its member, which is never given to another member, so
+/// a handle of a removed member or of a member of another tree is caught: [`Tree::get`] and the
+/// other methods handing back data or handles answer `None`, the remaining methods panic.
+/// Checking a handle costs O(1), except for the first check after a subtree was cut out of the
+/// tree, which climbs to the nearest member checked since then.
+/// ``` rust
+/// use dsa_sport::datastruct::tree_struct::Tree;
+/// let mut tree: Tree<char> = Tree::init_tree("a;2;b;c").unwrap();
+/// let b = tree.child(tree.root().unwrap(), 0).unwrap();
+/// drop(tree.remove_subtree(b));
+/// let other: Tree<char> = Tree::init_tree("z").unwrap();
+/// assert_eq!(tree.get(b), None);
+/// assert_eq!(tree.get(other.root().unwrap()), None);
+/// ```
+pub struct NodeId<T> {
+    serial: usize,
+    marker: PhantomData<*const T>,
+}
+
+impl<T> NodeId<T> {
+    fn of(member: *mut Member<T>) -> Self {
+        Self { serial: unsafe { (*member).serial }, marker: PhantomData }
+    }
+}
+
+impl<T> Clone for NodeId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeId<T> {}
+
+impl<T> PartialEq for NodeId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.serial == other.serial
+    }
+}
+
+impl<T> Eq for NodeId<T> {}
+
+impl<T> std::hash::Hash for NodeId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.serial.hash(state);
+    }
+}
+
+impl<T> std::fmt::Debug for NodeId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NodeId({})", self.serial)
+    }
+}
+
+impl<T> Tree<T> {
+    pub fn new() -> Self {
+        Self { root: ptr::null_mut(), stamp: next_serial() }
+    }
+
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> = Tree::new();
+    /// assert!(tree.is_empty());
+    /// let tree: Tree<String> = Tree::init_tree("root;0").unwrap();
+    /// assert!(!tree.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.root.is_null()
+    }
+
+    pub fn root(&self) -> Option<NodeId<T>> {
+        if self.root.is_null() {
+            return None;
+        }
+        Some(NodeId::of(self.root))
+    }
+
+    /// put a new root on top of the tree, the old root becomes its only child
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let mut tree = Tree::new();
+    /// tree.set_root("b");
+    /// tree.set_root("a");
+    /// assert_eq!(tree.to_tree_code(), "a;1;b");
+    /// ```
+    pub fn set_root(&mut self, element: T) -> NodeId<T> {
+        let old_root = self.root;
+        self.root = self.allocate(element, ptr::null_mut());
+        if !old_root.is_null() {
+            unsafe {
+                (*old_root).parent = self.root;
+                (*self.root).stem.push_back(old_root);
+            }
+        }
+        NodeId::of(self.root)
+    }
+
+    /// tell whether `node` is a member of this tree
+    pub fn contains(&self, node: NodeId<T>) -> bool {
+        self.member(node).is_some()
+    }
+
+    /// data of `node`, `None` if it is not a member of this tree
+    pub fn get(&self, node: NodeId<T>) -> Option<&T> {
+        self.member(node).map(|member| unsafe { &(*member).data })
+    }
+
+    /// mutable flavour of [`Tree::get`]
+    pub fn get_mut(&mut self, node: NodeId<T>) -> Option<&mut T> {
+        self.member(node).map(|member| unsafe { &mut (*member).data })
+    }
+
+    /// # Panics
+    /// when `node` is not a member of this tree
+    pub fn child_count(&self, node: NodeId<T>) -> usize {
+        unsafe { (*self.expect_member(node)).stem.len() }
+    }
+
+    pub fn child(&self, node: NodeId<T>, index: usize) -> Option<NodeId<T>> {
+        let stem = unsafe { &(*self.member(node)?).stem };
+        if index >= stem.len() {
+            return None;
+        }
+        Some(NodeId::of(stem[index]))
+    }
+
+    /// append a new last child to `parent`
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let mut tree = Tree::new();
+    /// let root = tree.set_root("Plants");
+    /// let seed = tree.add_child(root, "seed").unwrap();
+    /// tree.add_child(seed, "Conifers");
+    /// tree.add_child(root, "no seed");
+    /// assert_eq!(tree.to_tree_code(), "Plants;2;seed;no seed;1;Conifers");
+    /// ```
+    pub fn add_child(&mut self, parent: NodeId<T>, element: T) -> Option<NodeId<T>> {
+        let index = unsafe { (*self.member(parent)?).stem.len() };
+        self.insert_child_at(parent, index, element)
+    }
+
+    /// insert a new child of `parent` at `index`, later children shift to the right. `None` if
+    /// `parent` is not a member of this tree
+    /// # Panics
+    /// when `index` is greater than the number of children
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let mut tree: Tree<char> = Tree::init_tree("a;2;b;d").unwrap();
+    /// let root = tree.root().unwrap();
+    /// tree.insert_child_at(root, 1, 'c');
+    /// assert_eq!(tree.to_tree_code(), "a;3;b;c;d");
+    /// ```
+    pub fn insert_child_at(
+        &mut self,
+        parent: NodeId<T>,
+        index: usize,
+        element: T,
+    ) -> Option<NodeId<T>> {
+        let parent = self.member(parent)?;
+        assert!(index <= unsafe { (*parent).stem.len() }, "child index out of bounds");
+        let node = self.allocate(element, parent);
+        unsafe {
+            (*parent).stem.insert_at(index, node);
+        }
+        Some(NodeId::of(node))
+    }
+
+    /// detach the subtree rooted at `node` and hand it back as a tree of its own, its members
+    /// keep their handles. The members of the subtree are not visited, so apart from finding
+    /// `node` among its siblings the cut takes O(1). `None` if `node` is not a member of this tree
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let mut tree: Tree<char> = Tree::init_tree("a;2;b;c;2;d;e").unwrap();
+    /// let b = tree.child(tree.root().unwrap(), 0).unwrap();
+    /// let branch = tree.remove_subtree(b).unwrap();
+    /// assert_eq!(branch.to_tree_code(), "b;2;d;e");
+    /// assert_eq!(tree.to_tree_code(), "a;1;c");
+    /// assert!(tree.remove_subtree(b).is_none());
+    /// ```
+    pub fn remove_subtree(&mut self, node: NodeId<T>) -> Option<Tree<T>> {
+        let node = self.member(node)?;
+        self.unlink(node);
+        // the members of the branch still carry the old stamp
+        self.stamp = next_serial();
+        Some(Tree { root: node, stamp: next_serial() })
+    }
+
+    /// hang the root of `subtree` below `parent` at `index`, its members keep their handles. Like
+    /// [`Tree::remove_subtree`] it does not visit the members of the subtree. The subtree is
+    /// handed back as the error when it is empty or `parent` is not a member of this tree
+    /// # Panics
+    /// when `index` is greater than the number of children
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let mut tree: Tree<char> = Tree::init_tree("a;2;b;e").unwrap();
+    /// let branch: Tree<char> = Tree::init_tree("c;1;d").unwrap();
+    /// let root = tree.root().unwrap();
+    /// let c = tree.insert_subtree_at(root, 1, branch).unwrap();
+    /// assert_eq!(tree.to_tree_code(), "a;3;b;c;e;0;1;d");
+    /// assert_eq!(tree.parent(c), Some(root));
+    /// ```
+    pub fn insert_subtree_at(
+        &mut self,
+        parent: NodeId<T>,
+        index: usize,
+        mut subtree: Tree<T>,
+    ) -> Result<NodeId<T>, Tree<T>> {
+        let parent = match self.member(parent) {
+            Some(parent) if !subtree.root.is_null() => parent,
+            _ => return Err(subtree),
+        };
+        assert!(index <= unsafe { (*parent).stem.len() }, "child index out of bounds");
+        let node = mem::replace(&mut subtree.root, ptr::null_mut());
+        unsafe {
+            (*node).parent = parent;
+            (*parent).stem.insert_at(index, node);
+        }
+        Ok(NodeId::of(node))
+    }
+
+    /// make `node` the last child of `new_parent`, the subtree travels along. `false` if either
+    /// handle is not a member of this tree
+    /// # Panics
+    /// when `new_parent` belongs to the subtree of `node`
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let mut tree: Tree<char> = Tree::init_tree("a;2;b;c;2;d;e").unwrap();
+    /// let root = tree.root().unwrap();
+    /// let (b, c) = (tree.child(root, 0).unwrap(), tree.child(root, 1).unwrap());
+    /// assert!(tree.move_subtree(b, c));
+    /// assert_eq!(tree.to_tree_code(), "a;1;c;1;b;2;d;e");
+    /// ```
+    pub fn move_subtree(&mut self, node: NodeId<T>, new_parent: NodeId<T>) -> bool {
+        let (node, new_parent) = match (self.member(node), self.member(new_parent)) {
+            (Some(node), Some(new_parent)) => (node, new_parent),
+            _ => return false,
+        };
+        let cyclic = Walker::new(node, Traversal::PreOrder).any(|(_, member)| member == new_parent);
+        assert!(!cyclic, "cannot move a subtree below one of its own members");
+        self.unlink(node);
+        unsafe {
+            (*node).parent = new_parent;
+            (*new_parent).stem.push_back(node);
+        }
+        true
+    }
+
+    /// exchange the children of `parent` at `i` and `j`
+    /// # Panics
+    /// when `i` or `j` is out of bounds or `parent` is not a member of this tree
+    pub fn swap_children(&mut self, parent: NodeId<T>, i: usize, j: usize) {
+        let parent = self.expect_member(parent);
+        unsafe {
+            (*parent).stem.swap(i, j);
+        }
+    }
+
+    /// stable sort of the children of `parent`, grandchildren keep their order
+    /// # Panics
+    /// when `parent` is not a member of this tree
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let mut tree: Tree<String> = Tree::init_tree("root;4;pear;fig;plum;kiwi").unwrap();
+    /// let root = tree.root().unwrap();
+    /// tree.sort_children_by(root, |a, b| a.len().cmp(&b.len()));
+    /// assert_eq!(tree.to_tree_code(), "root;4;fig;pear;plum;kiwi");
+    /// ```
+    pub fn sort_children_by<F>(&mut self, parent: NodeId<T>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> core::cmp::Ordering,
+    {
+        let stem = unsafe { &mut (*self.expect_member(parent)).stem };
+        // insertion sort, children lists are short and it keeps equal children in place
+        for i in 1..stem.len() {
+            let mut j = i;
+            while j > 0 {
+                let (before, after) = unsafe { (&(*stem[j - 1]).data, &(*stem[j]).data) };
+                if compare(before, after) != core::cmp::Ordering::Greater {
+                    break;
+                }
+                stem.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    pub fn parent(&self, node: NodeId<T>) -> Option<NodeId<T>> {
+        let parent = unsafe { (*self.member(node)?).parent };
+        if parent.is_null() {
+            return None;
+        }
+        Some(NodeId::of(parent))
+    }
+
+    pub fn first_child(&self, node: NodeId<T>) -> Option<NodeId<T>> {
+        self.child(node, 0)
+    }
+
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<char> = Tree::init_tree("a;3;b;c;d").unwrap();
+    /// let b = tree.first_child(tree.root().unwrap()).unwrap();
+    /// let c = tree.next_sibling(b).unwrap();
+    /// assert_eq!(tree.get(c), Some(&'c'));
+    /// assert_eq!(tree.prev_sibling(c), Some(b));
+    /// assert_eq!(tree.prev_sibling(b), None);
+    /// assert_eq!(tree.next_sibling(tree.root().unwrap()), None);
+    /// ```
+    pub fn next_sibling(&self, node: NodeId<T>) -> Option<NodeId<T>> {
+        let parent = self.parent(node)?;
+        self.child(parent, self.index_in_parent(self.member(node)?) + 1)
+    }
+
+    pub fn prev_sibling(&self, node: NodeId<T>) -> Option<NodeId<T>> {
+        let parent = self.parent(node)?;
+        let index = self.index_in_parent(self.member(node)?).checked_sub(1)?;
+        self.child(parent, index)
+    }
+
+    /// members above `node` from its parent up to the root
+    /// # Panics
+    /// when `node` is not a member of this tree
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> =
+    ///     Tree::init_tree("Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower").unwrap();
+    /// let mosses = tree.find_by_path("Plants/no seed/Mosses").unwrap();
+    /// let crumbs: Vec<&str> =
+    ///     tree.ancestors(mosses).map(|node| tree.get(node).unwrap().as_str()).collect();
+    /// assert_eq!(crumbs, ["no seed", "Plants"]);
+    /// ```
+    pub fn ancestors(&self, node: NodeId<T>) -> Ancestors<'_, T> {
+        let node = self.expect_member(node);
+        Ancestors { next: unsafe { (*node).parent }, marker: PhantomData }
+    }
+
+    /// number of edges on the longest path from the root down to a leaf, like
+    /// [`Tree::depth`] and [`Tree::diameter`] it counts edges, so a lone root and the empty tree
+    /// both have height 0
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> =
+    ///     Tree::init_tree("Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower").unwrap();
+    /// assert_eq!(tree.height(), 2);
+    /// assert_eq!(tree.size(), 8);
+    /// assert_eq!(tree.leaf_count(), 5);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.members().map(|(depth, _)| depth).max().unwrap_or(0)
+    }
+
+    /// number of members
+    pub fn size(&self) -> usize {
+        self.members().count()
+    }
+
+    /// number of members without children
+    pub fn leaf_count(&self) -> usize {
+        self.members().filter(|&(_, member)| unsafe { (*member).stem.is_empty() }).count()
+    }
+
+    /// entry `d` holds the number of members with exactly `d` children
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<char> = Tree::init_tree("a;2;b;c;3;d;e;f").unwrap();
+    /// let histogram = tree.degree_histogram();
+    /// assert_eq!((histogram[0], histogram[1], histogram[2], histogram[3]), (4, 0, 1, 1));
+    /// assert_eq!(histogram.len(), 4);
+    /// ```
+    pub fn degree_histogram(&self) -> Vector<usize> {
+        let mut histogram = Vector::new();
+        for (_, member) in self.members() {
+            let degree = unsafe { (*member).stem.len() };
+            while histogram.len() <= degree {
+                histogram.push_back(0);
+            }
+            histogram[degree] += 1;
+        }
+        histogram
+    }
+
+    /// number of edges between the root and `node`
+    pub fn depth(&self, node: NodeId<T>) -> usize {
+        self.ancestors(node).count()
+    }
+
+    /// members from the root down to `node`, both included
+    /// # Panics
+    /// when `node` is not a member of this tree
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<char> = Tree::init_tree("a;2;b;c;1;d").unwrap();
+    /// let d = tree.find_by_path("a/b/d").unwrap();
+    /// let path = tree.path(d);
+    /// assert_eq!(tree.depth(d), 2);
+    /// assert_eq!((0..path.len()).map(|i| *tree.get(path[i]).unwrap()).collect::<String>(), "abd");
+    /// ```
+    pub fn path(&self, node: NodeId<T>) -> Vector<NodeId<T>> {
+        let mut path = Vector::new();
+        let mut members = self.path_to(self.expect_member(node));
+        while let Some(member) = members.pop_front() {
+            path.push_back(NodeId::of(member));
+        }
+        path
+    }
+
+    /// deepest member having both `one` and `two` in its subtree
+    /// # Panics
+    /// when `one` or `two` is not a member of this tree
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> =
+    ///     Tree::init_tree("Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower").unwrap();
+    /// let algae = tree.find_by_path("Plants/no seed/algae").unwrap();
+    /// let ferns = tree.find_by_path("Plants/no seed/ferns").unwrap();
+    /// let conifers = tree.find_by_path("Plants/seed/Conifers").unwrap();
+    /// assert_eq!(tree.get(tree.lowest_common_ancestor(algae, ferns)).unwrap(), "no seed");
+    /// assert_eq!(tree.get(tree.lowest_common_ancestor(algae, conifers)).unwrap(), "Plants");
+    /// assert_eq!(tree.lowest_common_ancestor(ferns, ferns), ferns);
+    /// ```
+    pub fn lowest_common_ancestor(&self, one: NodeId<T>, two: NodeId<T>) -> NodeId<T> {
+        let (mut one_depth, mut two_depth) = (self.depth(one), self.depth(two));
+        let (mut one, mut two) = (self.expect_member(one), self.expect_member(two));
+        while one_depth > two_depth {
+            one = unsafe { (*one).parent };
+            one_depth -= 1;
+        }
+        while two_depth > one_depth {
+            two = unsafe { (*two).parent };
+            two_depth -= 1;
+        }
+        while one != two {
+            unsafe {
+                one = (*one).parent;
+                two = (*two).parent;
+            }
+        }
+        NodeId::of(one)
+    }
+
+    /// number of edges on the longest path between two members
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<char> = Tree::init_tree("a;2;b;c;1;d;0;1;e").unwrap();
+    /// assert_eq!(tree.diameter(), 4);
+    /// let chain: Tree<char> = Tree::init_tree("a;1;b;1;c").unwrap();
+    /// assert_eq!(chain.diameter(), 2);
+    /// ```
+    pub fn diameter(&self) -> usize {
+        if self.root.is_null() {
+            return 0;
+        }
+        // (member, visited children, longest and second longest chain hanging below it)
+        let mut member_stack = Vector::new();
+        member_stack.push_back((self.root, 0, 0, 0));
+        let mut diameter = 0;
+        while !member_stack.is_empty() {
+            let top = member_stack.len() - 1;
+            let (candidate_ptr, visited, longest, second) = member_stack[top];
+            let stem = unsafe { &(*candidate_ptr).stem };
+            if visited < stem.len() {
+                member_stack[top].1 += 1;
+                member_stack.push_back((stem[visited], 0, 0, 0));
+                continue;
+            }
+            member_stack.pop_back();
+            diameter = diameter.max(longest + second);
+            if let Some(parent) = member_stack.len().checked_sub(1) {
+                let chain = longest + 1;
+                if chain > member_stack[parent].2 {
+                    member_stack[parent].3 = member_stack[parent].2;
+                    member_stack[parent].2 = chain;
+                } else if chain > member_stack[parent].3 {
+                    member_stack[parent].3 = chain;
+                }
+            }
+        }
+        diameter
+    }
+
+    /// walk the tree in the given order, every item is the depth of the member (the root is at
+    /// depth 0) and its data
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::{Traversal, Tree};
+    /// let tree: Tree<char> = Tree::init_tree("a;2;b;c;1;d").unwrap();
+    /// let walk = |order| tree.traverse(order).map(|(_, c)| *c).collect::<String>();
+    /// assert_eq!(walk(Traversal::PreOrder), "abdc");
+    /// assert_eq!(walk(Traversal::PostOrder), "dbca");
+    /// assert_eq!(walk(Traversal::LevelOrder), "abcd");
+    /// ```
+    pub fn traverse(&self, order: Traversal) -> Iter<'_, T> {
+        Iter { walker: Walker::new(self.root, order), marker: PhantomData }
+    }
+
+    /// mutable flavour of [`Tree::traverse`]
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::{Traversal, Tree};
+    /// let mut tree: Tree<usize> = Tree::init_tree("0;2;0;0;1;0").unwrap();
+    /// for (depth, data) in tree.traverse_mut(Traversal::LevelOrder) {
+    ///     *data = depth;
+    /// }
+    /// assert_eq!(tree.to_tree_code(), "0;2;1;1;1;2");
+    /// ```
+    pub fn traverse_mut(&mut self, order: Traversal) -> IterMut<'_, T> {
+        IterMut { walker: Walker::new(self.root, order), marker: PhantomData }
+    }
+
+    /// depth first walk visiting a member before its children
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> = Tree::init_tree("Plants;2;no seed;seed;1;algae").unwrap();
+    /// let items: Vec<(usize, &String)> = tree.preorder().collect();
+    /// assert_eq!(items[1], (1, &"no seed".to_string()));
+    /// assert_eq!(items[2], (2, &"algae".to_string()));
+    /// ```
+    pub fn preorder(&self) -> Iter<'_, T> {
+        self.traverse(Traversal::PreOrder)
+    }
+
+    /// depth first walk visiting a member after its children
+    pub fn postorder(&self) -> Iter<'_, T> {
+        self.traverse(Traversal::PostOrder)
+    }
+
+    /// breadth first walk, level by level from the root
+    pub fn level_order(&self) -> Iter<'_, T> {
+        self.traverse(Traversal::LevelOrder)
+    }
+
+    pub fn preorder_mut(&mut self) -> IterMut<'_, T> {
+        self.traverse_mut(Traversal::PreOrder)
+    }
+
+    pub fn postorder_mut(&mut self) -> IterMut<'_, T> {
+        self.traverse_mut(Traversal::PostOrder)
+    }
+
+    pub fn level_order_mut(&mut self) -> IterMut<'_, T> {
+        self.traverse_mut(Traversal::LevelOrder)
+    }
+
+    // preorder walk over the raw members
+    fn members(&self) -> Walker<T> {
+        Walker::new(self.root, Traversal::PreOrder)
+    }
+
+    // walk handing out handles, for the submodules
+    fn nodes(&self, order: Traversal) -> impl Iterator<Item = (usize, NodeId<T>)> {
+        Walker::new(self.root, order).map(|(depth, member)| (depth, NodeId::of(member)))
+    }
+
+    // new member below `parent` carrying the stamp of the tree, the caller links it in
+    fn allocate(&mut self, element: T, parent: *mut Member<T>) -> *mut Member<T> {
+        Member::allocate_memory(Member::new(element, parent, self.stamp))
+    }
+
+    // the member of `node`, `None` for a removed member or a member of another tree. A member
+    // without the current stamp climbs until it meets a member with it or the top of its tree,
+    // the members on the way are stamped when that is this tree
+    fn member(&self, node: NodeId<T>) -> Option<*mut Member<T>> {
+        let address = LIVE_MEMBERS.with(|live| live.borrow().get(&node.serial).copied())?;
+        let member = address as *mut Member<T>;
+        let mut top = member;
+        unsafe {
+            while (*top).stamp.get() != self.stamp && !(*top).parent.is_null() {
+                top = (*top).parent;
+            }
+            if (*top).stamp.get() != self.stamp && top != self.root {
+                return None;
+            }
+            let mut current = member;
+            while current != top {
+                (*current).stamp.set(self.stamp);
+                current = (*current).parent;
+            }
+            (*top).stamp.set(self.stamp);
+        }
+        Some(member)
+    }
+
+    fn expect_member(&self, node: NodeId<T>) -> *mut Member<T> {
+        self.member(node).expect("node does not belong to this tree")
+    }
+
+    // data of a handle the tree handed out itself, for the submodules
+    fn data(&self, node: NodeId<T>) -> &T {
+        unsafe { &(*self.expect_member(node)).data }
+    }
+
+    fn data_mut(&mut self, node: NodeId<T>) -> &mut T {
+        unsafe { &mut (*self.expect_member(node)).data }
+    }
+
+    // new last child of a handle the tree handed out itself, for the submodules
+    fn push_child(&mut self, parent: NodeId<T>, element: T) -> NodeId<T> {
+        self.add_child(parent, element).expect("node does not belong to this tree")
+    }
+
+    // members from the root down to `node`
+    fn path_to(&self, node: *mut Member<T>) -> Vector<*mut Member<T>> {
+        let mut upwards = Vector::new();
+        let mut current = node;
+        while !current.is_null() {
+            upwards.push_back(current);
+            current = unsafe { (*current).parent };
+        }
+        let mut path = Vector::new();
+        while let Some(member) = upwards.pop_back() {
+            path.push_back(member);
+        }
+        path
+    }
+
+    // position of `node` in the stem of its parent
+    fn index_in_parent(&self, node: *mut Member<T>) -> usize {
+        let stem = unsafe { &(*(*node).parent).stem };
+        (0..stem.len()).find(|&i| stem[i] == node).expect("parent lists its children")
+    }
+
+    // take `node` out of the stem of its parent, or out of the root
+    fn unlink(&mut self, node: *mut Member<T>) {
+        if node == self.root {
+            self.root = ptr::null_mut();
+            return;
+        }
+        let index = self.index_in_parent(node);
+        unsafe {
+            (*(*node).parent).stem.remove_at(index);
+            (*node).parent = ptr::null_mut();
+        }
+    }
+}
+
+/// order in which [`Tree::traverse`] visits the members
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Traversal {
+    PreOrder,
+    PostOrder,
+    LevelOrder,
+}
+
+// explicit stack (depth first) or queue (breadth first) of `(member, depth, visited children)`
+// so that deep trees never overflow the call stack
+struct Walker<T> {
+    order: Traversal,
+    pending: Vector<(*mut Member<T>, usize, usize)>,
+}
+
+impl<T> Walker<T> {
+    fn new(root: *mut Member<T>, order: Traversal) -> Self {
+        let mut pending = Vector::new();
+        if !root.is_null() {
+            pending.push_back((root, 0, 0));
+        }
+        Self { order, pending }
+    }
+}
+
+impl<T> Iterator for Walker<T> {
+    type Item = (usize, *mut Member<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.order {
+            Traversal::PreOrder => {
+                let (node, depth, _) = self.pending.pop_back()?;
+                let stem = unsafe { &(*node).stem };
+                for i in (0..stem.len()).rev() {
+                    self.pending.push_back((stem[i], depth + 1, 0));
+                }
+                Some((depth, node))
+            },
+            Traversal::LevelOrder => {
+                let (node, depth, _) = self.pending.pop_front()?;
+                let stem = unsafe { &(*node).stem };
+                for i in 0..stem.len() {
+                    self.pending.push_back((stem[i], depth + 1, 0));
+                }
+                Some((depth, node))
+            },
+            Traversal::PostOrder => loop {
+                let top = self.pending.len().checked_sub(1)?;
+                let (node, depth, visited) = self.pending[top];
+                let stem = unsafe { &(*node).stem };
+                if visited < stem.len() {
+                    self.pending[top].2 += 1;
+                    self.pending.push_back((stem[visited], depth + 1, 0));
+                } else {
+                    self.pending.pop_back();
+                    return Some((depth, node));
+                }
+            },
+        }
+    }
+}
+
+/// iterator of [`Tree::ancestors`]
+pub struct Ancestors<'a, T> {
+    next: *mut Member<T>,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Ancestors<'a, T> {
+    type Item = NodeId<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.is_null() {
+            return None;
+        }
+        let node = self.next;
+        self.next = unsafe { (*node).parent };
+        Some(NodeId::of(node))
+    }
+}
+
+/// iterator of [`Tree::traverse`] yielding `(depth, &data)`
+pub struct Iter<'a, T> {
+    walker: Walker<T>,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.walker.next()?;
+        Some((depth, unsafe { &(*node).data }))
+    }
+}
+
+/// iterator of [`Tree::traverse_mut`] yielding `(depth, &mut data)`
+pub struct IterMut<'a, T> {
+    walker: Walker<T>,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (usize, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.walker.next()?;
+        Some((depth, unsafe { &mut (*node).data }))
+    }
+}
+
+impl<T> Tree<T>
+where
+    T: FromStr,
+{
+    /// initiate the tree with a special tree grammar, every label is followed (breadth first) by
+    /// the number of its children. Counts missing at the very end of the code are read as `0`.
+    /// ``` text
+    ///  ┍━━━━┑ ┍━━━━━┑
+    ///  root 3 A B C 2 D E 2 F G 0 0 0 0 1 H 0
+    ///           ┕━┿━━━━━━━┙     │
+    ///             ┕━━━━━━━━━━━━━┙
+    /// ```
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree_code = "Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower";
+    /// let tree: Tree<String> = Tree::init_tree(tree_code).unwrap();
+    /// let numbers: Tree<u32> = Tree::init_tree("1;2;2;3").unwrap();
+    /// assert_eq!(format!("{:?}", numbers), "1:2,3,\n2:\n3:\n");
+    /// ```
+    /// # Errors
+    /// the kind of the error and the index of the offending `;` separated token are reported
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::{Tree, TreeParseErrorKind};
+    /// let error = Tree::<String>::init_tree("root;2;A").unwrap_err();
+    /// assert_eq!(error.kind(), TreeParseErrorKind::Truncated);
+    /// assert_eq!(error.index(), 3);
+    /// let error = Tree::<String>::init_tree("root;x").unwrap_err();
+    /// assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::BadCount, 1));
+    /// let error = Tree::<String>::init_tree("root;1;A;0;B").unwrap_err();
+    /// assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::TrailingTokens, 4));
+    /// let error = Tree::<String>::init_tree("root;1;;0").unwrap_err();
+    /// assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::EmptyLabel, 2));
+    /// let error = Tree::<u8>::init_tree("1;1;300").unwrap_err();
+    /// assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::BadLabel, 2));
+    /// ```
+    pub fn init_tree(tree_code: &str) -> Result<Tree<T>, TreeParseError> {
+        let mut tree = Tree::new();
+        let mut tokens = tokenize(tree_code);
+        if tokens.is_empty() {
+            return Ok(tree);
+        }
+
+        // index of the token `tokens.pop_front()` returns next
+        let mut index = 0;
+        let root = Self::parse_label(tokens.pop_front(), &mut index)?;
+        tree.root = tree.allocate(root, ptr::null_mut());
+        let mut member_queue = Vector::new();
+        member_queue.push_back(tree.root);
+
+        while let Some(candidate_ptr) = member_queue.pop_front() {
+            if tokens.is_empty() {
+                break;
+            }
+            let stem_size = parse_count(tokens.pop_front(), &mut index)?;
+            for _ in 0..stem_size {
+                let node = Self::parse_label(tokens.pop_front(), &mut index)?;
+                let node_ptr = tree.allocate(node, candidate_ptr);
+                unsafe {
+                    (*candidate_ptr).stem.push_back(node_ptr);
+                }
+                member_queue.push_back(node_ptr);
+            }
+        }
+        if !tokens.is_empty() {
+            return Err(TreeParseError::new(TreeParseErrorKind::TrailingTokens, index));
+        }
+        Ok(tree)
+    }
+
+    /// depth first flavour of [`Tree::init_tree`], each label is directly followed by its child
+    /// count and then by the code of every child subtree
+    /// ``` text
+    ///  root 2 A 1 C 0 B 0
+    /// ```
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let preorder: Tree<String> = Tree::init_preorder_tree("root;2;A;1;C;0;B").unwrap();
+    /// let level_order: Tree<String> = Tree::init_tree("root;2;A;B;1;C").unwrap();
+    /// assert!(preorder == level_order);
+    /// ```
+    pub fn init_preorder_tree(tree_code: &str) -> Result<Tree<T>, TreeParseError> {
+        let mut tree = Tree::new();
+        let mut tokens = tokenize(tree_code);
+        if tokens.is_empty() {
+            return Ok(tree);
+        }
+
+        let mut index = 0;
+        let root = Self::parse_label(tokens.pop_front(), &mut index)?;
+        tree.root = tree.allocate(root, ptr::null_mut());
+        let stem_size = Self::parse_trailing_count(&mut tokens, &mut index)?;
+        // members whose children are still being read and how many are left
+        let mut member_stack = Vector::new();
+        member_stack.push_back((tree.root, stem_size));
+
+        while !member_stack.is_empty() {
+            let top = member_stack.len() - 1;
+            let (candidate_ptr, remaining) = member_stack[top];
+            if remaining == 0 {
+                member_stack.pop_back();
+                continue;
+            }
+            member_stack[top].1 -= 1;
+            let node = Self::parse_label(tokens.pop_front(), &mut index)?;
+            let node_ptr = tree.allocate(node, candidate_ptr);
+            unsafe {
+                (*candidate_ptr).stem.push_back(node_ptr);
+            }
+            let stem_size = Self::parse_trailing_count(&mut tokens, &mut index)?;
+            member_stack.push_back((node_ptr, stem_size));
+        }
+        if !tokens.is_empty() {
+            return Err(TreeParseError::new(TreeParseErrorKind::TrailingTokens, index));
+        }
+        Ok(tree)
+    }
+
+    fn parse_label(token: Option<String>, index: &mut usize) -> Result<T, TreeParseError> {
+        let label = match token {
+            None => return Err(TreeParseError::new(TreeParseErrorKind::Truncated, *index)),
+            Some(label) => label,
+        };
+        if label.is_empty() {
+            return Err(TreeParseError::new(TreeParseErrorKind::EmptyLabel, *index));
+        }
+        let label = label
+            .parse::<T>()
+            .map_err(|_| TreeParseError::new(TreeParseErrorKind::BadLabel, *index))?;
+        *index += 1;
+        Ok(label)
+    }
+
+    // a count missing at the end of the code stands for a leaf
+    fn parse_trailing_count(
+        tokens: &mut Vector<String>,
+        index: &mut usize,
+    ) -> Result<usize, TreeParseError> {
+        if tokens.is_empty() {
+            return Ok(0);
+        }
+        parse_count(tokens.pop_front(), index)
+    }
+}
+
+impl<T> Tree<T>
+where
+    T: std::fmt::Display,
+{
+    /// write the tree back in the breadth first grammar of [`Tree::init_tree`], so that parsing
+    /// the code gives the same tree. `;` and `\` inside labels are escaped with a `\` and the
+    /// counts of the trailing leaves are left out.
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree_code = "Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower";
+    /// let tree: Tree<String> = Tree::init_tree(tree_code).unwrap();
+    /// assert_eq!(tree.to_tree_code(), tree_code);
+    ///
+    /// let tree: Tree<String> = Tree::init_tree(r"a\;b;1;c\\d").unwrap();
+    /// assert_eq!(format!("{}", tree), "╚═a;b\n  ╚═c\\d\n");
+    /// assert_eq!(tree.to_tree_code(), r"a\;b;1;c\\d");
+    /// ```
+    /// # Panics
+    /// panics if a label is displayed as the empty string, which [`Tree::init_tree`] rejects
+    pub fn to_tree_code(&self) -> String {
+        let mut tokens = Vector::new();
+        if !self.root.is_null() {
+            let mut member_queue = Vector::new();
+            member_queue.push_back(self.root);
+            tokens.push_back((escape(unsafe { &(*self.root).data }), false));
+            while let Some(candidate_ptr) = member_queue.pop_front() {
+                let node = unsafe { &*candidate_ptr };
+                tokens.push_back((node.stem.len().to_string(), true));
+                for i in 0..node.stem.len() {
+                    tokens.push_back((escape(unsafe { &(*node.stem[i]).data }), false));
+                    member_queue.push_back(node.stem[i]);
+                }
+            }
+        }
+        join_tokens(tokens)
+    }
+
+    /// find a member by the labels on its path from the root joined with `/`, a `/` inside a
+    /// label is written `\/`. The first child matching a label is followed.
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> =
+    ///     Tree::init_tree("Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower").unwrap();
+    /// let conifers = tree.find_by_path("Plants/seed/Conifers").unwrap();
+    /// assert_eq!(tree.get(conifers).unwrap(), "Conifers");
+    /// assert_eq!(tree.label_path(conifers), "Plants/seed/Conifers");
+    /// assert!(tree.find_by_path("Plants/Conifers").is_none());
+    /// assert!(tree.find_by_path("Trees").is_none());
+    /// ```
+    pub fn find_by_path(&self, path: &str) -> Option<NodeId<T>> {
+        let mut labels = split_escaped(path, '/');
+        let root = unsafe { self.root.as_ref()? };
+        if labels.pop_front()? != root.data.to_string() {
+            return None;
+        }
+        let mut candidate_ptr = self.root;
+        while let Some(label) = labels.pop_front() {
+            let stem = unsafe { &(*candidate_ptr).stem };
+            candidate_ptr = (0..stem.len())
+                .map(|i| stem[i])
+                .find(|&child| unsafe { (*child).data.to_string() } == label)?;
+        }
+        Some(NodeId::of(candidate_ptr))
+    }
+
+    /// path of `node` in the format read by [`Tree::find_by_path`]
+    /// # Panics
+    /// when `node` is not a member of this tree
+    pub fn label_path(&self, node: NodeId<T>) -> String {
+        let path = self.path_to(self.expect_member(node));
+        let mut out = String::new();
+        for i in 0..path.len() {
+            if i > 0 {
+                out.push('/');
+            }
+            out.push_str(&escape_with(unsafe { &(*path[i]).data }, '/'));
+        }
+        out
+    }
+
+    /// write the tree in the depth first grammar of [`Tree::init_preorder_tree`]
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree_code = "Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower";
+    /// let tree: Tree<String> = Tree::init_tree(tree_code).unwrap();
+    /// let preorder = tree.to_preorder_code();
+    /// assert_eq!(
+    ///     preorder,
+    ///     "Plants;2;no seed;3;algae;0;Mosses;0;ferns;0;seed;2;Conifers;0;sunflower"
+    /// );
+    /// assert!(Tree::<String>::init_preorder_tree(&preorder).unwrap() == tree);
+    /// ```
+    /// # Panics
+    /// panics if a label is displayed as the empty string, like [`Tree::to_tree_code`]
+    pub fn to_preorder_code(&self) -> String {
+        let mut tokens = Vector::new();
+        let mut member_stack = Vector::new();
+        if !self.root.is_null() {
+            member_stack.push_back(self.root);
+        }
+        while let Some(candidate_ptr) = member_stack.pop_back() {
+            let node = unsafe { &*candidate_ptr };
+            tokens.push_back((escape(&node.data), false));
+            tokens.push_back((node.stem.len().to_string(), true));
+            for i in (0..node.stem.len()).rev() {
+                member_stack.push_back(node.stem[i]);
+            }
+        }
+        join_tokens(tokens)
+    }
+}
+
+impl<T> PartialEq for Tree<T>
+where
+    T: PartialEq,
+{
+    /// same shape, same labels and same order of children
+    fn eq(&self, other: &Self) -> bool {
+        let mut pairs = Vector::new();
+        pairs.push_back((self.root, other.root));
+        while let Some((one, two)) = pairs.pop_back() {
+            if one.is_null() || two.is_null() {
+                if one != two {
+                    return false;
+                }
+                continue;
+            }
+            let (one, two) = unsafe { (&*one, &*two) };
+            if one.data != two.data || one.stem.len() != two.stem.len() {
+                return false;
+            }
+            for i in 0..one.stem.len() {
+                pairs.push_back((one.stem[i], two.stem[i]));
+            }
+        }
+        true
+    }
+}
+
+impl<T> Eq for Tree<T> where T: Eq {}
+
+// draw a tree with the box glyphs, one member per line below the prefixes of its ancestors.
+// `children` lists the members right below a member and `label` writes one member, pending
+// members wait on an explicit stack so that deep trees never overflow the call stack
+pub(crate) fn render_tree<N, C, L>(root: Option<N>, mut children: C, mut label: L) -> String
+where
+    N: Copy,
+    C: FnMut(N) -> Vector<N>,
+    L: FnMut(N) -> String,
+{
+    let mut out = String::new();
+    // member, prefix drawn before its glyph and whether it is the last child
+    let mut pending = Vector::new();
+    if let Some(root) = root {
+        pending.push_back((root, String::new(), true));
+    }
+    while let Some((node, prefix, last)) = pending.pop_back() {
+        out.push_str(&prefix);
+        out.push_str(if last { CORNER } else { EDGE });
+        out.push_str(&label(node));
+        out.push('\n');
+        let below = format!("{}{}", prefix, if last { BLANK } else { LINE });
+        let below_members = children(node);
+        for i in (0..below_members.len()).rev() {
+            pending.push_back((below_members[i], below.clone(), i + 1 == below_members.len()));
+        }
+    }
+    out
+}
+
+// split the tree grammar on `;`, a `\` makes the next character part of the token
+pub(crate) fn tokenize(tree_code: &str) -> Vector<String> {
+    split_escaped(tree_code, ';')
+}
+
+fn split_escaped(code: &str, separator: char) -> Vector<String> {
+    let mut tokens = Vector::new();
+    if code.is_empty() {
+        return tokens;
+    }
+    let mut token = String::new();
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            token.push(chars.next().unwrap_or('\\'));
+        } else if c == separator {
+            tokens.push_back(mem::take(&mut token));
+        } else {
+            token.push(c);
+        }
+    }
+    tokens.push_back(token);
+    tokens
+}
+
+// the grammar reads an empty token as an error (or as a missing child of a binary tree), so an
+// empty label has no code that parses back to it
+pub(crate) fn escape<T>(label: &T) -> String
+where
+    T: std::fmt::Display,
+{
+    let escaped = escape_with(label, ';');
+    if escaped.is_empty() {
+        panic!("label is empty, the tree grammar has no way to write it");
+    }
+    escaped
+}
+
+fn escape_with<T>(label: &T, separator: char) -> String
+where
+    T: std::fmt::Display,
+{
+    let mut out = String::new();
+    for c in label.to_string().chars() {
+        if c == separator || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// join `(token, is_count)` pairs dropping the counts of the trailing leaves
+pub(crate) fn join_tokens(mut tokens: Vector<(String, bool)>) -> String {
+    while tokens.len() > 1 {
+        let last = &tokens[tokens.len() - 1];
+        if !(last.1 && last.0 == "0") {
+            break;
+        }
+        tokens.pop_back();
+    }
+    let mut out = tokens.pop_front().map(|(token, _)| token).unwrap_or_default();
+    while let Some((token, _)) = tokens.pop_front() {
+        out.push(';');
+        out.push_str(&token);
+    }
+    out
+}
+
+pub(crate) fn parse_count(token: Option<String>, index: &mut usize) -> Result<usize, TreeParseError> {
+    let count = match token {
+        None => return Err(TreeParseError::new(TreeParseErrorKind::Truncated, *index)),
+        Some(count) => count,
+    };
+    let count = count
+        .parse::<usize>()
+        .map_err(|_| TreeParseError::new(TreeParseErrorKind::BadCount, *index))?;
+    *index += 1;
+    Ok(count)
+}
+
+/// parse the tree grammar of [`Tree::init_tree`]
+/// # Example
+/// ``` rust
+/// use dsa_sport::datastruct::tree_struct::Tree;
+/// let tree: Tree<i64> = "10;2;-1;7;1;4".parse().unwrap();
+/// assert_eq!(format!("{}", tree), "╚═10\n  ╠═-1\n  ║ ╚═4\n  ╚═7\n");
+/// ```
+impl<T> FromStr for Tree<T>
+where
+    T: FromStr,
+{
+    type Err = TreeParseError;
+
+    fn from_str(tree_code: &str) -> Result<Self, Self::Err> {
+        Tree::init_tree(tree_code)
+    }
+}
+
+/// what went wrong while reading a tree grammar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeParseErrorKind {
+    /// a child count is not a non negative integer
+    BadCount,
+    /// the code ends while a count still expects children
+    Truncated,
+    /// tokens are left once every member got its children
+    TrailingTokens,
+    /// a label is the empty string
+    EmptyLabel,
+    /// a label cannot be parsed into the data type of the tree
+    BadLabel,
+    /// a member of a binary tree lists more than two children
+    TooManyChildren,
+}
+
+/// error of [`Tree::init_tree`], `index` is the position of the offending token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeParseError {
+    kind: TreeParseErrorKind,
+    index: usize,
+}
+
+impl TreeParseError {
+    pub(crate) fn new(kind: TreeParseErrorKind, index: usize) -> Self {
+        Self { kind, index }
+    }
+
+    pub fn kind(&self) -> TreeParseErrorKind {
+        self.kind
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl std::fmt::Display for TreeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.kind {
+            TreeParseErrorKind::BadCount => "child count is not a number",
+            TreeParseErrorKind::Truncated => "tree code ends before all children are listed",
+            TreeParseErrorKind::TrailingTokens => "unexpected tokens after the last member",
+            TreeParseErrorKind::EmptyLabel => "label is empty",
+            TreeParseErrorKind::BadLabel => "label cannot be parsed",
+            TreeParseErrorKind::TooManyChildren => "binary member has more than two children",
+        };
+        write!(f, "{} at token {}", reason, self.index)
+    }
+}
+
+impl std::error::Error for TreeParseError {}
+
+impl<T> Drop for Tree<T> {
+    fn drop(&mut self) {
+        if self.root.is_null() {
+            return;
+        }
+        let mut pending = Vector::new();
+        pending.push_back(self.root);
+        while let Some(node) = pending.pop_back() {
+            let mut member = unsafe { Member::deallocate_memory(node) };
+            while let Some(child) = member.stem.pop_back() {
+                pending.push_back(child);
+            }
+        }
+    }
+}
+
+impl<T> Default for Tree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> std::fmt::Display for Tree<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let root = self.root();
+        let out = render_tree(
+            root,
+            |node| {
+                let mut children = Vector::new();
+                for i in 0..self.child_count(node) {
+                    children.push_back(self.child(node, i).expect("i < count"));
+                }
+                children
+            },
+            |node| self.data(node).to_string(),
+        );
+        write!(f, "{}", out)
+    }
+}
+
+impl<T> std::fmt::Debug for Tree<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.root.is_null() {
+            return write!(f, "--");
+        }
+        let mut out = String::new();
+        for (_, member) in self.members() {
+            let node = unsafe { &*member };
+            out.push_str(&format!("{}:", node.data));
+            for i in 0..node.stem.len() {
+                out.push_str(unsafe { &format!("{},", (*node.stem[i]).data) });
+            }
+            out.push('\n');
+        }
+        write!(f, "{}", out)
+    }
+}
@@ -0,0 +1,135 @@
+//! transformations of a [`Tree`] that keep or trim its shape
+//!
+//! every walk runs on an explicit stack like the rest of the tree, so they are safe on deep
+//! trees. `map`, `fold_up` and `fold_down` give a new tree with the shape of the old one, member
+//! for member, which can be walked side by side with it.
+use super::{NodeId, Traversal, Tree};
+use crate::datastruct::vec_struct::Vector;
+use std::collections::HashMap;
+
+impl<T> Tree<T> {
+    /// tree of the same shape holding `change` of every label
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> = Tree::init_tree("Plants;2;no seed;seed").unwrap();
+    /// assert_eq!(tree.map(|label| label.len()).to_tree_code(), "6;2;7;4");
+    /// ```
+    pub fn map<U, F>(&self, mut change: F) -> Tree<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        self.rebuild(|node, _| change(self.data(node)))
+    }
+
+    /// bottom up aggregation, `combine` gets the label of a member and the results of its
+    /// children and the result of every member is kept in a tree of the same shape
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<u32> = Tree::init_tree("1;2;2;3;2;4;5").unwrap();
+    /// let sums = tree.fold_up(|label, children: &[&u32]| label + children.iter().copied().sum::<u32>());
+    /// assert_eq!(sums.to_tree_code(), "15;2;11;3;2;4;5");
+    /// ```
+    pub fn fold_up<A, F>(&self, mut combine: F) -> Tree<A>
+    where
+        F: FnMut(&T, &[&A]) -> A,
+    {
+        let mut results: HashMap<NodeId<T>, A> = HashMap::new();
+        for (_, node) in self.nodes(Traversal::PostOrder) {
+            let children: Vec<&A> = (0..self.child_count(node))
+                .map(|i| &results[&self.child(node, i).expect("i < count")])
+                .collect();
+            let result = combine(self.data(node), &children);
+            results.insert(node, result);
+        }
+        self.rebuild(|node, _| results.remove(&node).expect("every member is folded"))
+    }
+
+    /// top down pass, the context of a member is `pass` of the context of its parent and of its
+    /// label, `seed` stands for the context above the root
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let tree: Tree<String> = Tree::init_tree("Plants;2;no seed;seed;0;1;Conifers").unwrap();
+    /// let paths = tree.fold_down(String::new(), |above: &String, label| format!("{}/{}", above, label));
+    /// assert_eq!(paths.to_tree_code(), "/Plants;2;/Plants/no seed;/Plants/seed;0;1;/Plants/seed/Conifers");
+    /// ```
+    pub fn fold_down<C, F>(&self, seed: C, mut pass: F) -> Tree<C>
+    where
+        F: FnMut(&C, &T) -> C,
+    {
+        self.rebuild(|node, parent| pass(parent.unwrap_or(&seed), self.data(node)))
+    }
+
+    /// cut every subtree whose root matches `cut`, the members below a cut root are not asked
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::Tree;
+    /// let mut tree: Tree<String> =
+    ///     Tree::init_tree("Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower").unwrap();
+    /// tree.prune(|label| label.starts_with('s'));
+    /// assert_eq!(tree.to_tree_code(), "Plants;1;no seed;3;algae;Mosses;ferns");
+    /// tree.prune(|label| label == "Plants");
+    /// assert!(tree.is_empty());
+    /// ```
+    pub fn prune<P>(&mut self, mut cut: P)
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut member_stack = Vector::new();
+        if let Some(root) = self.root() {
+            member_stack.push_back(root);
+        }
+        while let Some(node) = member_stack.pop_back() {
+            if cut(self.data(node)) {
+                self.remove_subtree(node);
+                continue;
+            }
+            for i in (0..self.child_count(node)).rev() {
+                member_stack.push_back(self.child(node, i).expect("i < count"));
+            }
+        }
+    }
+
+    /// labels in the given order
+    /// # Example
+    /// ``` rust
+    /// use dsa_sport::datastruct::tree_struct::{Traversal, Tree};
+    /// let tree: Tree<char> = Tree::init_tree("a;2;b;c;1;d").unwrap();
+    /// let labels = tree.flatten(Traversal::PostOrder);
+    /// assert_eq!((0..labels.len()).map(|i| *labels[i]).collect::<String>(), "dbca");
+    /// ```
+    pub fn flatten(&self, order: Traversal) -> Vector<&T> {
+        let mut labels = Vector::new();
+        for (_, label) in self.traverse(order) {
+            labels.push_back(label);
+        }
+        labels
+    }
+
+    // tree of the same shape, `make` gets every member after its parent together with the data
+    // already made for the parent
+    fn rebuild<U, F>(&self, mut make: F) -> Tree<U>
+    where
+        F: FnMut(NodeId<T>, Option<&U>) -> U,
+    {
+        let mut tree = Tree::new();
+        let mut made: HashMap<NodeId<T>, NodeId<U>> = HashMap::new();
+        for (_, node) in self.nodes(Traversal::PreOrder) {
+            let copy = match self.parent(node) {
+                None => {
+                    let data = make(node, None);
+                    tree.set_root(data)
+                },
+                Some(parent) => {
+                    let parent = made[&parent];
+                    let data = make(node, Some(tree.data(parent)));
+                    tree.push_child(parent, data)
+                },
+            };
+            made.insert(node, copy);
+        }
+        tree
+    }
+}
@@ -0,0 +1,166 @@
+//! zipper over a [`Tree`] written `TreeZipper<T>`
+//!
+//! the zipper owns the tree split in two parts: the focus, which is the subtree under the cursor,
+//! and a stack of crumbs. Every crumb is the parent tree with a hole where the focus was cut out
+//! together with the index of the hole. Going down cuts a child out of the focus, going up puts
+//! the focus back into the hole. Neither visits the members of the subtrees, a step only shifts
+//! the siblings of the hole, so an edit costs time proportional to the path walked to reach it
+//! and the number of children along that path. The module does not contain a single `unsafe`
+//! block, it only relies on the public API of [`Tree`].
+//!
+//! # Example
+//! ```
+//! # use dsa_sport::datastruct::tree_struct::{Tree, TreeZipper};
+//! let tree: Tree<String> =
+//!     Tree::init_tree("Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower").unwrap();
+//! let mut zipper = TreeZipper::new(tree).unwrap();
+//! zipper.down(0).unwrap().down(1).unwrap().modify(|label| label.make_ascii_lowercase());
+//! zipper.right().unwrap().insert_child(0, "horsetail".to_string());
+//! zipper.up().unwrap().right().unwrap().down(0).unwrap().delete();
+//! assert_eq!(
+//!     zipper.root().to_tree_code(),
+//!     "Plants;2;no seed;seed;3;algae;mosses;ferns;1;sunflower;0;0;1;horsetail"
+//! );
+//! ```
+#![forbid(unsafe_code)]
+
+use super::Tree;
+use crate::datastruct::vec_struct::Vector;
+use core::mem;
+
+pub struct TreeZipper<T> {
+    focus: Tree<T>,
+    // parent trees with a hole and the index of the hole, the innermost crumb last
+    crumbs: Vector<(Tree<T>, usize)>,
+}
+
+impl<T> TreeZipper<T> {
+    /// focus on the root of `tree`, there is nothing to focus on in an empty tree
+    pub fn new(tree: Tree<T>) -> Option<Self> {
+        if tree.is_empty() {
+            return None;
+        }
+        Some(Self { focus: tree, crumbs: Vector::new() })
+    }
+
+    /// data of the focused member
+    pub fn focus(&self) -> &T {
+        self.focus.data(self.focus.root().expect("the focus is never empty"))
+    }
+
+    /// subtree under the cursor
+    pub fn focus_tree(&self) -> &Tree<T> {
+        &self.focus
+    }
+
+    /// number of steps between the root and the focus
+    pub fn depth(&self) -> usize {
+        self.crumbs.len()
+    }
+
+    pub fn child_count(&self) -> usize {
+        self.focus.child_count(self.focus.root().expect("the focus is never empty"))
+    }
+
+    /// move the focus to the child at `index`
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::tree_struct::{Tree, TreeZipper};
+    /// let mut zipper = TreeZipper::new(Tree::<char>::init_tree("a;2;b;c").unwrap()).unwrap();
+    /// assert!(zipper.down(2).is_none());
+    /// assert_eq!(zipper.down(1).map(|zipper| *zipper.focus()), Some('c'));
+    /// ```
+    pub fn down(&mut self, index: usize) -> Option<&mut Self> {
+        let root = self.focus.root()?;
+        let child = self.focus.child(root, index)?;
+        let child = self.focus.remove_subtree(child)?;
+        let parent = mem::replace(&mut self.focus, child);
+        self.crumbs.push_back((parent, index));
+        Some(self)
+    }
+
+    /// move the focus to the parent, `None` at the root
+    pub fn up(&mut self) -> Option<&mut Self> {
+        let (mut parent, index) = self.crumbs.pop_back()?;
+        let root = parent.root().expect("a crumb is never empty");
+        let hung = parent.insert_subtree_at(root, index, mem::take(&mut self.focus));
+        assert!(hung.is_ok(), "the focus is never empty");
+        self.focus = parent;
+        Some(self)
+    }
+
+    /// move the focus to the previous sibling
+    pub fn left(&mut self) -> Option<&mut Self> {
+        let index = self.hole()?.checked_sub(1)?;
+        self.up()?.down(index)
+    }
+
+    /// move the focus to the next sibling
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::tree_struct::{Tree, TreeZipper};
+    /// let mut zipper = TreeZipper::new(Tree::<char>::init_tree("a;2;b;c").unwrap()).unwrap();
+    /// zipper.down(0).unwrap();
+    /// assert!(zipper.left().is_none());
+    /// assert_eq!(*zipper.right().unwrap().focus(), 'c');
+    /// assert!(zipper.right().is_none());
+    /// assert_eq!(*zipper.left().unwrap().focus(), 'b');
+    /// ```
+    pub fn right(&mut self) -> Option<&mut Self> {
+        let index = self.hole()?;
+        let top = self.crumbs.len() - 1;
+        let parent = &self.crumbs[top].0;
+        // the focus is cut out of its parent, so the siblings after it start at `index`
+        if index >= parent.child_count(parent.root().expect("a crumb is never empty")) {
+            return None;
+        }
+        self.up()?.down(index + 1)
+    }
+
+    /// change the data of the focused member
+    pub fn modify<F>(&mut self, change: F) -> &mut Self
+    where
+        F: FnOnce(&mut T),
+    {
+        let root = self.focus.root().expect("the focus is never empty");
+        change(self.focus.data_mut(root));
+        self
+    }
+
+    /// add a leaf below the focus at `index`, the focus stays where it is
+    /// # Panics
+    /// when `index` is greater than the number of children
+    pub fn insert_child(&mut self, index: usize, element: T) -> &mut Self {
+        let root = self.focus.root().expect("the focus is never empty");
+        self.focus.insert_child_at(root, index, element);
+        self
+    }
+
+    /// cut the focused subtree out and move the focus to the parent, the root cannot be deleted
+    /// # Example
+    /// ```
+    /// # use dsa_sport::datastruct::tree_struct::{Tree, TreeZipper};
+    /// let mut zipper = TreeZipper::new(Tree::<char>::init_tree("a;2;b;c;1;d").unwrap()).unwrap();
+    /// assert!(zipper.delete().is_none());
+    /// let branch = zipper.down(0).unwrap().delete().unwrap();
+    /// assert_eq!(branch.to_tree_code(), "b;1;d");
+    /// assert_eq!(*zipper.focus(), 'a');
+    /// assert_eq!(zipper.root().to_tree_code(), "a;1;c");
+    /// ```
+    pub fn delete(&mut self) -> Option<Tree<T>> {
+        let (parent, _) = self.crumbs.pop_back()?;
+        Some(mem::replace(&mut self.focus, parent))
+    }
+
+    /// zip the tree back together
+    pub fn root(mut self) -> Tree<T> {
+        while self.up().is_some() {}
+        mem::take(&mut self.focus)
+    }
+
+    // index of the focus in its parent
+    fn hole(&self) -> Option<usize> {
+        let top = self.crumbs.len().checked_sub(1)?;
+        Some(self.crumbs[top].1)
+    }
+}
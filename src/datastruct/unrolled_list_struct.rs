@@ -0,0 +1,398 @@
+//! cache friendly linked list written `UnrolledList<T>`
+//!
+//! instead of one element per member, every member of the list owns a small [`Vector`] block of
+//! up to `block_capacity` elements. Walking the list touches one member per block, and the
+//! elements of a block sit next to each other in memory.
+//!
+//! A full block is split in two halves before an insertion, and a block which falls under half
+//! of its capacity after a removal borrows from, or merges with, the following block so every
+//! block except the last stays at least half full.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::unrolled_list_struct::UnrolledList;
+//! let mut list = UnrolledList::with_block_capacity(4);
+//! for i in 0..6 {
+//!     list.push_back(i);
+//! }
+//! assert_eq!(format!("{:?}", list), "⎩0,1,2,3⎭↪⎩4,5⎭↪✘");
+//! list.insert(2, 9);
+//! assert_eq!(format!("{:?}", list), "⎩0,1,9⎭↪⎩2,3⎭↪⎩4,5⎭↪✘");
+//! assert_eq!(list[3], 2);
+//! ```
+
+use crate::datastruct::vec_struct::Vector;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Index;
+use core::ops::IndexMut;
+use core::ptr;
+use std::alloc;
+
+const LEFT: &str = "⎩";
+const PHI: &str = "✘";
+const RIGHT: &str = "⎭";
+const LINK: &str = "↪";
+const SEPARATOR: &str = ",";
+
+const DEFAULT_BLOCK_CAPACITY: usize = 16;
+
+pub struct UnrolledList<T> {
+    head: *mut Member<T>,
+    tail: *mut Member<T>,
+    length: usize,
+    block_capacity: usize,
+}
+
+struct Member<T> {
+    block: Vector<T>,
+    next: *mut Member<T>,
+}
+
+impl<T> UnrolledList<T> {
+    pub fn new() -> Self {
+        Self::with_block_capacity(DEFAULT_BLOCK_CAPACITY)
+    }
+
+    /// # Panics
+    /// a block must hold at least two elements so it can be split
+    /// ```should_panic
+    /// # use dsa_sport::datastruct::unrolled_list_struct::UnrolledList;
+    /// let list: UnrolledList<u8> = UnrolledList::with_block_capacity(1);
+    /// ```
+    pub fn with_block_capacity(block_capacity: usize) -> Self {
+        if block_capacity < 2 {
+            panic!("block capacity must be at least 2");
+        }
+        Self { head: ptr::null_mut(), tail: ptr::null_mut(), length: 0, block_capacity }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// number of members (blocks) in the list
+    pub fn blocks(&self) -> usize {
+        let mut count = 0;
+        let mut current = self.head;
+        while !current.is_null() {
+            count += 1;
+            current = unsafe { (*current).next };
+        }
+        count
+    }
+
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::unrolled_list_struct::UnrolledList;
+    /// let mut list = UnrolledList::with_block_capacity(2);
+    /// list.push_back('a');
+    /// list.push_back('b');
+    /// list.push_back('c');
+    /// assert_eq!(list.blocks(), 2);
+    /// ```
+    pub fn push_back(&mut self, element: T) {
+        unsafe {
+            if self.tail.is_null() || (*self.tail).block.len() == self.block_capacity {
+                let node = Member::allocate_memory(Member::new());
+                if self.tail.is_null() {
+                    self.head = node;
+                } else {
+                    (*self.tail).next = node;
+                }
+                self.tail = node;
+            }
+            (*self.tail).block.push_back(element);
+        }
+        self.length += 1;
+    }
+
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::unrolled_list_struct::UnrolledList;
+    /// let mut list = UnrolledList::new();
+    /// list.push_back('a');
+    /// assert_eq!(list.get(0), Some(&'a'));
+    /// assert_eq!(list.get(1), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (node, offset) = self.locate(index)?;
+        unsafe { Some(&(&(*node).block)[offset]) }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let (node, offset) = self.locate(index)?;
+        unsafe { Some(&mut (&mut (*node).block)[offset]) }
+    }
+
+    /// insert an element at `index`, a full block is split in two halves first
+    /// # Panics
+    /// panics if `index > len`
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::unrolled_list_struct::UnrolledList;
+    /// let mut list = UnrolledList::with_block_capacity(2);
+    /// list.insert(0, 'b');
+    /// list.insert(0, 'a');
+    /// list.insert(2, 'd');
+    /// list.insert(2, 'c');
+    /// assert_eq!(format!("{:?}", list), "⎩'a','b'⎭↪⎩'c','d'⎭↪✘");
+    /// list.insert(1, 'x');
+    /// assert_eq!(format!("{:?}", list), "⎩'a','x'⎭↪⎩'b'⎭↪⎩'c','d'⎭↪✘");
+    /// ```
+    pub fn insert(&mut self, index: usize, element: T) {
+        if index > self.length {
+            panic!("Out of bounds access");
+        }
+        if index == self.length {
+            self.push_back(element);
+            return;
+        }
+        let (mut node, mut offset) = self.locate(index).expect("index checked against length");
+        unsafe {
+            if (*node).block.len() == self.block_capacity {
+                let half = self.block_capacity / 2;
+                let sibling = self.split(node, half);
+                if offset > half {
+                    node = sibling;
+                    offset -= half;
+                }
+            }
+            (*node).block.insert_at(offset, element);
+        }
+        self.length += 1;
+    }
+
+    /// remove the element at `index`, an under filled block borrows from or merges with the next
+    /// block
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::unrolled_list_struct::UnrolledList;
+    /// let mut list = UnrolledList::with_block_capacity(4);
+    /// for i in 0..8 {
+    ///     list.push_back(i);
+    /// }
+    /// assert_eq!(list.remove(1), Some(1));
+    /// assert_eq!(list.remove(1), Some(2));
+    /// assert_eq!(format!("{:?}", list), "⎩0,3⎭↪⎩4,5,6,7⎭↪✘");
+    /// assert_eq!(list.remove(1), Some(3));
+    /// assert_eq!(format!("{:?}", list), "⎩0,4⎭↪⎩5,6,7⎭↪✘");
+    /// assert_eq!(list.remove(1), Some(4));
+    /// assert_eq!(format!("{:?}", list), "⎩0,5,6,7⎭↪✘");
+    /// assert_eq!(list.remove(9), None);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let (node, offset) = self.locate(index)?;
+        let min_fill = self.block_capacity / 2;
+        let element = unsafe {
+            let element = (*node).block.remove_at(offset);
+            let next = (*node).next;
+            if (*node).block.len() < min_fill && !next.is_null() {
+                if (*node).block.len() + (*next).block.len() <= self.block_capacity {
+                    // merge the following block into this one
+                    while let Some(moved) = (*next).block.pop_front() {
+                        (*node).block.push_back(moved);
+                    }
+                    self.unlink_after(node);
+                } else if let Some(moved) = (*next).block.pop_front() {
+                    (*node).block.push_back(moved);
+                }
+            }
+            if (*node).block.is_empty() {
+                self.unlink(node);
+            }
+            element
+        };
+        self.length -= 1;
+        element
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.length.checked_sub(1).and_then(|index| self.remove(index))
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { node: self.head, offset: 0, marker: PhantomData }
+    }
+}
+
+// private associated functions
+impl<T> UnrolledList<T> {
+    // member holding `index` and the offset inside its block
+    fn locate(&self, mut index: usize) -> Option<(*mut Member<T>, usize)> {
+        if index >= self.length {
+            return None;
+        }
+        let mut current = self.head;
+        unsafe {
+            while index >= (*current).block.len() {
+                index -= (*current).block.len();
+                current = (*current).next;
+            }
+        }
+        Some((current, index))
+    }
+
+    // move the elements from `at` onwards into a new member linked after `node`
+    unsafe fn split(&mut self, node: *mut Member<T>, at: usize) -> *mut Member<T> {
+        let mut moved = Vector::new();
+        while (*node).block.len() > at {
+            if let Some(element) = (*node).block.pop_back() {
+                moved.push_back(element);
+            }
+        }
+        let mut sibling = Member::new();
+        while let Some(element) = moved.pop_back() {
+            sibling.block.push_back(element);
+        }
+        sibling.next = (*node).next;
+        let sibling = Member::allocate_memory(sibling);
+        (*node).next = sibling;
+        if self.tail == node {
+            self.tail = sibling;
+        }
+        sibling
+    }
+
+    unsafe fn unlink_after(&mut self, node: *mut Member<T>) {
+        let next = (*node).next;
+        (*node).next = (*next).next;
+        if self.tail == next {
+            self.tail = node;
+        }
+        Member::deallocate_memory(next);
+    }
+
+    unsafe fn unlink(&mut self, node: *mut Member<T>) {
+        if self.head == node {
+            self.head = (*node).next;
+            if self.tail == node {
+                self.tail = ptr::null_mut();
+            }
+            Member::deallocate_memory(node);
+            return;
+        }
+        let mut previous = self.head;
+        while (*previous).next != node {
+            previous = (*previous).next;
+        }
+        self.unlink_after(previous);
+    }
+}
+
+impl<T> Member<T> {
+    fn new() -> Self {
+        Self { block: Vector::new(), next: ptr::null_mut() }
+    }
+
+    fn allocate_memory(candidate: Member<T>) -> *mut Member<T> {
+        let size = mem::size_of::<Member<T>>();
+        let align = mem::align_of::<Member<T>>();
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            let ptr = alloc::alloc(layout) as *mut Member<T>;
+            ptr.write(candidate);
+            ptr
+        }
+    }
+
+    unsafe fn deallocate_memory(node: *mut Member<T>) {
+        let size = mem::size_of::<Member<T>>();
+        let align = mem::align_of::<Member<T>>();
+        let member = node.read();
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(node as *mut u8, layout);
+        drop(member);
+    }
+}
+
+pub struct Iter<'a, T> {
+    node: *mut Member<T>,
+    offset: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        unsafe {
+            while !self.node.is_null() && self.offset >= (*self.node).block.len() {
+                self.node = (*self.node).next;
+                self.offset = 0;
+            }
+            if self.node.is_null() {
+                return None;
+            }
+            let block: &'a Vector<T> = &(*self.node).block;
+            self.offset += 1;
+            Some(&block[self.offset - 1])
+        }
+    }
+}
+
+impl<T> Index<usize> for UnrolledList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+
+impl<T> IndexMut<usize> for UnrolledList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+
+impl<T> Default for UnrolledList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for UnrolledList<T> {
+    fn drop(&mut self) {
+        let mut current = self.head;
+        while !current.is_null() {
+            unsafe {
+                let node = current;
+                current = (*node).next;
+                Member::deallocate_memory(node);
+            }
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for UnrolledList<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut out = String::new();
+        let mut current = self.head;
+        while !current.is_null() {
+            let block = unsafe { &(*current).block };
+            out += LEFT;
+            for i in 0..block.len() {
+                if i > 0 {
+                    out += SEPARATOR;
+                }
+                out += &format!("{:?}", block[i]);
+            }
+            out += RIGHT;
+            out += LINK;
+            current = unsafe { (*current).next };
+        }
+        out += PHI;
+        write!(f, "{}", out)
+    }
+}
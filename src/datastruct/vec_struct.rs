@@ -234,6 +234,78 @@ impl<T> Vector<T> {
             return None;
         }
     }
+
+    /// swap the elements at two offsets
+    /// # Panics
+    /// panics if one of the offsets is out of bounds
+    /// # Examples
+    /// ```rust
+    /// use dsa_sport::datastruct::vec_struct::Vector;
+    /// let mut v: Vector<char> = Vector::new();
+    /// v.push_back('A');
+    /// v.push_back('B');
+    /// v.swap(0, 1);
+    /// assert_eq!(format!("{}", v), "⎩B⎭⎩A⎭⎩▅⎭⎩▅⎭");
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) {
+        if i >= self.length || j >= self.length {
+            panic!("Out of bounds access");
+        }
+        if let Some(front) = self.front {
+            unsafe {
+                ptr::swap(
+                    self.pointer.add((front + i) % self.capacity),
+                    self.pointer.add((front + j) % self.capacity),
+                );
+            }
+        }
+    }
+
+    /// write an element at `index` shifting every later element one place to the back, O(n)
+    /// # Panics
+    /// panics if `index > len`
+    /// # Examples
+    /// ```rust
+    /// use dsa_sport::datastruct::vec_struct::Vector;
+    /// let mut v: Vector<char> = Vector::new();
+    /// v.push_back('A');
+    /// v.push_back('C');
+    /// v.insert_at(1, 'B');
+    /// assert_eq!(format!("{}", v), "⎩A⎭⎩B⎭⎩C⎭⎩▅⎭");
+    /// ```
+    pub fn insert_at(&mut self, index: usize, element: T) {
+        if index > self.length {
+            panic!("Out of bounds access");
+        }
+        self.push_back(element);
+        let mut offset = self.length - 1;
+        while offset > index {
+            self.swap(offset - 1, offset);
+            offset -= 1;
+        }
+    }
+
+    /// read the element at `index` shifting every later element one place to the front, O(n)
+    /// # Examples
+    /// ```rust
+    /// use dsa_sport::datastruct::vec_struct::Vector;
+    /// let mut v: Vector<char> = Vector::new();
+    /// v.push_back('A');
+    /// v.push_back('B');
+    /// v.push_back('C');
+    /// assert_eq!(v.remove_at(1), Some('B'));
+    /// assert_eq!(v.remove_at(2), None);
+    /// assert_eq!(format!("{}", v), "⎩A⎭⎩C⎭⎩▅⎭⎩▅⎭");
+    /// ```
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.length {
+            return None;
+        }
+        for offset in index..self.length - 1 {
+            self.swap(offset, offset + 1);
+        }
+        self.pop_back()
+    }
 }
 
 // private associated functions
@@ -259,7 +331,7 @@ impl<T> Vector<T> {
                     out.push_str(&format!(
                         "{}{}{}",
                         LEFT,
-                        self.pointer.add(offset).read(),
+                        &*self.pointer.add(offset),
                         RIGHT
                     ));
                 }
@@ -307,8 +379,12 @@ impl<T> Vector<T> {
 }
 
 impl<T> Drop for Vector<T> {
+    // drop the live elements before the buffer, a vector that never allocated has no buffer
     fn drop(&mut self) {
-        Vector::deallocate_memory(self);
+        while self.pop_back().is_some() {}
+        if !self.pointer.is_null() {
+            Vector::deallocate_memory(self);
+        }
     }
 }
 
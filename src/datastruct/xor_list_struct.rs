@@ -0,0 +1,324 @@
+//! doubly linked list with a single link per member written `XorList<T>`
+//!
+//! like [`crate::datastruct::list_struct::LinkedList`] it spends one pointer sized field per
+//! member, yet it can be walked in both directions. Every member stores the address of its
+//! previous member xor the address of its next member, so knowing one neighbour is enough to
+//! recover the other one:
+//! ``` text
+//! next = link ^ prev        prev = link ^ next
+//! ```
+//! The list is symmetric, so reversing it only swaps the head and the tail.
+//!
+//! Addresses are turned into integers with `<*mut T>::expose_provenance` and back with
+//! [`core::ptr::with_exposed_provenance_mut`], which keeps the structure usable under Miri. Both
+//! are stable since Rust 1.84, the `rust-version` of the crate.
+//!
+//! # Examples
+//! ```
+//! # use dsa_sport::datastruct::xor_list_struct::XorList;
+//! let mut list = XorList::new();
+//! list.push_back(2);
+//! list.push_back(3);
+//! list.push_front(1);
+//! assert_eq!(format!("{:?}", list), "⎩1⎭⇄⎩2⎭⇄⎩3⎭⇄✘");
+//! list.reverse();
+//! assert_eq!(format!("{:?}", list), "⎩3⎭⇄⎩2⎭⇄⎩1⎭⇄✘");
+//! assert_eq!(list.pop_front(), Some(3));
+//! ```
+
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr;
+use std::alloc;
+
+const LEFT: &str = "⎩";
+const PHI: &str = "✘";
+const RIGHT: &str = "⎭";
+const LINK: &str = "⇄";
+
+pub struct XorList<T> {
+    head: *mut Member<T>,
+    tail: *mut Member<T>,
+    length: usize,
+}
+
+struct Member<T> {
+    data: T,
+    // address of the previous member xor address of the next member
+    link: usize,
+}
+
+impl<T> XorList<T> {
+    pub fn new() -> Self {
+        Self { head: ptr::null_mut(), tail: ptr::null_mut(), length: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::xor_list_struct::XorList;
+    /// let mut list = XorList::new();
+    /// list.push_front('b');
+    /// list.push_front('a');
+    /// assert_eq!(list.front(), Some(&'a'));
+    /// ```
+    pub fn push_front(&mut self, element: T) {
+        let (head, tail) = (self.head, self.tail);
+        let node = Self::attach(head, element);
+        self.head = node;
+        if tail.is_null() {
+            self.tail = node;
+        }
+        self.length += 1;
+    }
+
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::xor_list_struct::XorList;
+    /// let mut list = XorList::new();
+    /// list.push_back('a');
+    /// list.push_back('b');
+    /// assert_eq!(list.back(), Some(&'b'));
+    /// ```
+    pub fn push_back(&mut self, element: T) {
+        let (head, tail) = (self.head, self.tail);
+        let node = Self::attach(tail, element);
+        self.tail = node;
+        if head.is_null() {
+            self.head = node;
+        }
+        self.length += 1;
+    }
+
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::xor_list_struct::XorList;
+    /// let mut list = XorList::new();
+    /// list.push_back(1);
+    /// list.push_back(2);
+    /// assert_eq!(list.pop_front(), Some(1));
+    /// assert_eq!(list.pop_front(), Some(2));
+    /// assert_eq!(list.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.head.is_null() {
+            return None;
+        }
+        let (element, next) = unsafe { Self::detach(self.head) };
+        self.head = next;
+        if next.is_null() {
+            self.tail = ptr::null_mut();
+        }
+        self.length -= 1;
+        Some(element)
+    }
+
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::xor_list_struct::XorList;
+    /// let mut list = XorList::new();
+    /// list.push_back(1);
+    /// list.push_back(2);
+    /// assert_eq!(list.pop_back(), Some(2));
+    /// assert_eq!(list.pop_back(), Some(1));
+    /// assert_eq!(list.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.tail.is_null() {
+            return None;
+        }
+        let (element, previous) = unsafe { Self::detach(self.tail) };
+        self.tail = previous;
+        if previous.is_null() {
+            self.head = ptr::null_mut();
+        }
+        self.length -= 1;
+        Some(element)
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.head.as_ref().map(|node| &node.data) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { self.tail.as_ref().map(|node| &node.data) }
+    }
+
+    /// reverse the list in O(1), a member does not know which of its neighbours is the next one
+    /// so swapping the two ends is enough
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::xor_list_struct::XorList;
+    /// let mut list = XorList::new();
+    /// for i in 1..4 {
+    ///     list.push_back(i);
+    /// }
+    /// list.reverse();
+    /// assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    pub fn reverse(&mut self) {
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// iterator from head to tail, `rev()` walks from tail to head
+    /// # Examples
+    /// ```
+    /// # use dsa_sport::datastruct::xor_list_struct::XorList;
+    /// let mut list = XorList::new();
+    /// for i in 1..4 {
+    ///     list.push_back(i);
+    /// }
+    /// assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// let mut iter = list.iter();
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next_back(), Some(&3));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next_back(), None);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.head,
+            before_front: ptr::null_mut(),
+            back: self.tail,
+            after_back: ptr::null_mut(),
+            remaining: self.length,
+            marker: PhantomData,
+        }
+    }
+}
+
+// private associated functions
+impl<T> XorList<T> {
+    fn address(node: *mut Member<T>) -> usize {
+        node.expose_provenance()
+    }
+
+    fn member_at(address: usize) -> *mut Member<T> {
+        ptr::with_exposed_provenance_mut(address)
+    }
+
+    // neighbour of `node` on the other side of `from`
+    unsafe fn step(node: *mut Member<T>, from: *mut Member<T>) -> *mut Member<T> {
+        Self::member_at((*node).link ^ Self::address(from))
+    }
+
+    // allocate a member next to the end `end` of the list
+    fn attach(end: *mut Member<T>, element: T) -> *mut Member<T> {
+        let node = Member::allocate_memory(Member { data: element, link: Self::address(end) });
+        if !end.is_null() {
+            unsafe {
+                (*end).link ^= Self::address(node);
+            }
+        }
+        node
+    }
+
+    // free the end member `end` and return its element and its only neighbour
+    unsafe fn detach(end: *mut Member<T>) -> (T, *mut Member<T>) {
+        let neighbour = Self::member_at((*end).link);
+        if !neighbour.is_null() {
+            (*neighbour).link ^= Self::address(end);
+        }
+        (Member::deallocate_memory(end), neighbour)
+    }
+}
+
+impl<T> Member<T> {
+    fn allocate_memory(candidate: Member<T>) -> *mut Member<T> {
+        let size = mem::size_of::<Member<T>>();
+        let align = mem::align_of::<Member<T>>();
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            let ptr = alloc::alloc(layout) as *mut Member<T>;
+            ptr.write(candidate);
+            ptr
+        }
+    }
+
+    unsafe fn deallocate_memory(node: *mut Member<T>) -> T {
+        let size = mem::size_of::<Member<T>>();
+        let align = mem::align_of::<Member<T>>();
+        let member = node.read();
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(node as *mut u8, layout);
+        member.data
+    }
+}
+
+pub struct Iter<'a, T> {
+    front: *mut Member<T>,
+    before_front: *mut Member<T>,
+    back: *mut Member<T>,
+    after_back: *mut Member<T>,
+    remaining: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        unsafe {
+            let node = self.front;
+            self.front = XorList::step(node, self.before_front);
+            self.before_front = node;
+            Some(&(*node).data)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        unsafe {
+            let node = self.back;
+            self.back = XorList::step(node, self.after_back);
+            self.after_back = node;
+            Some(&(*node).data)
+        }
+    }
+}
+
+impl<T> Default for XorList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for XorList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T> std::fmt::Debug for XorList<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut out = String::new();
+        for data in self.iter() {
+            out += &format!("{}{:?}{}{}", LEFT, data, RIGHT, LINK);
+        }
+        out += PHI;
+        write!(f, "{}", out)
+    }
+}
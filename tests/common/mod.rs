@@ -0,0 +1,110 @@
+// fixtures shared by the map tests
+
+// keys 0..n in a scrambled order
+pub fn scrambled(n: u64) -> Vec<u64> {
+    (0..n).map(|i| i * 7919 % n).collect()
+}
+
+// the edge cases of `split`, `merge` and `reverse` shared by the split and merge trees, `$map`
+// is the map and `$line` its sequence flavour
+#[allow(unused_macros)]
+macro_rules! split_merge_edge_tests {
+    ($map:ident, $line:ident) => {
+        #[test]
+        fn splits_at_the_edges_and_merges_with_empty_halves() {
+            let empty: $map<u32, ()> = $map::new();
+            let (low, high) = empty.split(&5);
+            assert!(low.is_empty() && high.is_empty());
+            let map: $map<u32, ()> = (10..20).map(|key| (key, ())).collect();
+            let (low, high) = map.split(&10);
+            assert!(low.is_empty());
+            assert_eq!(high.len(), 10);
+            let (low, high) = high.split(&20);
+            assert!(high.is_empty());
+            // a key in the tree goes to the upper half
+            let (low, high) = low.merge(high).split(&15);
+            assert_eq!(low.max(), Some((&14, &())));
+            assert_eq!(high.min(), Some((&15, &())));
+            let map = $map::new().merge(high).merge($map::new()).merge(low);
+            assert!(map.check_invariants());
+            assert!(map.keys().copied().eq(10..20));
+        }
+
+        #[test]
+        fn split_halves_own_their_values() {
+            use std::rc::Rc;
+
+            let counter = Rc::new(());
+            let mut map = $map::new();
+            for key in scrambled(100) {
+                map.insert(key, Rc::clone(&counter));
+            }
+            let (low, high) = map.split(&30);
+            let (middle, high) = high.split(&70);
+            assert_eq!((low.len(), middle.len(), high.len()), (30, 40, 30));
+            drop(middle);
+            assert_eq!(Rc::strong_count(&counter), 61);
+            let map = high.merge(low);
+            assert!(map.check_invariants());
+            drop(map);
+            assert_eq!(Rc::strong_count(&counter), 1);
+        }
+
+        #[test]
+        fn implicit_reverse_edges() {
+            let mut line: $line<u8> = (0..8).collect();
+            line.reverse(3..3);
+            line.reverse(5..=5);
+            assert!(line.iter().copied().eq(0..8));
+            line.reverse(1..7);
+            line.reverse(1..7);
+            assert!(line.iter().copied().eq(0..8));
+            line.reverse(..);
+            line.reverse(..4);
+            assert_eq!(line.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6, 7, 3, 2, 1, 0]);
+            assert!(line.check_invariants());
+            let mut empty: $line<u8> = $line::new();
+            empty.reverse(..);
+            assert!(empty.is_empty());
+        }
+
+        #[test]
+        fn implicit_split_inside_a_reversed_range() {
+            let mut line: $line<u32> = (0..100).collect();
+            let mut model: Vec<u32> = (0..100).collect();
+            line.reverse(20..80);
+            model[20..80].reverse();
+            // the pending reversal has to reach both halves of the cut
+            let (low, high) = line.split(50);
+            assert!(low.iter().eq(model[..50].iter()));
+            assert!(high.iter().eq(model[50..].iter()));
+            let (mut low, mut high) = (low, high);
+            low.reverse(40..);
+            high.reverse(..10);
+            model[40..50].reverse();
+            model[50..60].reverse();
+            let line = low.merge(high);
+            assert!(line.check_invariants());
+            assert!(line.iter().eq(model.iter()));
+            for &at in [0, 100].iter() {
+                let line: $line<u32> = (0..100).collect();
+                let (low, high) = line.split(at);
+                assert_eq!((low.len(), high.len()), (at, 100 - at));
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "split index 9 beyond length 8")]
+        fn implicit_split_rejects_an_index_beyond_the_end() {
+            let line: $line<u8> = (0..8).collect();
+            line.split(9);
+        }
+
+        #[test]
+        #[should_panic(expected = "range 0..18446744073709551616 out of 0..8")]
+        fn implicit_reverse_rejects_an_overflowing_range() {
+            let mut line: $line<u8> = (0..8).collect();
+            line.reverse(..=usize::MAX);
+        }
+    };
+}
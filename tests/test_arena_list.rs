@@ -0,0 +1,93 @@
+use dsa_sport::datastruct::arena_list_struct::ArenaList;
+
+#[test]
+fn handles_survive_relinking() {
+    let mut list = ArenaList::new();
+    let handles: Vec<_> = [5, 3, 1, 4, 2].iter().map(|i| list.add_node(*i)).collect();
+    list.merge_sort();
+    assert_eq!(format!("{:?}", list), "⎩1⎭↪⎩2⎭↪⎩3⎭↪⎩4⎭↪⎩5⎭↪✘");
+    list.reverse_recursive();
+    list.rotate_from_k(2);
+    for (handle, value) in handles.iter().zip([5, 3, 1, 4, 2].iter()) {
+        assert_eq!(list.get(*handle), Some(value));
+    }
+    list.add_node(6);
+    assert_eq!(list.len(), 6);
+    assert_eq!(list.rec_len(), 6);
+}
+
+#[test]
+fn freed_slots_are_reused_and_stale_handles_rejected() {
+    let mut list = ArenaList::new();
+    let a = list.add_node('a');
+    list.add_node('b');
+    assert_eq!(list.delete_node_at_rec(0), Some('a'));
+    assert!(!list.contains(a));
+    let c = list.add_node('c');
+    assert_eq!(list.capacity(), 2);
+    assert_ne!(a, c);
+    assert_eq!(list.get(a), None);
+    assert_eq!(list.get(c), Some(&'c'));
+    assert_eq!(format!("{:?}", list), "⎩'b'⎭↪⎩'c'⎭↪✘");
+}
+
+#[test]
+fn empty_list_answers_none() {
+    let mut list: ArenaList<u8> = ArenaList::new();
+    assert!(list.is_empty());
+    assert_eq!(list.len(), 0);
+    assert_eq!(list.rec_len(), 0);
+    assert_eq!(list.take_mid(), None);
+    assert_eq!(list.get_at(0), None);
+    assert_eq!(list.handle_at(0), None);
+    assert_eq!(list.delete_node_at(0), None);
+    assert_eq!(list.delete_node_at_rec(0), None);
+    assert_eq!(list.insert_node_at(1, 7), None);
+    list.reverse_iterative();
+    list.merge_sort();
+    assert_eq!(format!("{:?}", list), "✘");
+    let only = list.add_node(7);
+    assert_eq!(list.remove(only), Some(7));
+    assert!(list.is_empty());
+    assert_eq!(format!("{:?}", list), "✘");
+    // the list has to forget its tail as well as its head
+    list.add_node(8);
+    assert_eq!(format!("{:?}", list), "⎩8⎭↪✘");
+}
+
+#[test]
+fn removing_head_and_tail_keeps_both_ends() {
+    let mut list = ArenaList::new();
+    let handles: Vec<_> = (1..=4).map(|i| list.add_node(i)).collect();
+    assert_eq!(list.remove(handles[0]), Some(1));
+    assert_eq!(list.remove(handles[3]), Some(4));
+    assert_eq!(format!("{:?}", list), "⎩2⎭↪⎩3⎭↪✘");
+    // appending after a removed tail links to the new last member
+    list.add_node(5);
+    assert_eq!(list.delete_node_at(0), Some(2));
+    assert_eq!(list.delete_node_at_rec(1), Some(5));
+    list.add_node(6);
+    list.insert_node_at(0, 0);
+    assert_eq!(format!("{:?}", list), "⎩0⎭↪⎩3⎭↪⎩6⎭↪✘");
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.rec_len(), 3);
+    assert_eq!(list.get(handles[1]), None);
+    assert_eq!(list.get(handles[2]), Some(&3));
+}
+
+#[test]
+fn removal_and_insertion_cycles_keep_the_capacity() {
+    let mut list = ArenaList::new();
+    let mut handles: Vec<_> = (0..8).map(|i| list.add_node(i)).collect();
+    for round in 0..50 {
+        let gone = handles.remove(round % handles.len());
+        assert!(list.remove(gone).is_some());
+        assert_eq!(list.get(gone), None);
+        let fresh = list.insert_node_at(round % list.len(), 100 + round);
+        handles.push(fresh.unwrap());
+        assert_ne!(Some(gone), fresh);
+        assert_eq!(list.capacity(), 8);
+    }
+    assert_eq!(list.len(), 8);
+    assert!(handles.iter().all(|&handle| list.contains(handle)));
+}
@@ -0,0 +1,173 @@
+use dsa_sport::datastruct::avl_map_struct::{AvlEvent, AvlMap, Replay, RotationCase};
+use std::collections::BTreeMap;
+
+mod common;
+use common::scrambled;
+
+#[test]
+fn every_rotation_case_is_logged() {
+    let mut cases = Vec::new();
+    for keys in [[3, 2, 1], [1, 2, 3], [3, 1, 2], [1, 3, 2]].iter() {
+        let mut map = AvlMap::new();
+        map.start_rotation_log();
+        map.extend(keys.iter().map(|&key| (key, ())));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(map.height(), 2);
+        match map.rotation_log().last() {
+            Some(AvlEvent::Rotate { case, pivot }) => cases.push((*case, *pivot)),
+            other => panic!("expected a rotation, got {:?}", other),
+        }
+    }
+    assert_eq!(
+        cases,
+        vec![
+            (RotationCase::LL, 3),
+            (RotationCase::RR, 1),
+            (RotationCase::LR, 3),
+            (RotationCase::RL, 1)
+        ]
+    );
+}
+
+#[test]
+fn random_work_matches_std() {
+    let keys = scrambled(2003);
+    let mut map = AvlMap::new();
+    let mut model = BTreeMap::new();
+    for (i, &key) in keys.iter().enumerate() {
+        assert_eq!(map.insert(key % 700, i), model.insert(key % 700, i));
+        if i % 3 == 0 {
+            let gone = keys[i / 2] % 700;
+            assert_eq!(map.remove(&gone), model.remove(&gone));
+        }
+        if i % 97 == 0 {
+            assert!(map.check_invariants());
+        }
+    }
+    assert!(map.check_invariants());
+    assert_eq!(map.len(), model.len());
+    assert!(map.iter().eq(model.iter()));
+    assert!(map.range(100..=300).rev().eq(model.range(100..=300).rev()));
+    for (_, value) in map.iter_mut() {
+        *value = 0;
+    }
+    assert!(map.values().all(|&value| value == 0));
+    while let Some((&key, _)) = map.min() {
+        map.remove(&key);
+    }
+    assert!(map.is_empty());
+    assert_eq!(map.height(), 0);
+}
+
+#[test]
+fn replay_ends_on_the_same_tree() {
+    let mut map = AvlMap::new();
+    map.start_rotation_log();
+    for key in scrambled(200) {
+        map.insert(key % 150, ());
+    }
+    for key in 40..120 {
+        map.remove(&key);
+    }
+    let log = map.stop_rotation_log();
+    assert!(map.rotation_log().is_empty());
+    let mut replay = Replay::new(&log);
+    let mut unbalanced = 0;
+    while let Some(event) = replay.step() {
+        // an insertion or removal that needs rotations leaves the tree out of balance
+        let rotation = matches!(event, AvlEvent::Rotate { .. });
+        if !rotation && !replay.tree().check_invariants() {
+            unbalanced += 1;
+        }
+    }
+    assert!(unbalanced > 0);
+    assert_eq!(replay.position(), log.len());
+    assert!(replay.tree().check_invariants());
+    assert_eq!(format!("{}", replay), format!("{}", map).replace(": ()", ""));
+}
+
+#[test]
+fn balance_factors_are_rendered() {
+    let mut map: AvlMap<u8, ()> = [2, 1, 3, 4].iter().map(|&key| (key, ())).collect();
+    assert_eq!(format!("{}", map), "╚═2: () [-1]\n  ╠═L 1: () [0]\n  ╚═R 3: () [-1]\n    ╚═R 4: () [0]\n");
+    map.insert(0, ());
+    map.insert(5, ());
+    map.insert(6, ());
+    assert_eq!(
+        format!("{}", map),
+        "╚═2: () [-1]\n  ╠═L 1: () [+1]\n  ║ ╚═L 0: () [0]\n  ╚═R 4: () [-1]\n    ╠═L 3: () [0]\n    ╚═R 5: () [-1]\n      ╚═R 6: () [0]\n"
+    );
+}
+
+#[test]
+fn an_insertion_rotates_at_most_once() {
+    let mut map = AvlMap::new();
+    map.start_rotation_log();
+    for key in scrambled(1009) {
+        map.insert(key, ());
+    }
+    for key in 0..1009 {
+        map.insert(key, ());
+    }
+    let log = map.stop_rotation_log();
+    let inserts = log.iter().filter(|event| matches!(event, AvlEvent::Insert(_))).count();
+    assert_eq!(inserts, 1009);
+    // a replaced value is no insertion and rotates nothing
+    let mut rotations_since_insert = 0;
+    for event in log.iter() {
+        match event {
+            AvlEvent::Insert(_) => rotations_since_insert = 0,
+            AvlEvent::Rotate { .. } => {
+                rotations_since_insert += 1;
+                assert_eq!(rotations_since_insert, 1, "second rotation after one insertion");
+            },
+            AvlEvent::Remove(_) => panic!("nothing was removed"),
+        }
+    }
+}
+
+#[test]
+fn a_removal_can_rotate_on_every_level() {
+    // the sparsest AVL tree of height 5 leans left on every level, taking out its rightmost
+    // key unbalances the root of every left leaning subtree on the way up
+    let mut map = AvlMap::new();
+    for key in [8, 5, 11, 3, 7, 10, 12, 2, 4, 6, 9, 1].iter() {
+        map.insert(*key, ());
+    }
+    assert_eq!(map.height(), 5);
+    map.start_rotation_log();
+    map.remove(&12);
+    let log: Vec<String> = map.rotation_log().iter().map(|event| event.to_string()).collect();
+    assert_eq!(log, ["remove 12", "LL at 11", "LL at 8"]);
+    assert!(map.check_invariants());
+    assert_eq!(map.height(), 4);
+}
+
+#[test]
+fn replay_shows_the_factor_before_a_removal_rotation() {
+    let mut full = AvlMap::new();
+    full.start_rotation_log();
+    full.extend([2, 1, 4, 3].iter().map(|&key| (key, ())));
+    full.remove(&1);
+    let mut replay = Replay::new(full.rotation_log());
+    while replay.position() < 5 {
+        replay.step();
+    }
+    assert_eq!(format!("{}", replay), "╚═2 [-2]\n  ╚═R 4 [+1]\n    ╚═L 3 [0]\n");
+    assert_eq!(replay.step(), Some(&AvlEvent::Rotate { case: RotationCase::RL, pivot: 2 }));
+    assert_eq!(format!("{}", replay), "╚═3 [0]\n  ╠═L 2 [0]\n  ╚═R 4 [0]\n");
+}
+
+#[test]
+fn clear_keeps_the_log_running() {
+    let mut map = AvlMap::new();
+    map.start_rotation_log();
+    map.extend((1..=3).map(|key| (key, ())));
+    assert_eq!(map.take_rotation_log().len(), 4);
+    map.insert(4, ());
+    map.clear();
+    assert!(map.rotation_log().is_empty());
+    map.insert(9, ());
+    assert_eq!(map.stop_rotation_log(), vec![AvlEvent::Insert(9)]);
+    assert!(map.rotation_log().is_empty());
+}
@@ -0,0 +1,182 @@
+use dsa_sport::datastruct::b_tree_map_struct::BTreeMap;
+use std::collections::BTreeMap as StdMap;
+use std::rc::Rc;
+
+mod common;
+use common::scrambled;
+
+// levels of `keys` removed from the map one at a time, checked after every removal
+fn removed<const ORDER: usize>(map: &mut BTreeMap<u32, (), ORDER>, keys: &[u32]) -> String {
+    for key in keys.iter() {
+        assert_eq!(map.remove(key), Some(()));
+        assert!(map.check_invariants(), "order {} after removing {}", ORDER, key);
+    }
+    format!("{}", map)
+}
+
+// the fewest levels holding `count` entries, every node full
+fn fewest_levels(order: usize, count: usize) -> usize {
+    let (mut levels, mut capacity) = (0, 0);
+    while capacity < count {
+        levels += 1;
+        capacity = order.pow(levels as u32) - 1;
+    }
+    levels
+}
+
+fn bulk_loading_edges<const ORDER: usize>() {
+    let mut counts = vec![0, 1, ORDER - 1, ORDER, ORDER + 1];
+    for levels in 2..=3 {
+        let full = ORDER.pow(levels) - 1;
+        counts.extend([full - 1, full, full + 1].iter().copied());
+    }
+    for &count in counts.iter() {
+        let map: BTreeMap<u32, u32, ORDER> =
+            BTreeMap::from_sorted((0..count as u32).map(|key| (key, key)));
+        assert!(map.check_invariants(), "order {} with {} entries", ORDER, count);
+        assert_eq!(map.len(), count);
+        assert_eq!(map.height(), fewest_levels(ORDER, count), "order {} with {}", ORDER, count);
+        assert!(map.iter().map(|(key, _)| *key).eq(0..count as u32));
+    }
+}
+
+fn random_work<const ORDER: usize>() {
+    let keys = scrambled(3001);
+    let mut map: BTreeMap<u64, usize, ORDER> = BTreeMap::new();
+    let mut model = StdMap::new();
+    for (i, &key) in keys.iter().enumerate() {
+        assert_eq!(map.insert(key % 1000, i), model.insert(key % 1000, i));
+        if i % 2 == 0 {
+            let gone = keys[i / 2] % 1000;
+            assert_eq!(map.remove_entry(&gone), model.remove_entry(&gone));
+        }
+        if i % 101 == 0 {
+            assert!(map.check_invariants(), "order {} after {} steps", ORDER, i);
+        }
+    }
+    assert!(map.check_invariants());
+    assert_eq!(map.len(), model.len());
+    assert!(map.iter().eq(model.iter()));
+    assert_eq!(map.min(), model.iter().next());
+    assert_eq!(map.max(), model.iter().next_back());
+    for &(low, high) in [(0, 1000), (250, 750), (500, 501), (999, 2000)].iter() {
+        assert!(map.range(low..high).eq(model.range(low..high)));
+        assert!(map.range(low..=high).eq(model.range(low..=high)));
+    }
+    for &key in keys.iter() {
+        assert_eq!(map.remove(&(key % 1000)), model.remove(&(key % 1000)));
+    }
+    assert!(map.is_empty());
+    assert_eq!(map.height(), 0);
+    assert!(map.check_invariants());
+}
+
+#[test]
+fn random_work_matches_std_for_every_order() {
+    random_work::<3>();
+    random_work::<4>();
+    random_work::<5>();
+    random_work::<16>();
+}
+
+#[test]
+fn bulk_loading_fills_valid_nodes() {
+    for count in 0..200 {
+        let map: BTreeMap<u32, u32, 3> = BTreeMap::from_sorted((0..count).map(|key| (key, key)));
+        assert!(map.check_invariants(), "{} entries", count);
+        assert!(map.keys().copied().eq(0..count));
+        let map: BTreeMap<u32, u32, 6> = BTreeMap::from_sorted((0..count).map(|key| (key, key)));
+        assert!(map.check_invariants(), "{} entries", count);
+        assert_eq!(map.len(), count as usize);
+    }
+    let mut map: BTreeMap<u32, (), 4> = BTreeMap::from_sorted((0..1000).map(|key| (key * 2, ())));
+    map.insert(501, ());
+    map.remove(&500);
+    assert!(map.check_invariants());
+    assert_eq!(map.range(498..=502).map(|(key, _)| *key).collect::<Vec<_>>(), vec![498, 501, 502]);
+}
+
+#[test]
+#[should_panic(expected = "bulk loading needs strictly increasing keys")]
+fn bulk_loading_rejects_unsorted_keys() {
+    BTreeMap::<u32, (), 3>::from_sorted(vec![(1, ()), (3, ()), (2, ())]);
+}
+
+#[test]
+fn values_change_in_place() {
+    let mut map: BTreeMap<&str, u32, 3> = BTreeMap::new();
+    for word in "one two three two one one".split(' ') {
+        match map.get_mut(&word) {
+            Some(count) => *count += 1,
+            None => {
+                map.insert(word, 1);
+            },
+        }
+    }
+    assert_eq!(format!("{:?}", map), r#"{"one": 3, "three": 1, "two": 2}"#);
+    assert!(map.contains_key(&"two"));
+    assert_eq!(map.get(&"four"), None);
+}
+
+#[test]
+fn bulk_loading_edge_sizes_use_the_fewest_levels() {
+    bulk_loading_edges::<3>();
+    bulk_loading_edges::<4>();
+    bulk_loading_edges::<5>();
+    bulk_loading_edges::<8>();
+}
+
+#[test]
+fn a_short_leaf_borrows_from_its_left_sibling() {
+    let mut map: BTreeMap<u32, (), 3> = BTreeMap::from_sorted((1..=5).map(|key| (key, ())));
+    assert_eq!(format!("{}", map), "[3]\n[1 2] [4 5]\n");
+    assert_eq!(removed(&mut map, &[4, 5]), "[2]\n[1] [3]\n");
+    let mut map: BTreeMap<u32, (), 5> = BTreeMap::from_sorted((1..=9).map(|key| (key, ())));
+    assert_eq!(format!("{}", map), "[5]\n[1 2 3 4] [6 7 8 9]\n");
+    assert_eq!(removed(&mut map, &[9, 8, 6]), "[4]\n[1 2 3] [5 7]\n");
+}
+
+#[test]
+fn a_short_leaf_borrows_from_its_right_sibling() {
+    let mut map: BTreeMap<u32, (), 4> = BTreeMap::from_sorted((1..=9).map(|key| (key, ())));
+    assert_eq!(format!("{}", map), "[4 7]\n[1 2 3] [5 6] [8 9]\n");
+    assert_eq!(removed(&mut map, &[1, 2]), "[4 7]\n[3] [5 6] [8 9]\n");
+    assert_eq!(removed(&mut map, &[3]), "[5 7]\n[4] [6] [8 9]\n");
+}
+
+#[test]
+fn a_short_inner_node_borrows_a_child_with_the_entry() {
+    let mut map: BTreeMap<u32, (), 3> = (1..=10).map(|key| (key, ())).collect();
+    assert_eq!(format!("{}", map), "[4]\n[2] [6 8]\n[1] [3] [5] [7] [9 10]\n");
+    // the leaves merge, the emptied inner node takes 6 through the root with the leaf [5]
+    assert_eq!(removed(&mut map, &[1]), "[6]\n[4] [8]\n[2 3] [5] [7] [9 10]\n");
+}
+
+#[test]
+fn merges_climb_up_and_shrink_the_root() {
+    let mut map: BTreeMap<u32, (), 3> = (1..=7).map(|key| (key, ())).collect();
+    assert_eq!(map.height(), 3);
+    assert_eq!(removed(&mut map, &[1]), "[4 6]\n[2 3] [5] [7]\n");
+    assert_eq!(map.height(), 2);
+    let mut map: BTreeMap<u32, (), 5> = BTreeMap::from_sorted((1..=5).map(|key| (key, ())));
+    assert_eq!(format!("{}", map), "[3]\n[1 2] [4 5]\n");
+    assert_eq!(removed(&mut map, &[4]), "[1 2 3 5]\n");
+    assert_eq!(removed(&mut map, &[1, 2, 3, 5]), "");
+    assert_eq!(map.height(), 0);
+}
+
+#[test]
+fn borrowed_and_merged_values_are_released_once() {
+    let counter = Rc::new(());
+    let mut map: BTreeMap<u64, Rc<()>, 3> = BTreeMap::new();
+    for key in scrambled(200) {
+        map.insert(key, Rc::clone(&counter));
+    }
+    for key in (0..200).filter(|key| key % 3 != 0) {
+        assert!(map.remove(&key).is_some());
+    }
+    assert!(map.check_invariants());
+    assert_eq!(Rc::strong_count(&counter), 1 + map.len());
+    drop(map);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
@@ -0,0 +1,127 @@
+use dsa_sport::datastruct::binary_tree_struct::{BinaryTraversal, BinaryTree, TraversalError};
+use dsa_sport::datastruct::tree_struct::TreeParseErrorKind;
+use std::cell::Cell;
+use std::str::FromStr;
+
+fn labels(tree: &BinaryTree<u32>, order: BinaryTraversal) -> Vec<u32> {
+    tree.traverse(order).map(|(_, label)| *label).collect()
+}
+
+#[test]
+fn traversal_pairs_rebuild_the_same_tree() {
+    let tree: BinaryTree<u32> = BinaryTree::init_tree("1;2;2;3;2;4;5;2;;6;1;7;1;8").unwrap();
+    let pre = labels(&tree, BinaryTraversal::PreOrder);
+    let ino = labels(&tree, BinaryTraversal::InOrder);
+    let post = labels(&tree, BinaryTraversal::PostOrder);
+    assert_eq!(BinaryTree::from_pre_in(&pre, &ino).unwrap(), tree);
+    assert_eq!(BinaryTree::from_post_in(&post, &ino).unwrap(), tree);
+    let level: Vec<Option<u32>> =
+        tree.to_level_order().iter().map(|entry| entry.copied()).collect();
+    assert_eq!(BinaryTree::from_level_order(&level).unwrap(), tree);
+    assert_eq!(tree.to_tree_code(), "1;2;2;3;2;4;5;2;;6;1;7;1;8");
+}
+
+#[test]
+fn bad_sequences_are_rejected() {
+    assert_eq!(BinaryTree::from_pre_in(&[1, 2], &[1]).unwrap_err(), TraversalError::LengthMismatch);
+    assert_eq!(
+        BinaryTree::from_post_in(&[1, 2, 3], &[1, 2, 4]).unwrap_err(),
+        TraversalError::Inconsistent
+    );
+    assert_eq!(
+        BinaryTree::from_level_order(&[None, Some(2)]).unwrap_err(),
+        TraversalError::Orphan { index: 1 }
+    );
+    assert!(BinaryTree::<u32>::from_pre_in(&[], &[]).unwrap().is_empty());
+    assert!(BinaryTree::<u32>::from_level_order(&[None, None]).unwrap().is_empty());
+}
+
+#[test]
+fn binary_grammar_errors_point_at_the_token() {
+    let error = BinaryTree::<u32>::init_tree("1;2;2").unwrap_err();
+    assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::Truncated, 3));
+    let error = BinaryTree::<u32>::init_tree(";1;2").unwrap_err();
+    assert_eq!(error.kind(), TreeParseErrorKind::EmptyLabel);
+    let error = BinaryTree::<u32>::init_tree("1;1;x").unwrap_err();
+    assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::BadLabel, 2));
+    let error = BinaryTree::<u32>::init_tree("1;2;2;3;1;4;0;0;7").unwrap_err();
+    assert_eq!(error.kind(), TreeParseErrorKind::TrailingTokens);
+}
+
+thread_local! {
+    static LIVE_LABELS: Cell<usize> = const { Cell::new(0) };
+}
+
+// a label that counts how many of its kind are alive on the test thread, `bad` does not parse
+struct Counted(String);
+
+impl FromStr for Counted {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, ()> {
+        if text == "bad" {
+            return Err(());
+        }
+        LIVE_LABELS.with(|live| live.set(live.get() + 1));
+        Ok(Counted(text.to_string()))
+    }
+}
+
+impl std::fmt::Display for Counted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        LIVE_LABELS.with(|live| live.set(live.get() - 1));
+    }
+}
+
+fn live_labels() -> usize {
+    LIVE_LABELS.with(Cell::get)
+}
+
+#[test]
+fn binary_grammar_releases_every_label() {
+    let broken = ["a;2;b;c;2;bad;d", "a;2;b;c;2", "a;2;b;c;3;d;e;f", "a;2;b;;1;d;0;e", "a;2;b;c;x"];
+    for code in broken.iter() {
+        assert!(BinaryTree::<Counted>::init_tree(code).is_err(), "{}", code);
+        assert_eq!(live_labels(), 0, "{}", code);
+    }
+    let code = "a;2;b;c;2;;d;1;e;1;f";
+    let tree: BinaryTree<Counted> = BinaryTree::init_tree(code).unwrap();
+    assert_eq!(live_labels(), 6);
+    assert_eq!(tree.to_tree_code(), code);
+    assert_eq!(live_labels(), 6);
+    drop(tree);
+    assert_eq!(live_labels(), 0);
+}
+
+#[test]
+fn mirror_and_checks() {
+    let mut tree: BinaryTree<u32> = BinaryTree::init_tree("1;2;2;3;2;4;5;2;;6").unwrap();
+    assert_eq!(labels(&tree, BinaryTraversal::InOrder), vec![4, 2, 5, 1, 3, 6]);
+    assert!(tree.is_balanced());
+    assert!(!tree.is_symmetric());
+    tree.mirror();
+    assert_eq!(labels(&tree, BinaryTraversal::InOrder), vec![6, 3, 1, 5, 2, 4]);
+    assert_eq!(format!("{}", tree), "╚═1\n  ╠═L 3\n  ║ ╚═L 6\n  ╚═R 2\n    ╠═L 5\n    ╚═R 4\n");
+    assert_eq!(format!("{:?}", tree), "1 3 2 6 ✘ 5 4");
+    assert_eq!((tree.size(), tree.height()), (6, 3));
+}
+
+#[test]
+fn deep_chain_is_walked_without_recursion() {
+    let depth = 100_000;
+    let pre: Vec<u32> = (0..depth).collect();
+    let ino: Vec<u32> = (0..depth).rev().collect();
+    let mut tree = BinaryTree::from_pre_in(&pre, &ino).unwrap();
+    assert_eq!(tree.height(), depth as usize);
+    assert!(!tree.is_balanced());
+    tree.mirror();
+    assert_eq!(labels(&tree, BinaryTraversal::InOrder), pre);
+    let code = tree.to_tree_code();
+    assert_eq!(BinaryTree::<u32>::init_tree(&code).unwrap(), tree);
+}
@@ -0,0 +1,101 @@
+use dsa_sport::datastruct::bst_map_struct::BstMap;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use std::rc::Rc;
+
+mod common;
+use common::scrambled;
+
+#[test]
+fn removal_covers_all_three_cases() {
+    let mut map: BstMap<u32, u32> =
+        [50, 30, 70, 20, 40, 60, 80, 65].iter().map(|&k| (k, k * 10)).collect();
+    // a leaf
+    assert_eq!(map.remove(&20), Some(200));
+    // one child
+    assert_eq!(map.remove(&60), Some(600));
+    assert_eq!(format!("{}", map), "╚═50: 500\n  ╠═L 30: 300\n  ║ ╚═R 40: 400\n  ╚═R 70: 700\n    ╠═L 65: 650\n    ╚═R 80: 800\n");
+    // two children, the root takes the entry of its successor
+    assert_eq!(map.remove_entry(&50), Some((50, 500)));
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![30, 40, 65, 70, 80]);
+    assert!(map.check_invariants());
+    assert_eq!(map.remove(&50), None);
+    for key in [30, 40, 65, 70, 80].iter() {
+        map.remove(key);
+        assert!(map.check_invariants());
+    }
+    assert!(map.is_empty());
+    assert_eq!(map.height(), 0);
+}
+
+#[test]
+fn ordered_queries_match_std() {
+    let keys = scrambled(1009);
+    let mut map = BstMap::new();
+    let mut model = BTreeMap::new();
+    for &key in keys.iter() {
+        if key % 3 != 0 {
+            assert_eq!(map.insert(key * 2, key), model.insert(key * 2, key));
+        }
+    }
+    assert_eq!(map.insert(4, 0), model.insert(4, 0));
+    assert_eq!(map.len(), model.len());
+    assert!(map.check_invariants());
+    assert_eq!(map.min(), model.iter().next());
+    assert_eq!(map.max(), model.iter().next_back());
+    for probe in 0..2030 {
+        assert_eq!(map.floor(&probe), model.range(..=probe).next_back());
+        assert_eq!(map.ceiling(&probe), model.range(probe..).next());
+        assert_eq!(map.predecessor(&probe), model.range(..probe).next_back());
+        assert_eq!(
+            map.successor(&probe),
+            model.range((Bound::Excluded(probe), Bound::Unbounded)).next()
+        );
+        assert_eq!(map.get(&probe), model.get(&probe));
+    }
+    for &(low, high) in [(0, 2030), (100, 101), (13, 400), (999, 1200)].iter() {
+        assert!(map.range(low..high).eq(model.range(low..high)));
+        assert!(map.range(low..=high).rev().eq(model.range(low..=high).rev()));
+    }
+    assert_eq!(map.range((Bound::Included(500), Bound::Excluded(100))).count(), 0);
+    for &key in keys.iter().take(500) {
+        assert_eq!(map.remove(&(key * 2)), model.remove(&(key * 2)));
+    }
+    assert!(map.check_invariants());
+    assert!(map.iter().eq(model.iter()));
+}
+
+#[test]
+fn values_change_in_place() {
+    let mut map: BstMap<u32, u32> = (0..10).map(|i| (i, i)).collect();
+    for (key, value) in map.iter_mut() {
+        *value += key;
+    }
+    *map.get_mut(&9).unwrap() = 0;
+    assert_eq!(map.values().copied().collect::<Vec<_>>(), vec![0, 2, 4, 6, 8, 10, 12, 14, 16, 0]);
+    assert_eq!(map.range(..2).collect::<Vec<_>>(), vec![(&0, &0), (&1, &2)]);
+}
+
+#[test]
+fn drop_releases_every_value() {
+    let counter = Rc::new(());
+    let mut map = BstMap::new();
+    for key in scrambled(100) {
+        map.insert(key, Rc::clone(&counter));
+    }
+    map.remove(&42);
+    map.insert(7, Rc::clone(&counter));
+    assert_eq!(Rc::strong_count(&counter), 100);
+    drop(map);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn sorted_input_gives_a_chain() {
+    let depth = 10_000;
+    let map: BstMap<u32, ()> = (0..depth).map(|key| (key, ())).collect();
+    assert_eq!(map.height(), depth as usize);
+    assert!(map.check_invariants());
+    assert_eq!(map.iter().next_back(), Some((&(depth - 1), &())));
+    assert_eq!(map.range(10..20).count(), 10);
+}
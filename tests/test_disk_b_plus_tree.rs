@@ -0,0 +1,235 @@
+use dsa_sport::datastruct::disk_b_plus_tree_struct::{BPlusOptions, DiskBPlusTree};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::PathBuf;
+
+// page file and log of one test, removed before it starts
+fn scratch(name: &str) -> (PathBuf, PathBuf) {
+    let path =
+        std::env::temp_dir().join(format!("dsa_sport_bpt_{}_{}.db", std::process::id(), name));
+    let wal = PathBuf::from(format!("{}.wal", path.display()));
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(&wal);
+    (path, wal)
+}
+
+fn cleanup(files: &(PathBuf, PathBuf)) {
+    let _ = fs::remove_file(&files.0);
+    let _ = fs::remove_file(&files.1);
+}
+
+fn small() -> BPlusOptions {
+    BPlusOptions::new().page_size(64).cache_pages(8).checkpoint_every(50).sync(false)
+}
+
+fn entries(tree: &mut DiskBPlusTree) -> io::Result<Vec<(u64, u64)>> {
+    tree.iter().collect()
+}
+
+#[test]
+fn random_work_matches_std_and_survives_reopening() -> io::Result<()> {
+    for &page_size in [64, 96, 256].iter() {
+        let files = scratch(&format!("random_{}", page_size));
+        let options = small().page_size(page_size);
+        let mut tree = DiskBPlusTree::open(&files.0, options.clone())?;
+        let mut model = BTreeMap::new();
+        for i in 0..3000u64 {
+            let key = i * 7919 % 1000;
+            assert_eq!(tree.insert(key, i)?, model.insert(key, i));
+            if i % 3 == 0 {
+                let gone = i * 104_729 % 1000;
+                assert_eq!(tree.remove(gone)?, model.remove(&gone));
+            }
+            if i % 250 == 0 {
+                assert!(tree.check_invariants()?, "page size {} after {} steps", page_size, i);
+            }
+        }
+        assert!(tree.check_invariants()?);
+        assert_eq!(tree.len(), model.len());
+        assert_eq!(entries(&mut tree)?, model.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>());
+        drop(tree);
+
+        let mut tree = DiskBPlusTree::open(&files.0, options)?;
+        assert!(tree.check_invariants()?);
+        assert_eq!(tree.len(), model.len());
+        for key in 0..1000 {
+            assert_eq!(tree.get(key)?, model.get(&key).copied());
+        }
+        for key in 0..1000 {
+            assert_eq!(tree.remove(key)?, model.remove(&key));
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.height()?, 1);
+        assert!(tree.check_invariants()?);
+        drop(tree);
+        cleanup(&files);
+    }
+    Ok(())
+}
+
+#[test]
+fn range_scans_follow_the_leaf_links() -> io::Result<()> {
+    let files = scratch("range");
+    let mut tree = DiskBPlusTree::open(&files.0, small())?;
+    for key in (0..500).map(|key| key * 2) {
+        tree.insert(key, key + 1)?;
+    }
+    assert!(tree.height()? > 2);
+    let model: BTreeMap<u64, u64> = (0..500).map(|key| (key * 2, key * 2 + 1)).collect();
+    for &(low, high) in [(0, 1000), (101, 401), (400, 401), (998, 2000), (1200, 1300)].iter() {
+        let scan: Vec<_> = tree.range(low..high).collect::<io::Result<_>>()?;
+        assert!(scan.iter().eq(model
+            .range(low..high)
+            .map(|(&k, &v)| (k, v))
+            .collect::<Vec<_>>()
+            .iter()));
+        let scan: Vec<_> = tree.range(low..=high).collect::<io::Result<_>>()?;
+        assert!(scan.into_iter().eq(model.range(low..=high).map(|(&k, &v)| (k, v))));
+    }
+    let tail: Vec<_> = tree.range(990..).collect::<io::Result<_>>()?;
+    assert_eq!(tail, [(990, 991), (992, 993), (994, 995), (996, 997), (998, 999)]);
+    assert_eq!(tree.range(..4).count(), 2);
+    drop(tree);
+    cleanup(&files);
+    Ok(())
+}
+
+#[test]
+fn committed_operations_survive_a_crash() -> io::Result<()> {
+    let files = scratch("crash");
+    let options = small().checkpoint_every(10_000);
+    let mut tree = DiskBPlusTree::open(&files.0, options.clone())?;
+    for key in 0..300 {
+        tree.insert(key, key * 10)?;
+    }
+    for key in (0..300).step_by(3) {
+        tree.remove(key)?;
+    }
+    // no checkpoint runs, the page file only holds what the cache evicted
+    std::mem::forget(tree);
+    assert!(fs::metadata(&files.1)?.len() > 0);
+
+    let mut tree = DiskBPlusTree::open(&files.0, options)?;
+    assert_eq!(fs::metadata(&files.1)?.len(), 16);
+    assert!(tree.check_invariants()?);
+    assert_eq!(tree.len(), 200);
+    for key in 0..300 {
+        assert_eq!(tree.get(key)?, if key % 3 == 0 { None } else { Some(key * 10) });
+    }
+    drop(tree);
+    cleanup(&files);
+    Ok(())
+}
+
+#[test]
+fn a_torn_batch_is_rolled_back() -> io::Result<()> {
+    let files = scratch("torn");
+    let options = small().checkpoint_every(10_000);
+    let mut tree = DiskBPlusTree::open(&files.0, options.clone())?;
+    for key in 0..100 {
+        tree.insert(key, key)?;
+    }
+    let logged = fs::metadata(&files.1)?.len();
+    tree.insert(1000, 1000)?;
+    std::mem::forget(tree);
+    // the last batch loses its commit record
+    let torn = fs::metadata(&files.1)?.len() - 10;
+    OpenOptions::new().write(true).open(&files.1)?.set_len(torn)?;
+    assert!(torn > logged);
+
+    let mut tree = DiskBPlusTree::open(&files.0, options)?;
+    assert!(tree.check_invariants()?);
+    assert_eq!(tree.len(), 100);
+    assert_eq!(tree.get(1000)?, None);
+    assert_eq!(tree.get(99)?, Some(99));
+    drop(tree);
+    cleanup(&files);
+    Ok(())
+}
+
+#[test]
+fn a_lone_torn_batch_is_cut_off_before_new_batches() -> io::Result<()> {
+    let files = scratch("lone_torn");
+    let options = small().checkpoint_every(10_000);
+    let mut tree = DiskBPlusTree::open(&files.0, options.clone())?;
+    tree.insert(1000, 1000)?;
+    std::mem::forget(tree);
+    let torn = fs::metadata(&files.1)?.len() - 10;
+    OpenOptions::new().write(true).open(&files.1)?.set_len(torn)?;
+
+    // nothing is committed, the torn bytes still go
+    let mut tree = DiskBPlusTree::open(&files.0, options.clone())?;
+    assert_eq!(fs::metadata(&files.1)?.len(), 16);
+    assert!(tree.is_empty());
+    for key in 10..20 {
+        tree.insert(key, key)?;
+    }
+    std::mem::forget(tree);
+
+    let mut tree = DiskBPlusTree::open(&files.0, options)?;
+    assert!(tree.check_invariants()?);
+    assert_eq!(tree.len(), 10);
+    assert_eq!(entries(&mut tree)?, (10..20).map(|key| (key, key)).collect::<Vec<_>>());
+    drop(tree);
+    cleanup(&files);
+    Ok(())
+}
+
+#[test]
+fn the_cache_evicts_the_least_recently_used_page() -> io::Result<()> {
+    let files = scratch("cache");
+    let mut tree = DiskBPlusTree::open(&files.0, small().cache_pages(12).checkpoint_every(1))?;
+    for key in 0..200 {
+        tree.insert(key, key)?;
+    }
+    assert!(tree.cache_stats().evictions > 0);
+    // once read, the root to leaf path of one key stays cached
+    tree.get(7)?;
+    let before = tree.cache_stats();
+    for _ in 0..10 {
+        tree.get(7)?;
+    }
+    let after = tree.cache_stats();
+    assert_eq!(after.misses, before.misses);
+    assert_eq!(after.hits - before.hits, 10 * tree.height()? as u64);
+    assert_eq!(after.evictions, before.evictions);
+    // a full scan reads every leaf again
+    assert_eq!(tree.iter().count(), 200);
+    assert!(tree.cache_stats().misses > after.misses);
+    drop(tree);
+    cleanup(&files);
+    Ok(())
+}
+
+#[test]
+fn freed_pages_are_reused() -> io::Result<()> {
+    let files = scratch("free");
+    let mut tree = DiskBPlusTree::open(&files.0, small())?;
+    for key in 0..400 {
+        tree.insert(key, key)?;
+    }
+    let pages = tree.page_count();
+    for key in 0..400 {
+        tree.remove(key)?;
+    }
+    assert!(tree.check_invariants()?);
+    for key in 0..400 {
+        tree.insert(key, key)?;
+    }
+    assert!(tree.check_invariants()?);
+    assert_eq!(tree.page_count(), pages);
+    drop(tree);
+    cleanup(&files);
+    Ok(())
+}
+
+#[test]
+fn a_foreign_file_is_rejected() -> io::Result<()> {
+    let files = scratch("foreign");
+    fs::write(&files.0, vec![7; 4096])?;
+    let error = DiskBPlusTree::open(&files.0, small()).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    cleanup(&files);
+    Ok(())
+}
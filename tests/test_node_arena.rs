@@ -0,0 +1,75 @@
+use dsa_sport::datastruct::node_arena_struct::NodeArena;
+use std::rc::Rc;
+
+#[test]
+fn cycle_queries_agree() {
+    let mut arena = NodeArena::new();
+    let mut ids = Vec::new();
+    for i in 0..10 {
+        ids.push(arena.add_node(i));
+    }
+    arena.set_next(ids[9], Some(ids[4]));
+    assert!(arena.has_cycle_floyd());
+    assert!(arena.has_cycle_brent());
+    assert_eq!(arena.cycle_start(), Some(ids[4]));
+    assert_eq!(arena.cycle_length(), Some(6));
+}
+
+#[test]
+fn intersection_on_shared_cycle() {
+    let mut arena = NodeArena::new();
+    let a = arena.add_node('a');
+    let b = arena.add_node('b');
+    let c = arena.add_node('c');
+    arena.set_next(c, Some(b));
+    let x = arena.add_detached('x');
+    arena.set_next(x, Some(c));
+    assert_eq!(arena.intersection(Some(a), Some(x)), Some(b));
+    let y = arena.add_detached('y');
+    assert_eq!(arena.intersection(Some(a), Some(y)), None);
+}
+
+#[test]
+fn copy_keeps_cycle_and_random() {
+    let mut arena = NodeArena::new();
+    let a = arena.add_node(1);
+    let b = arena.add_node(2);
+    let c = arena.add_node(3);
+    arena.set_next(c, Some(a));
+    arena.set_random(b, Some(a));
+    let copy = arena.copy_random_list();
+    assert_eq!(format!("{:?}", copy), "⎩1⎭↪⎩2⎭↪⎩3⎭↪⟲⎩1⎭");
+    assert_eq!(copy.random(1), Some(0));
+    assert_eq!(copy.cycle_length(), Some(3));
+}
+
+#[test]
+fn copy_numbers_members_in_visit_order() {
+    let mut arena = NodeArena::new();
+    let a = arena.add_node('a');
+    arena.add_node('b');
+    arena.add_node('c');
+    let d = arena.add_node('d');
+    arena.set_random(a, Some(d));
+    let copy = arena.copy_random_list();
+    // the random target of the head is copied before the rest of the chain
+    assert_eq!(copy.get(2), Some(&'d'));
+    assert_eq!(copy.random(0), Some(2));
+    assert_eq!(format!("{:?}", copy), "⎩'a'⎭↪⎩'b'⎭↪⎩'c'⎭↪⎩'d'⎭↪✘");
+}
+
+#[test]
+fn drop_releases_every_value() {
+    let counter = Rc::new(());
+    let mut arena = NodeArena::new();
+    for _ in 0..4 {
+        arena.add_node(Rc::clone(&counter));
+    }
+    arena.add_detached(Rc::clone(&counter));
+    let copy = arena.copy_random_list();
+    assert_eq!(Rc::strong_count(&counter), 10);
+    drop(arena);
+    assert_eq!(Rc::strong_count(&counter), 5);
+    drop(copy);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
@@ -0,0 +1,163 @@
+use dsa_sport::datastruct::order_stat_tree_struct::OrderStatTree;
+
+mod common;
+use common::scrambled;
+
+// sorted vector standing in for the tree
+fn rank(model: &[u64], key: u64) -> usize {
+    model.partition_point(|&k| k < key)
+}
+
+fn random_work(multiset: bool) {
+    let keys = scrambled(3001);
+    let mut tree = if multiset { OrderStatTree::new_multiset() } else { OrderStatTree::new() };
+    let mut model: Vec<u64> = Vec::new();
+    for (i, &key) in keys.iter().enumerate() {
+        let key = key % 500;
+        let at = rank(&model, key);
+        let fresh = multiset || model.get(at) != Some(&key);
+        assert_eq!(tree.insert(key), fresh);
+        if fresh {
+            model.insert(at, key);
+        }
+        if i % 3 == 0 {
+            let gone = keys[i / 3] % 500;
+            let at = rank(&model, gone);
+            let present = model.get(at) == Some(&gone);
+            assert_eq!(tree.remove(&gone), present);
+            if present {
+                model.remove(at);
+            }
+        }
+        if i % 101 == 0 {
+            assert!(tree.check_invariants(), "after {} steps", i);
+        }
+    }
+    assert!(tree.check_invariants());
+    assert_eq!(tree.len(), model.len());
+    assert!(tree.iter().eq(model.iter()));
+    for (index, key) in model.iter().enumerate() {
+        assert_eq!(tree.select(index), Some(key));
+    }
+    assert_eq!(tree.select(model.len()), None);
+    for key in 0..=500 {
+        assert_eq!(tree.rank(&key), rank(&model, key));
+        assert_eq!(tree.count(&key), rank(&model, key + 1) - rank(&model, key));
+    }
+    for &(low, high) in [(0, 500), (100, 400), (250, 251), (300, 200), (499, 1000)].iter() {
+        let inside = model.iter().filter(|&&key| low <= key && key < high).count();
+        assert_eq!(tree.count_range(low..high), inside);
+        let inside = model.iter().filter(|&&key| low < key && key <= high).count();
+        assert_eq!(
+            tree.count_range((std::ops::Bound::Excluded(low), std::ops::Bound::Included(high))),
+            inside
+        );
+    }
+    assert_eq!(tree.count_range(..), model.len());
+    assert_eq!(tree.min(), model.first());
+    assert_eq!(tree.max(), model.last());
+}
+
+#[test]
+fn random_set_work_matches_a_sorted_vec() {
+    random_work(false);
+}
+
+#[test]
+fn random_multiset_work_matches_a_sorted_vec() {
+    random_work(true);
+}
+
+#[test]
+fn copies_are_counted_and_removed() {
+    let mut tree = OrderStatTree::new_multiset();
+    tree.extend("mississippi".chars());
+    assert!(tree.is_multiset());
+    assert_eq!(tree.iter().collect::<String>(), "iiiimppssss");
+    assert_eq!(tree.count(&'s'), 4);
+    assert_eq!(tree.rank(&'p'), 5);
+    assert_eq!(tree.select(6), Some(&'p'));
+    assert_eq!(tree.count_range('j'..'q'), 3);
+    assert!(tree.remove(&'s'));
+    assert_eq!(tree.remove_all(&'i'), 4);
+    assert_eq!(tree.remove_all(&'i'), 0);
+    assert_eq!(format!("{:?}", tree), "['m', 'p', 'p', 's', 's', 's']");
+    assert!(tree.check_invariants());
+
+    let set: OrderStatTree<char> = "mississippi".chars().collect();
+    assert_eq!(set.iter().collect::<String>(), "imps");
+    assert_eq!(set.count(&'s'), 1);
+}
+
+#[test]
+fn rank_and_select_on_an_empty_tree() {
+    let tree: OrderStatTree<u32> = OrderStatTree::new_multiset();
+    assert_eq!(tree.rank(&7), 0);
+    assert_eq!(tree.select(0), None);
+    assert_eq!(tree.count_range(..), 0);
+}
+
+#[test]
+fn select_steps_through_every_copy() {
+    let mut tree = OrderStatTree::new_multiset();
+    // three copies of every key, inserted so that members rotate while holding copies
+    for _ in 0..3 {
+        for key in scrambled(50) {
+            tree.insert(key * 10);
+        }
+    }
+    assert_eq!(tree.len(), 150);
+    assert!(tree.check_invariants());
+    for index in 0..150 {
+        let key = tree.select(index).copied().unwrap();
+        assert_eq!(key, index as u64 / 3 * 10);
+        // the rank of a key is the position of its first copy
+        assert_eq!(tree.rank(&key), index / 3 * 3);
+        assert_eq!(tree.count(&key), 3);
+    }
+    assert_eq!(tree.select(150), None);
+    // a key between two members ranks after every copy below it
+    assert_eq!(tree.rank(&15), 6);
+    assert_eq!(tree.rank(&1000), 150);
+}
+
+#[test]
+fn removing_a_copy_shifts_the_later_positions() {
+    let mut tree = OrderStatTree::new_multiset();
+    tree.extend([5, 1, 5, 9, 5, 3, 9].iter().copied());
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), vec![1, 3, 5, 5, 5, 9, 9]);
+    assert_eq!((tree.rank(&5), tree.rank(&9)), (2, 5));
+    assert!(tree.remove(&5));
+    assert_eq!((tree.rank(&5), tree.rank(&9)), (2, 4));
+    assert_eq!(tree.select(3), Some(&5));
+    assert_eq!(tree.select(4), Some(&9));
+    assert_eq!(tree.remove_all(&5), 2);
+    assert_eq!((tree.rank(&5), tree.rank(&9)), (2, 2));
+    assert_eq!(tree.select(2), Some(&9));
+    assert!(tree.check_invariants());
+}
+
+#[test]
+fn range_bounds_include_or_exclude_every_copy() {
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+    let mut tree = OrderStatTree::new_multiset();
+    tree.extend([2, 4, 4, 4, 6, 6].iter().copied());
+    assert_eq!(tree.count_range(4..=4), 3);
+    assert_eq!(tree.count_range(4..6), 3);
+    assert_eq!(tree.count_range(..4), 1);
+    assert_eq!(tree.count_range((Excluded(4), Unbounded)), 2);
+    assert_eq!(tree.count_range((Excluded(4), Included(6))), 2);
+    assert_eq!(tree.count_range((Excluded(4), Excluded(6))), 0);
+    assert_eq!(tree.count_range((Included(6), Excluded(2))), 0);
+}
+
+#[test]
+fn the_set_mode_keeps_one_copy_in_ranks() {
+    let mut tree = OrderStatTree::new();
+    tree.extend([4, 4, 2, 4].iter().copied());
+    assert_eq!(tree.len(), 2);
+    assert_eq!(tree.rank(&5), 2);
+    assert_eq!(tree.select(1), Some(&4));
+    assert_eq!(tree.select(2), None);
+    assert_eq!(tree.count(&4), 1);
+}
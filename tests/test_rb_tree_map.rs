@@ -0,0 +1,136 @@
+use dsa_sport::datastruct::rb_tree_map_struct::{Entry, RbTreeMap};
+use std::collections::BTreeMap;
+
+mod common;
+use common::scrambled;
+
+// map of `keys` in insertion order with `gone` removed, checked after every removal
+fn removed(keys: &[u8], gone: &[u8]) -> RbTreeMap<u8, ()> {
+    let mut map: RbTreeMap<u8, ()> = keys.iter().map(|&key| (key, ())).collect();
+    for key in gone.iter() {
+        assert_eq!(map.remove(key), Some(()));
+        assert!(map.check_invariants().is_ok(), "after removing {}", key);
+    }
+    map
+}
+
+#[test]
+fn random_work_keeps_the_invariants() {
+    let keys = scrambled(3001);
+    let mut map = RbTreeMap::new();
+    let mut model = BTreeMap::new();
+    for (i, &key) in keys.iter().enumerate() {
+        assert_eq!(map.insert(key % 1000, i), model.insert(key % 1000, i));
+        if i % 2 == 0 {
+            let gone = keys[i / 2] % 1000;
+            assert_eq!(map.remove_entry(&gone), model.remove_entry(&gone));
+        }
+        if i % 101 == 0 {
+            assert!(map.check_invariants().is_ok());
+        }
+    }
+    assert!(map.check_invariants().is_ok());
+    assert_eq!(map.len(), model.len());
+    assert!(map.iter().eq(model.iter()));
+    assert!(map.range(250..750).rev().eq(model.range(250..750).rev()));
+    for &key in keys.iter() {
+        map.remove(&(key % 1000));
+        if key % 50 == 0 {
+            assert!(map.check_invariants().is_ok());
+        }
+    }
+    assert!(map.is_empty());
+    assert_eq!(map.check_invariants(), Ok(0));
+}
+
+#[test]
+fn entries_look_once() {
+    let mut map: RbTreeMap<char, usize> = RbTreeMap::new();
+    for (i, c) in "abracadabra".chars().enumerate() {
+        match map.entry(c) {
+            Entry::Occupied(mut entry) => {
+                *entry.get_mut() += 1;
+            },
+            Entry::Vacant(entry) => {
+                assert_eq!(*entry.key(), c);
+                assert_eq!(*entry.insert(1), 1);
+                assert!(i < 7);
+            },
+        }
+    }
+    assert_eq!(map.iter().map(|(c, n)| format!("{}{}", c, n)).collect::<String>(), "a5b2c1d1r2");
+    if let Entry::Occupied(mut entry) = map.entry('b') {
+        assert_eq!(entry.insert(7), 2);
+        assert_eq!(entry.remove_entry(), ('b', 7));
+    }
+    assert_eq!(map.entry('z').key(), &'z');
+    assert_eq!(*map.entry('a').and_modify(|n| *n *= 2).or_insert(0), 10);
+    assert_eq!(map.len(), 4);
+    assert!(map.check_invariants().is_ok());
+}
+
+#[test]
+fn colours_are_rendered() {
+    let map: RbTreeMap<u8, ()> = (1..=5).map(|key| (key, ())).collect();
+    assert_eq!(
+        format!("{:?}", map),
+        "╚═2: () (B)\n  ╠═L 1: () (B)\n  ╚═R 4: () (B)\n    ╠═L 3: () (R)\n    ╚═R 5: () (R)\n"
+    );
+    assert_eq!(
+        format!("{}", map),
+        "╚═2: ()\n  ╠═L 1: ()\n  ╚═R 4: ()\n    ╠═L \u{1b}[31m3: ()\u{1b}[0m\n    ╚═R \u{1b}[31m5: ()\u{1b}[0m\n"
+    );
+    assert_eq!(format!("{}", RbTreeMap::<u8, ()>::new()), "");
+}
+
+#[test]
+fn removing_a_red_member_needs_no_fixup() {
+    let map = removed(&[2, 1, 3, 4], &[4]);
+    assert_eq!(format!("{:?}", map), "╚═2: () (B)\n  ╠═L 1: () (B)\n  ╚═R 3: () (B)\n");
+    assert_eq!(map.check_invariants(), Ok(2));
+}
+
+#[test]
+fn a_red_child_takes_the_place_of_its_black_parent() {
+    let map = removed(&[2, 1, 3, 4], &[3]);
+    assert_eq!(format!("{:?}", map), "╚═2: () (B)\n  ╠═L 1: () (B)\n  ╚═R 4: () (B)\n");
+    assert_eq!(map.check_invariants(), Ok(2));
+}
+
+#[test]
+fn a_black_sibling_with_black_children_turns_red() {
+    // the missing black climbs to the root, so every path loses one
+    let map = removed(&[2, 1, 3, 4], &[4, 1]);
+    assert_eq!(format!("{:?}", map), "╚═2: () (B)\n  ╚═R 3: () (R)\n");
+    assert_eq!(map.check_invariants(), Ok(1));
+}
+
+#[test]
+fn a_red_far_nephew_settles_with_one_rotation() {
+    let map = removed(&[2, 1, 3, 4], &[1]);
+    assert_eq!(format!("{:?}", map), "╚═3: () (B)\n  ╠═L 2: () (B)\n  ╚═R 4: () (B)\n");
+    assert_eq!(map.check_invariants(), Ok(2));
+}
+
+#[test]
+fn a_red_near_nephew_is_turned_outward_first() {
+    let map = removed(&[2, 1, 4, 3], &[1]);
+    assert_eq!(format!("{:?}", map), "╚═3: () (B)\n  ╠═L 2: () (B)\n  ╚═R 4: () (B)\n");
+    assert_eq!(map.check_invariants(), Ok(2));
+}
+
+#[test]
+fn a_red_sibling_is_rotated_above_the_parent() {
+    let map = removed(&[1, 2, 3, 4, 5, 6], &[]);
+    assert_eq!(
+        format!("{:?}", map),
+        "╚═2: () (B)\n  ╠═L 1: () (B)\n  ╚═R 4: () (R)\n    ╠═L 3: () (B)\n    ╚═R 5: () (B)\n      ╚═R 6: () (R)\n"
+    );
+    // the red sibling 4 goes up, then its black child 3 turns red below the now red parent 2
+    let map = removed(&[1, 2, 3, 4, 5, 6], &[1]);
+    assert_eq!(
+        format!("{:?}", map),
+        "╚═4: () (B)\n  ╠═L 2: () (B)\n  ║ ╚═R 3: () (R)\n  ╚═R 5: () (B)\n    ╚═R 6: () (R)\n"
+    );
+    assert_eq!(map.check_invariants(), Ok(2));
+}
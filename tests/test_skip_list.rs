@@ -0,0 +1,113 @@
+use dsa_sport::datastruct::skip_list_struct::{SkipList, SkipSet};
+use std::collections::BTreeMap;
+
+#[test]
+fn skip_list_matches_btree_map() {
+    for &(probability, seed) in [(0.5, 1), (0.25, 2), (0.75, 3)].iter() {
+        let mut list = SkipList::with_probability(probability, seed);
+        let mut model = BTreeMap::new();
+        let mut x: u64 = seed;
+        for step in 0..2000u64 {
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let key = (x >> 33) % 200;
+            if step % 3 == 0 {
+                assert_eq!(list.remove(&key), model.remove(&key));
+            } else {
+                assert_eq!(list.insert(key, step), model.insert(key, step));
+            }
+        }
+        assert_eq!(list.len(), model.len());
+        let expected: Vec<_> = model.iter().collect();
+        assert_eq!(list.iter().collect::<Vec<_>>(), expected);
+        for (index, (key, value)) in model.iter().enumerate() {
+            assert_eq!(list.select(index), Some((key, value)));
+            assert_eq!(list.rank(key), index);
+        }
+        let expected: Vec<_> = model.range(50..=120).collect();
+        assert_eq!(list.range(50..=120).collect::<Vec<_>>(), expected);
+    }
+}
+
+#[test]
+fn same_seed_same_lanes() {
+    let mut one = SkipSet::with_probability(0.5, 42);
+    let mut two = SkipSet::with_probability(0.5, 42);
+    for k in 0..20 {
+        one.insert(k);
+        two.insert(k);
+    }
+    let lanes = format!("{:?}", one);
+    assert_eq!(lanes, format!("{:?}", two));
+    let bottom = lanes.lines().last().unwrap();
+    assert!(bottom.starts_with("⎩0⎭↪⎩1⎭↪"));
+    assert!(lanes.lines().count() > 1);
+    for lane in lanes.lines() {
+        assert_eq!(lane.chars().count(), bottom.chars().count());
+    }
+}
+
+#[test]
+fn duplicate_insert_replaces_the_value() {
+    let mut list = SkipList::with_seed(7);
+    assert_eq!(list.insert(3, 'a'), None);
+    assert_eq!(list.insert(3, 'b'), Some('a'));
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.get(&3), Some(&'b'));
+    let mut set = SkipSet::with_probability(0.5, 7);
+    assert!(set.insert(3));
+    assert!(!set.insert(3));
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.select(1), None);
+}
+
+#[test]
+fn removing_a_missing_key_changes_nothing() {
+    let mut list = SkipList::with_seed(9);
+    assert_eq!(list.remove(&1), None);
+    for key in [2, 4, 6].iter() {
+        list.insert(*key, *key * 10);
+    }
+    let lanes = format!("{:?}", list);
+    for key in [0, 3, 7].iter() {
+        assert_eq!(list.remove(key), None);
+    }
+    assert_eq!(list.len(), 3);
+    assert_eq!(format!("{:?}", list), lanes);
+    assert_eq!(list.remove(&4), Some(40));
+    assert_eq!(list.remove(&4), None);
+    assert!(!list.contains_key(&4));
+    assert_eq!(list.rank(&6), 1);
+}
+
+#[test]
+fn range_bounds_are_honoured() {
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+    let list: SkipList<u32, ()> = {
+        let mut list = SkipList::with_seed(3);
+        for key in (0..20).map(|key| key * 2) {
+            list.insert(key, ());
+        }
+        list
+    };
+    let keys = |range: (std::ops::Bound<u32>, std::ops::Bound<u32>)| {
+        list.range(range).map(|(key, _)| *key).collect::<Vec<_>>()
+    };
+    assert_eq!(keys((Included(4), Excluded(10))), [4, 6, 8]);
+    assert_eq!(keys((Excluded(4), Included(10))), [6, 8, 10]);
+    assert_eq!(keys((Included(5), Included(9))), [6, 8]);
+    assert_eq!(keys((Unbounded, Excluded(4))), [0, 2]);
+    assert_eq!(keys((Excluded(34), Unbounded)), [36, 38]);
+    assert_eq!(keys((Included(39), Unbounded)), Vec::<u32>::new());
+    assert_eq!(keys((Excluded(6), Excluded(8))), Vec::<u32>::new());
+    assert_eq!(keys((Included(8), Excluded(8))), Vec::<u32>::new());
+    assert_eq!(list.range(..).count(), 20);
+    let set = {
+        let mut set = SkipSet::new();
+        for key in 0..10 {
+            set.insert(key);
+        }
+        set
+    };
+    assert_eq!(set.range(7..).copied().collect::<Vec<_>>(), [7, 8, 9]);
+    assert_eq!(set.range(..=1).copied().collect::<Vec<_>>(), [0, 1]);
+}
@@ -0,0 +1,129 @@
+use dsa_sport::datastruct::splay_tree_struct::{ImplicitSplayTree, SplayTree};
+use std::collections::BTreeMap;
+
+#[macro_use]
+mod common;
+use common::scrambled;
+
+#[test]
+fn random_work_matches_std() {
+    let keys = scrambled(2003);
+    let mut map = SplayTree::new();
+    let mut model = BTreeMap::new();
+    for (i, &key) in keys.iter().enumerate() {
+        assert_eq!(map.insert(key % 700, i), model.insert(key % 700, i));
+        assert_eq!(map.root().map(|(key, _)| *key), Some(key % 700));
+        if i % 2 == 0 {
+            let gone = keys[i / 2] % 700;
+            assert_eq!(map.remove_entry(&gone), model.remove_entry(&gone));
+        }
+        if i % 3 == 0 {
+            let probe = keys[i / 3] % 800;
+            assert_eq!(map.get(&probe), model.get(&probe));
+        }
+        if i % 101 == 0 {
+            assert!(map.check_invariants(), "after {} steps", i);
+        }
+    }
+    assert!(map.check_invariants());
+    assert_eq!(map.len(), model.len());
+    assert!(map.iter().eq(model.iter()));
+    assert_eq!(map.min(), model.iter().next());
+    assert_eq!(map.max(), model.iter().next_back());
+}
+
+#[test]
+fn a_lookup_splays_to_the_root() {
+    // sorted insertions leave a path
+    let mut map: SplayTree<u32, ()> = (0..64).map(|key| (key, ())).collect();
+    assert_eq!(map.height(), 64);
+    assert!(map.contains_key(&0));
+    assert_eq!(map.root(), Some((&0, &())));
+    // zig-zig steps roughly halve the depth of the path
+    assert!(map.height() <= 34, "height {}", map.height());
+    assert!(!map.contains_key(&100));
+    assert_eq!(map.root(), Some((&63, &())));
+    assert!(map.check_invariants());
+}
+
+#[test]
+fn sequential_access_stays_cheap() {
+    let mut map: SplayTree<u32, u32> = (0..50_000).map(|key| (key, key)).collect();
+    for key in 0..50_000 {
+        assert_eq!(map.get(&key), Some(&key));
+    }
+    assert!(map.check_invariants());
+}
+
+#[test]
+fn split_and_merge_round_trip() {
+    for &at in [0, 1, 301, 998, 999, 2000].iter() {
+        let map: SplayTree<u32, u32> =
+            scrambled(500).into_iter().map(|k| (k as u32 * 2, 0)).collect();
+        let (low, high) = map.split(&at);
+        assert!(low.check_invariants() && high.check_invariants());
+        assert!(low.keys().all(|&key| key < at));
+        assert!(high.keys().all(|&key| key >= at));
+        assert_eq!(low.len() + high.len(), 500);
+        let map = if at % 2 == 0 { low.merge(high) } else { high.merge(low) };
+        assert!(map.check_invariants());
+        assert!(map.keys().copied().eq((0..500).map(|key| key * 2)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "the keys of the two splay trees interleave")]
+fn merge_rejects_interleaved_keys() {
+    let odd: SplayTree<u32, ()> = [1, 3].iter().map(|&key| (key, ())).collect();
+    let even: SplayTree<u32, ()> = [2, 4].iter().map(|&key| (key, ())).collect();
+    odd.merge(even);
+}
+
+#[test]
+fn implicit_work_matches_vec() {
+    let mut line = ImplicitSplayTree::new();
+    let mut model = Vec::new();
+    for (i, key) in scrambled(1009).into_iter().enumerate() {
+        let at = key as usize % (model.len() + 1);
+        line.insert(at, i);
+        model.insert(at, i);
+        if i % 3 == 1 {
+            let gone = (key as usize * 31) % model.len();
+            assert_eq!(line.remove(gone), Some(model.remove(gone)));
+        }
+        if i % 5 == 0 {
+            let (a, b) = (key as usize % model.len(), (key as usize * 17) % model.len());
+            let (start, end) = (a.min(b), a.max(b));
+            line.reverse(start..=end);
+            model[start..=end].reverse();
+        }
+        if i % 7 == 0 {
+            let probe = (key as usize * 13) % model.len();
+            assert_eq!(line.get(probe), model.get(probe));
+        }
+        if i % 97 == 0 {
+            assert!(line.check_invariants());
+            assert!(line.iter().eq(model.iter()), "after {} steps", i);
+        }
+    }
+    assert_eq!(line.len(), model.len());
+    assert!(line.iter().eq(model.iter()));
+    assert_eq!(line.get(model.len()), None);
+    assert_eq!(line.remove(model.len()), None);
+}
+
+#[test]
+fn implicit_split_and_merge_cut_and_concatenate() {
+    let mut line: ImplicitSplayTree<char> = "abcdefgh".chars().collect();
+    line.reverse(2..);
+    let (low, high) = line.split(5);
+    assert_eq!(format!("{:?}", low), "['a', 'b', 'h', 'g', 'f']");
+    assert_eq!(high.iter().collect::<String>(), "edc");
+    let mut line = high.merge(low);
+    *line.get_mut(7).unwrap() = 'F';
+    line.insert(3, '|');
+    assert_eq!(line.iter().collect::<String>(), "edc|abhgF");
+    assert!(line.check_invariants());
+}
+
+split_merge_edge_tests!(SplayTree, ImplicitSplayTree);
@@ -0,0 +1,119 @@
+use dsa_sport::datastruct::treap_struct::{ImplicitTreap, Treap};
+use std::collections::BTreeMap;
+
+#[macro_use]
+mod common;
+use common::scrambled;
+
+#[test]
+fn random_work_matches_std() {
+    let keys = scrambled(2003);
+    let mut map = Treap::with_seed(11);
+    let mut model = BTreeMap::new();
+    for (i, &key) in keys.iter().enumerate() {
+        assert_eq!(map.insert(key % 700, i), model.insert(key % 700, i));
+        if i % 2 == 0 {
+            let gone = keys[i / 2] % 700;
+            assert_eq!(map.remove_entry(&gone), model.remove_entry(&gone));
+        }
+        if i % 101 == 0 {
+            assert!(map.check_invariants(), "after {} steps", i);
+        }
+    }
+    assert!(map.check_invariants());
+    assert_eq!(map.len(), model.len());
+    assert!(map.iter().eq(model.iter()));
+    assert_eq!(map.min(), model.iter().next());
+    assert_eq!(map.max(), model.iter().next_back());
+}
+
+#[test]
+fn a_seed_builds_the_same_tree() {
+    let build = |seed| {
+        let mut map = Treap::with_seed(seed);
+        map.extend((0..20).map(|key| (key, ())));
+        format!("{}", map)
+    };
+    assert_eq!(build(3), build(3));
+    assert_ne!(build(3), build(4));
+}
+
+#[test]
+fn split_and_merge_round_trip() {
+    let map: Treap<u32, u32> = (0..500).map(|key| (key * 2, key)).collect();
+    for &at in [0, 1, 301, 998, 999, 2000].iter() {
+        let map: Treap<u32, u32> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        let (low, high) = map.split(&at);
+        assert!(low.check_invariants() && high.check_invariants());
+        assert!(low.keys().all(|&key| key < at));
+        assert!(high.keys().all(|&key| key >= at));
+        assert_eq!(low.len() + high.len(), 500);
+        let map = if at % 2 == 0 { low.merge(high) } else { high.merge(low) };
+        assert!(map.check_invariants());
+        assert!(map.keys().copied().eq((0..500).map(|key| key * 2)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "the keys of the two treaps interleave")]
+fn merge_rejects_shared_keys() {
+    let low: Treap<u32, ()> = (0..5).map(|key| (key, ())).collect();
+    let high: Treap<u32, ()> = (4..9).map(|key| (key, ())).collect();
+    low.merge(high);
+}
+
+#[test]
+fn implicit_work_matches_vec() {
+    let mut line = ImplicitTreap::with_seed(5);
+    let mut model = Vec::new();
+    for (i, key) in scrambled(1009).into_iter().enumerate() {
+        let at = key as usize % (model.len() + 1);
+        line.insert(at, i);
+        model.insert(at, i);
+        if i % 3 == 1 {
+            let gone = (key as usize * 31) % model.len();
+            assert_eq!(line.remove(gone), Some(model.remove(gone)));
+        }
+        if i % 5 == 0 {
+            let (a, b) = (key as usize % model.len(), (key as usize * 17) % model.len());
+            let (start, end) = (a.min(b), a.max(b));
+            line.reverse(start..end);
+            model[start..end].reverse();
+        }
+        if i % 97 == 0 {
+            assert!(line.check_invariants());
+            assert!(line.iter().eq(model.iter()), "after {} steps", i);
+        }
+    }
+    assert_eq!(line.len(), model.len());
+    assert!(line.iter().eq(model.iter()));
+    for (index, value) in model.iter().enumerate() {
+        assert_eq!(line.get(index), Some(value));
+    }
+    assert_eq!(line.get(model.len()), None);
+    assert_eq!(line.remove(model.len()), None);
+}
+
+#[test]
+fn implicit_split_and_merge_cut_and_concatenate() {
+    let mut line: ImplicitTreap<char> = "abcdefgh".chars().collect();
+    line.reverse(..);
+    let (low, high) = line.split(3);
+    assert_eq!(format!("{:?}", low), "['h', 'g', 'f']");
+    assert_eq!(high.iter().collect::<String>(), "edcba");
+    let mut line = high.merge(low);
+    *line.get_mut(0).unwrap() = 'E';
+    line.push_front('<');
+    line.push_back('>');
+    assert_eq!(line.iter().collect::<String>(), "<Edcbahgf>");
+    assert!(line.check_invariants());
+}
+
+#[test]
+#[should_panic(expected = "range 3..9 out of 0..8")]
+fn reverse_rejects_a_range_beyond_the_end() {
+    let mut line: ImplicitTreap<u8> = (0..8).collect();
+    line.reverse(3..9);
+}
+
+split_merge_edge_tests!(Treap, ImplicitTreap);
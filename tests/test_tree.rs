@@ -0,0 +1,592 @@
+use dsa_sport::datastruct::tree_struct::Tree;
+use std::cell::Cell;
+use std::str::FromStr;
+
+const PLANTS: &str = "Plants;2;no seed;seed;3;algae;Mosses;ferns;2;Conifers;sunflower";
+
+#[test]
+fn tree_debug_lists_children() {
+    let tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    assert_eq!(
+        format!("{:?}", tree),
+        "Plants:no seed,seed,\nno seed:algae,Mosses,ferns,\nalgae:\nMosses:\nferns:\nseed:Conifers,sunflower,\nConifers:\nsunflower:\n"
+    );
+}
+
+#[test]
+fn empty_tree_renders_nothing() {
+    let tree: Tree<String> = Tree::new();
+    assert_eq!(format!("{}", tree), "");
+    assert_eq!(format!("{:?}", tree), "--");
+}
+
+#[test]
+fn parse_errors_point_at_the_token() {
+    let error = "Plants;3;no seed;seed".parse::<Tree<String>>().unwrap_err();
+    assert_eq!(error.to_string(), "tree code ends before all children are listed at token 4");
+    let tree: Tree<String> = "".parse().unwrap();
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn tree_code_round_trips() {
+    let codes = ["", "root", "a;3;b;c;d;1;e;2;f;g", r"x\;y;2;\;z\;;w;1;v"];
+    for code in codes.iter() {
+        let tree: Tree<String> = Tree::init_tree(code).unwrap();
+        assert_eq!(tree.to_tree_code(), *code);
+        let preorder = tree.to_preorder_code();
+        let again: Tree<String> = Tree::init_preorder_tree(&preorder).unwrap();
+        assert!(again == tree);
+        assert_eq!(again.to_preorder_code(), preorder);
+    }
+    let numbers: Tree<i32> = "0;3;1;0;2;0".parse().unwrap();
+    assert_eq!(numbers.to_tree_code(), "0;3;1;0;2");
+}
+
+#[test]
+fn built_labels_round_trip() {
+    let mut tree = Tree::new();
+    let root = tree.set_root(String::from("0"));
+    for label in [";", "\\", "2", "a;b\\", " "].iter() {
+        let child = tree.add_child(root, label.to_string()).unwrap();
+        tree.add_child(child, label.repeat(2));
+    }
+    let code = tree.to_tree_code();
+    assert!(Tree::<String>::init_tree(&code).unwrap() == tree);
+    let preorder = tree.to_preorder_code();
+    assert!(Tree::<String>::init_preorder_tree(&preorder).unwrap() == tree);
+}
+
+#[test]
+#[should_panic(expected = "label is empty, the tree grammar has no way to write it")]
+fn an_empty_label_is_not_written() {
+    let mut tree = Tree::new();
+    let root = tree.set_root(String::from("root"));
+    tree.add_child(root, String::new());
+    tree.to_tree_code();
+}
+
+#[test]
+fn preorder_errors() {
+    use dsa_sport::datastruct::tree_struct::TreeParseErrorKind;
+    let error = Tree::<String>::init_preorder_tree("a;2;b;0").unwrap_err();
+    assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::Truncated, 4));
+    let error = Tree::<String>::init_preorder_tree("a;1;b;0;c").unwrap_err();
+    assert_eq!((error.kind(), error.index()), (TreeParseErrorKind::TrailingTokens, 4));
+}
+
+thread_local! {
+    static LIVE_LABELS: Cell<usize> = const { Cell::new(0) };
+}
+
+// a label that counts how many of its kind are alive on the test thread, `bad` does not parse
+struct Counted(String);
+
+impl FromStr for Counted {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, ()> {
+        if text == "bad" {
+            return Err(());
+        }
+        LIVE_LABELS.with(|live| live.set(live.get() + 1));
+        Ok(Counted(text.to_string()))
+    }
+}
+
+impl std::fmt::Display for Counted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        LIVE_LABELS.with(|live| live.set(live.get() - 1));
+    }
+}
+
+fn live_labels() -> usize {
+    LIVE_LABELS.with(Cell::get)
+}
+
+#[test]
+fn parsing_and_writing_release_every_label() {
+    let broken = ["a;2;b;bad;1;c", "a;3;b;c", "a;2;b;c;x;d", "a;1;b;0;c", "a;1;;0"];
+    for code in broken.iter() {
+        assert!(Tree::<Counted>::init_tree(code).is_err(), "{}", code);
+        assert_eq!(live_labels(), 0, "{}", code);
+    }
+    let broken = ["a;2;b;0;bad", "a;2;b;0", "a;x;b", "a;1;b;0;c"];
+    for code in broken.iter() {
+        assert!(Tree::<Counted>::init_preorder_tree(code).is_err(), "{}", code);
+        assert_eq!(live_labels(), 0, "{}", code);
+    }
+
+    let tree: Tree<Counted> = Tree::init_tree(PLANTS).unwrap();
+    assert_eq!(live_labels(), 8);
+    assert_eq!(tree.to_tree_code(), PLANTS);
+    let preorder = tree.to_preorder_code();
+    assert!(tree.find_by_path("Plants/seed/Conifers").is_some());
+    assert!(tree.find_by_path("Plants/bad/Conifers").is_none());
+    assert_eq!(live_labels(), 8);
+    drop(tree);
+    assert_eq!(live_labels(), 0);
+    let tree: Tree<Counted> = Tree::init_preorder_tree(&preorder).unwrap();
+    assert_eq!(tree.to_preorder_code(), preorder);
+    drop(tree);
+    assert_eq!(live_labels(), 0);
+}
+
+#[test]
+fn traversals_report_depth() {
+    use dsa_sport::datastruct::tree_struct::Traversal;
+    let tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let walk = |order| {
+        tree.traverse(order)
+            .map(|(depth, name)| format!("{}{}", depth, name))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    assert_eq!(
+        walk(Traversal::PreOrder),
+        "0Plants,1no seed,2algae,2Mosses,2ferns,1seed,2Conifers,2sunflower"
+    );
+    assert_eq!(
+        walk(Traversal::PostOrder),
+        "2algae,2Mosses,2ferns,1no seed,2Conifers,2sunflower,1seed,0Plants"
+    );
+    assert_eq!(
+        walk(Traversal::LevelOrder),
+        "0Plants,1no seed,1seed,2algae,2Mosses,2ferns,2Conifers,2sunflower"
+    );
+    let empty: Tree<String> = Tree::new();
+    assert_eq!(empty.preorder().count(), 0);
+}
+
+#[test]
+fn deep_chain_does_not_overflow() {
+    let depth = 200_000;
+    let code = vec!["x;1"; depth].join(";") + ";x";
+    let mut tree: Tree<String> = Tree::init_tree(&code).unwrap();
+    assert_eq!(tree.preorder().last().map(|(d, _)| d), Some(depth));
+    assert_eq!(tree.postorder().next().map(|(d, _)| d), Some(depth));
+    for (depth, name) in tree.level_order_mut() {
+        name.push_str(&depth.to_string());
+    }
+    assert_eq!(tree.level_order().nth(7).map(|(_, name)| name.as_str()), Some("x7"));
+}
+
+#[test]
+fn mutations_reshape_the_tree() {
+    let mut tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let root = tree.root().unwrap();
+    let (no_seed, seed) = (tree.child(root, 0).unwrap(), tree.child(root, 1).unwrap());
+    let ferns = tree.child(no_seed, 2).unwrap();
+    assert!(tree.move_subtree(ferns, root));
+    tree.swap_children(root, 0, 2);
+    tree.sort_children_by(seed, |a, b| b.cmp(a));
+    tree.add_child(ferns, "fern".to_string());
+    *tree.get_mut(seed).unwrap() = "seeds".to_string();
+    assert_eq!(
+        tree.to_tree_code(),
+        "Plants;3;ferns;seeds;no seed;1;fern;2;sunflower;Conifers;2;algae;Mosses"
+    );
+    let branch = tree.remove_subtree(no_seed).unwrap();
+    assert!(!tree.contains(no_seed) && branch.contains(no_seed));
+    assert_eq!(branch.to_tree_code(), "no seed;2;algae;Mosses");
+    let whole = tree.remove_subtree(root).unwrap();
+    assert!(tree.is_empty());
+    assert_eq!(whole.get(root).unwrap(), "Plants");
+}
+
+#[test]
+#[should_panic(expected = "own members")]
+fn move_into_own_subtree_panics() {
+    let mut tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let root = tree.root().unwrap();
+    let seed = tree.child(root, 1).unwrap();
+    tree.move_subtree(root, seed);
+}
+
+#[test]
+fn drop_frees_every_member() {
+    use std::rc::Rc;
+    let shared = Rc::new(());
+    let mut tree = Tree::new();
+    let root = tree.set_root(Rc::clone(&shared));
+    let mut parent = root;
+    for i in 0..1000 {
+        let child = tree.add_child(parent, Rc::clone(&shared)).unwrap();
+        tree.add_child(parent, Rc::clone(&shared));
+        if i % 2 == 0 {
+            parent = child;
+        }
+    }
+    assert_eq!(Rc::strong_count(&shared), 2002);
+    drop(tree);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn structural_queries() {
+    let tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    assert_eq!((tree.height(), tree.size(), tree.leaf_count(), tree.diameter()), (2, 8, 5, 4));
+    let lone: Tree<String> = Tree::init_tree("root").unwrap();
+    assert_eq!((lone.height(), lone.depth(lone.root().unwrap()), lone.diameter()), (0, 0, 0));
+    let empty: Tree<String> = Tree::new();
+    assert_eq!((empty.height(), empty.size(), empty.leaf_count(), empty.diameter()), (0, 0, 0, 0));
+    assert!(empty.degree_histogram().is_empty());
+    assert!(empty.find_by_path("").is_none());
+
+    let odd: Tree<String> = Tree::init_tree(r"a/b;2;c\\;c;0;1;d").unwrap();
+    let d = odd.find_by_path(r"a\/b/c/d").unwrap();
+    assert_eq!(odd.label_path(d), r"a\/b/c/d");
+    let slash = odd.find_by_path(r"a\/b/c\\").unwrap();
+    assert_eq!(odd.get(slash).unwrap(), r"c\");
+    assert_eq!(odd.get(odd.lowest_common_ancestor(slash, d)).unwrap(), "a/b");
+}
+
+#[test]
+fn parent_links_follow_mutations() {
+    let mut tree: Tree<String> = Tree::init_preorder_tree("a;2;b;1;c;0;d").unwrap();
+    let c = tree.find_by_path("a/b/c").unwrap();
+    let d = tree.find_by_path("a/d").unwrap();
+    assert_eq!(tree.get(tree.parent(c).unwrap()).unwrap(), "b");
+    assert!(tree.move_subtree(c, d));
+    assert_eq!(tree.label_path(c), "a/d/c");
+    assert_eq!(tree.depth(c), 2);
+    let e = tree.insert_child_at(d, 0, "e".to_string()).unwrap();
+    assert_eq!(tree.next_sibling(e), Some(c));
+    let old_root = tree.root().unwrap();
+    let top = tree.set_root("top".to_string());
+    assert_eq!(tree.parent(old_root), Some(top));
+    assert_eq!(tree.ancestors(c).count(), 3);
+    let b = tree.find_by_path("top/a/b").unwrap();
+    assert_eq!(tree.lowest_common_ancestor(b, e), old_root);
+    let branch = tree.remove_subtree(d).unwrap();
+    assert_eq!(branch.parent(d), None);
+    assert_eq!(branch.label_path(c), "d/c");
+    assert_eq!(tree.first_child(old_root), Some(b));
+    assert_eq!(tree.next_sibling(b), None);
+}
+
+#[test]
+fn zipper_edits_and_rebuilds() {
+    use dsa_sport::datastruct::tree_struct::TreeZipper;
+    let tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let mut zipper = TreeZipper::new(tree).unwrap();
+    zipper.down(1).unwrap().down(1).unwrap();
+    assert_eq!((zipper.focus().as_str(), zipper.depth()), ("sunflower", 2));
+    zipper.modify(|label| label.push('s')).insert_child(0, "seed head".to_string());
+    zipper.left().unwrap().up().unwrap().left().unwrap();
+    assert_eq!(zipper.focus(), "no seed");
+    assert_eq!(zipper.child_count(), 3);
+    let tree = zipper.root();
+    assert_eq!(
+        tree.label_path(tree.find_by_path("Plants/seed/sunflowers/seed head").unwrap()),
+        "Plants/seed/sunflowers/seed head"
+    );
+    assert!(TreeZipper::new(Tree::<String>::new()).is_none());
+}
+
+#[test]
+fn zipper_drop_releases_crumbs() {
+    use dsa_sport::datastruct::tree_struct::TreeZipper;
+    use std::rc::Rc;
+    let shared = Rc::new(());
+    let mut tree = Tree::new();
+    let mut parent = tree.set_root(Rc::clone(&shared));
+    for _ in 0..50 {
+        parent = tree.add_child(parent, Rc::clone(&shared)).unwrap();
+    }
+    let mut zipper = TreeZipper::new(tree).unwrap();
+    for _ in 0..30 {
+        zipper.down(0).unwrap();
+    }
+    drop(zipper);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn zipper_steps_do_not_visit_the_subtrees() {
+    use dsa_sport::datastruct::tree_struct::TreeZipper;
+    let depth = 100_000;
+    let code = vec!["x;1"; depth].join(";") + ";x";
+    let chain: Tree<String> = Tree::init_tree(&code).unwrap();
+    let mut zipper = TreeZipper::new(chain).unwrap();
+    // a step costing the size of the subtree below it would make this walk take hours
+    while zipper.child_count() > 0 {
+        zipper.down(0).unwrap();
+    }
+    zipper.modify(|label| label.push('y'));
+    assert_eq!(zipper.depth(), depth);
+    let chain = zipper.root();
+    assert_eq!(chain.height(), depth);
+    assert_eq!(chain.to_tree_code(), vec!["x;1"; depth].join(";") + ";xy");
+}
+
+#[test]
+fn directory_walk_lists_and_sizes_entries() {
+    use dsa_sport::datastruct::tree_struct::{FsEntry, FsKind, WalkOptions};
+    use std::fs;
+    let root = std::env::temp_dir().join(format!("dsa_sport_walk_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src/bin")).unwrap();
+    fs::create_dir_all(root.join("target/debug")).unwrap();
+    fs::write(root.join("Cargo.toml"), "1234567890").unwrap();
+    fs::write(root.join("src/lib.rs"), "12345").unwrap();
+    fs::write(root.join("src/lib.o"), "123").unwrap();
+    fs::write(root.join("src/bin/main.rs"), "12").unwrap();
+    fs::write(root.join("target/debug/app"), "1").unwrap();
+
+    let options = WalkOptions::new().exclude("target").exclude("*.o");
+    let tree = Tree::<FsEntry>::from_dir(&root, &options).unwrap();
+    let name = root.file_name().unwrap().to_string_lossy().into_owned();
+    assert_eq!(
+        format!("{}", tree),
+        format!(
+            "╚═{}/\n  ╠═Cargo.toml\n  ╚═src/\n    ╠═bin/\n    ║ ╚═main.rs\n    ╚═lib.rs\n",
+            name
+        )
+    );
+    assert_eq!(tree.get(tree.root().unwrap()).unwrap().size(), 17);
+    let src = tree.get(tree.child(tree.root().unwrap(), 1).unwrap()).unwrap();
+    assert_eq!((src.kind(), src.size()), (FsKind::Dir, 7));
+
+    let shallow =
+        Tree::<FsEntry>::from_dir(&root, &WalkOptions::new().max_depth(1).exclude("src/*.o"))
+            .unwrap();
+    assert_eq!(shallow.size(), 4);
+    let src = shallow.get(shallow.child(shallow.root().unwrap(), 1).unwrap()).unwrap();
+    assert_eq!((src.name(), src.size()), ("src", 7));
+    assert_eq!(shallow.get(shallow.root().unwrap()).unwrap().size(), 18);
+
+    let deep = Tree::<FsEntry>::from_dir(&root, &WalkOptions::new().exclude("**/*.rs")).unwrap();
+    assert_eq!(deep.get(deep.root().unwrap()).unwrap().size(), 14);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn interchange_formats_round_trip() {
+    use dsa_sport::datastruct::tree_struct::NewickNode;
+    let tree: Tree<String> =
+        Tree::init_tree(r#"Plants;3;no seed;it's "odd";(a b);2;algae;Mosses\\ferns;1;é_ü"#)
+            .unwrap();
+    let json = tree.to_json();
+    assert!(Tree::<String>::from_json(&json).unwrap() == tree);
+    let sexpr = tree.to_sexpr();
+    assert_eq!(
+        sexpr,
+        r#"(Plants ("no seed" (algae) (Mosses\ferns)) ("it's \"odd\"" (é_ü)) ("(a b)"))"#
+    );
+    assert!(Tree::<String>::from_sexpr(&sexpr).unwrap() == tree);
+    assert!(Tree::<String>::from_sexpr("").unwrap().is_empty());
+    assert!(Tree::<String>::from_json(" null ").unwrap().is_empty());
+
+    let newick = "(('it''s':0.1,b_c)[comment],'under_score':1e-3);";
+    let parsed = Tree::<NewickNode>::from_newick(newick).unwrap();
+    assert_eq!(parsed.to_newick(), "(('it''s':0.1,b_c),'under_score':0.001);");
+    let root = parsed.root().unwrap();
+    let inner = parsed.first_child(root).unwrap();
+    assert_eq!(parsed.get(parsed.child(inner, 1).unwrap()), Some(&NewickNode::new("b c", None)));
+    assert!(Tree::<NewickNode>::from_newick(&parsed.to_newick()).unwrap() == parsed);
+}
+
+#[test]
+fn interchange_errors_carry_positions() {
+    use dsa_sport::datastruct::tree_struct::{NewickNode, TreeFormatErrorKind};
+    let error = Tree::<String>::from_sexpr("(a\n  (b c)\n  ())").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.line(), error.column()),
+        (TreeFormatErrorKind::MissingLabel, 3, 4)
+    );
+    assert_eq!(error.to_string(), "member has no label at line 3 column 4");
+    let error = Tree::<u8>::from_json(r#"{"label":"1","children":[{"label":"x"}]}"#).unwrap_err();
+    assert_eq!((error.kind(), error.position()), (TreeFormatErrorKind::BadLabel, 34));
+    let error = Tree::<String>::from_json(r#"{"label":"a","label":"b"}"#).unwrap_err();
+    assert_eq!((error.kind(), error.position()), (TreeFormatErrorKind::DuplicateKey, 13));
+    let error = Tree::<String>::from_json(r#"{"label":"a\q"}"#).unwrap_err();
+    assert_eq!((error.kind(), error.position()), (TreeFormatErrorKind::BadEscape, 11));
+    let error = Tree::<String>::from_json(r#"{"children":[]}"#).unwrap_err();
+    assert_eq!(error.kind(), TreeFormatErrorKind::MissingLabel);
+    let error = Tree::<String>::from_sexpr("(a) b").unwrap_err();
+    assert_eq!((error.kind(), error.position()), (TreeFormatErrorKind::TrailingCharacters, 4));
+    let error = Tree::<NewickNode>::from_newick("(a,b));").unwrap_err();
+    assert_eq!((error.kind(), error.position()), (TreeFormatErrorKind::UnexpectedCharacter, 5));
+}
+
+#[test]
+fn interchange_handles_deep_trees() {
+    let depth = 100_000;
+    let code = vec!["x;1"; depth].join(";") + ";x";
+    let tree: Tree<String> = Tree::init_tree(&code).unwrap();
+    let again = Tree::<String>::from_json(&tree.to_json()).unwrap();
+    assert_eq!(again.height(), depth);
+    let again = Tree::<String>::from_sexpr(&tree.to_sexpr()).unwrap();
+    assert!(again == tree);
+}
+
+#[test]
+fn diff_lists_subtree_edits() {
+    use dsa_sport::datastruct::tree_struct::TreeEdit;
+    let old: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let new: Tree<String> = Tree::init_tree(
+        "Flora;3;seed;no seed;fungi;2;sunflower;Conifers;2;algae;ferns;2;mould;yeast",
+    )
+    .unwrap();
+    assert_eq!(
+        old.diff(&new),
+        vec![
+            TreeEdit::Delete { path: "Plants/no seed/Mosses".to_string() },
+            TreeEdit::Relabel { path: "Plants".to_string(), label: "Flora".to_string() },
+            TreeEdit::Insert { path: "Flora/fungi".to_string() },
+        ]
+    );
+    assert!(old.diff(&old).is_empty());
+    let empty: Tree<String> = Tree::new();
+    assert_eq!(empty.diff(&old), vec![TreeEdit::Insert { path: "Plants".to_string() }]);
+    assert_eq!(old.diff(&empty), vec![TreeEdit::Delete { path: "Plants".to_string() }]);
+}
+
+#[test]
+fn isomorphism_ignores_child_order() {
+    let one: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let two: Tree<String> =
+        Tree::init_tree("Plants;2;seed;no seed;2;sunflower;Conifers;3;ferns;algae;Mosses").unwrap();
+    assert!(one != two && !one.same_shape(&two));
+    assert!(one.is_labelled_isomorphic(&two) && one.is_isomorphic(&two));
+    assert_eq!(one.canonical_hash(), two.canonical_hash());
+    let renamed: Tree<String> =
+        Tree::init_tree("Plants;2;seed;no seed;2;rose;Conifers;3;ferns;algae;Mosses").unwrap();
+    assert!(!one.is_labelled_isomorphic(&renamed) && one.is_isomorphic(&renamed));
+    assert_ne!(one.canonical_hash(), renamed.canonical_hash());
+    assert!(Tree::<u8>::new().is_isomorphic(&Tree::<char>::new()));
+}
+
+#[test]
+fn transformations_keep_the_shape() {
+    use dsa_sport::datastruct::tree_struct::Traversal;
+    let tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let sizes = tree.fold_up(|_, children: &[&usize]| 1 + children.iter().copied().sum::<usize>());
+    assert!(sizes.same_shape(&tree));
+    assert_eq!(sizes.to_tree_code(), "8;2;4;3;3;1;1;1;2;1;1");
+    let lengths = tree.map(|label| label.len());
+    let labels = lengths.flatten(Traversal::LevelOrder);
+    assert_eq!((labels.len(), *labels[0], *labels[1]), (8, 6, 7));
+
+    let depth = 100_000;
+    let code = vec!["x;1"; depth].join(";") + ";x";
+    let mut chain: Tree<String> = Tree::init_tree(&code).unwrap();
+    let heights = chain.fold_up(|_, children: &[&usize]| children.first().map_or(0, |h| **h + 1));
+    assert_eq!(heights.flatten(Traversal::PreOrder)[0], &depth);
+    let depths = chain.fold_down(0usize, |above, _| above + 1);
+    assert_eq!(depths.flatten(Traversal::PostOrder)[0], &(depth + 1));
+    let mut seen = 0;
+    chain.prune(|_| {
+        seen += 1;
+        seen > 10
+    });
+    assert_eq!(chain.size(), 10);
+}
+
+#[test]
+fn stale_and_foreign_handles_are_rejected() {
+    let mut tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let root = tree.root().unwrap();
+    let no_seed = tree.child(root, 0).unwrap();
+    let algae = tree.child(no_seed, 0).unwrap();
+    drop(tree.remove_subtree(no_seed));
+    // the freed members may be reused by another tree, the handles must not reach them
+    let other: Tree<String> = Tree::init_tree("zzz;2;zzz;zzz").unwrap();
+    for &stale in [no_seed, algae].iter() {
+        assert!(!tree.contains(stale));
+        assert_eq!(tree.get(stale), None);
+        assert_eq!(tree.get_mut(stale), None);
+        assert_eq!(tree.parent(stale), None);
+        assert_eq!(tree.child(stale, 0), None);
+        assert!(tree.remove_subtree(stale).is_none());
+        assert!(tree.add_child(stale, "x".to_string()).is_none());
+        assert!(!tree.move_subtree(stale, root));
+    }
+    let foreign = other.child(other.root().unwrap(), 1).unwrap();
+    assert_eq!(tree.get(foreign), None);
+    assert!(tree.insert_child_at(foreign, 0, "x".to_string()).is_none());
+    assert!(!tree.move_subtree(root, foreign));
+    let branch: Tree<String> = Tree::init_tree("b").unwrap();
+    let branch = tree.insert_subtree_at(foreign, 0, branch).unwrap_err();
+    assert_eq!(branch.to_tree_code(), "b");
+    assert_eq!(tree.to_tree_code(), "Plants;1;seed;2;Conifers;sunflower");
+}
+
+#[test]
+#[should_panic(expected = "node does not belong to this tree")]
+fn queries_on_a_stale_handle_panic() {
+    let mut tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let seed = tree.find_by_path("Plants/seed").unwrap();
+    drop(tree.remove_subtree(seed));
+    tree.depth(seed);
+}
+
+#[test]
+fn moved_subtrees_keep_their_handles() {
+    let mut tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let seed = tree.find_by_path("Plants/seed").unwrap();
+    let conifers = tree.find_by_path("Plants/seed/Conifers").unwrap();
+    let mut branch = tree.remove_subtree(seed).unwrap();
+    assert_eq!(branch.get(conifers).map(String::as_str), Some("Conifers"));
+    *branch.get_mut(conifers).unwrap() = "pines".to_string();
+    let mut host: Tree<String> = Tree::init_tree("host").unwrap();
+    let top = host.root().unwrap();
+    assert_eq!(host.insert_subtree_at(top, 0, branch).ok(), Some(seed));
+    assert_eq!(host.label_path(conifers), "host/seed/pines");
+    assert!(!tree.contains(conifers));
+}
+
+#[test]
+fn handles_follow_their_members_through_cuts() {
+    let mut tree: Tree<String> = Tree::init_tree(PLANTS).unwrap();
+    let root = tree.root().unwrap();
+    let seed = tree.find_by_path("Plants/seed").unwrap();
+    let conifers = tree.find_by_path("Plants/seed/Conifers").unwrap();
+    let algae = tree.find_by_path("Plants/no seed/algae").unwrap();
+    // every handle is checked once before the cut
+    assert!([root, seed, conifers, algae].iter().all(|&node| tree.contains(node)));
+    let mut branch = tree.remove_subtree(seed).unwrap();
+    assert!(!tree.contains(conifers) && !tree.contains(seed));
+    assert!(tree.contains(algae) && tree.contains(root));
+    assert!(branch.contains(conifers) && !branch.contains(algae));
+    let pines = branch.add_child(conifers, "pines".to_string()).unwrap();
+    let inner = branch.remove_subtree(conifers).unwrap();
+    assert!(!branch.contains(pines) && inner.contains(pines));
+    assert!(tree.insert_subtree_at(algae, 0, inner).is_ok());
+    assert!(tree.insert_subtree_at(root, 1, branch).is_ok());
+    assert!([seed, conifers, pines, algae].iter().all(|&node| tree.contains(node)));
+    assert_eq!(tree.label_path(pines), "Plants/no seed/algae/Conifers/pines");
+}
+
+#[test]
+fn cutting_and_hanging_leave_the_members_alone() {
+    let depth = 100_000;
+    let code = vec!["x;1"; depth].join(";") + ";x";
+    let mut chain: Tree<String> = Tree::init_tree(&code).unwrap();
+    let root = chain.root().unwrap();
+    let below = chain.child(root, 0).unwrap();
+    // a cut or hang visiting the 100 000 members below would make this loop take minutes
+    for _ in 0..10_000 {
+        let branch = chain.remove_subtree(below).unwrap();
+        assert!(chain.insert_subtree_at(root, 0, branch).is_ok());
+    }
+    assert_eq!(chain.height(), depth);
+}
+
+#[test]
+fn json_keys_after_an_empty_children_array() {
+    use dsa_sport::datastruct::tree_struct::TreeFormatErrorKind;
+    let tree = Tree::<String>::from_json(r#"{"children":[],"label":"a"}"#).unwrap();
+    assert_eq!(tree.to_tree_code(), "a");
+    let tree = Tree::<String>::from_json(r#"{"children":[ ] , "label":"a"}"#).unwrap();
+    assert_eq!(tree.to_tree_code(), "a");
+    let error = Tree::<String>::from_json(r#"{"children":[] "label":"a"}"#).unwrap_err();
+    assert_eq!((error.kind(), error.column()), (TreeFormatErrorKind::UnexpectedCharacter, 16));
+}
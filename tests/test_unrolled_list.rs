@@ -0,0 +1,111 @@
+use dsa_sport::datastruct::unrolled_list_struct::UnrolledList;
+
+#[test]
+fn unrolled_list_matches_vec() {
+    let mut list = UnrolledList::with_block_capacity(5);
+    let mut model = Vec::new();
+    let mut x: u64 = 7;
+    for step in 0..3000usize {
+        x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let roll = (x >> 33) as usize;
+        if step % 4 == 3 && !model.is_empty() {
+            let index = roll % model.len();
+            assert_eq!(list.remove(index), Some(model.remove(index)));
+        } else {
+            let index = roll % (model.len() + 1);
+            list.insert(index, step);
+            model.insert(index, step);
+        }
+    }
+    assert_eq!(list.len(), model.len());
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), model);
+    for (index, value) in model.iter().enumerate() {
+        assert_eq!(list[index], *value);
+    }
+    // every block except the last one is at least half full
+    assert!(list.blocks() <= model.len() / 2 + 1);
+    while let Some(value) = list.pop_back() {
+        assert_eq!(Some(value), model.pop());
+    }
+    assert!(list.is_empty());
+    assert_eq!(format!("{:?}", list), "✘");
+}
+
+fn filled(capacity: usize, n: usize) -> UnrolledList<usize> {
+    let mut list = UnrolledList::with_block_capacity(capacity);
+    for i in 0..n {
+        list.push_back(i);
+    }
+    list
+}
+
+#[test]
+fn a_full_block_splits_around_the_insertion() {
+    for &(at, expected) in [
+        (0, "⎩9,0,1⎭↪⎩2,3⎭↪✘"),
+        (1, "⎩0,9,1⎭↪⎩2,3⎭↪✘"),
+        (2, "⎩0,1,9⎭↪⎩2,3⎭↪✘"),
+        (3, "⎩0,1⎭↪⎩2,9,3⎭↪✘"),
+        (4, "⎩0,1,2,3⎭↪⎩9⎭↪✘"),
+    ]
+    .iter()
+    {
+        let mut list = filled(4, 4);
+        list.insert(at, 9);
+        assert_eq!(format!("{:?}", list), expected, "insert at {}", at);
+        assert_eq!(list[at], 9);
+        assert_eq!(list.len(), 5);
+    }
+    // an odd capacity keeps the smaller half in front
+    let mut list = filled(5, 5);
+    list.insert(3, 9);
+    assert_eq!(format!("{:?}", list), "⎩0,1⎭↪⎩2,9,3,4⎭↪✘");
+}
+
+#[test]
+fn an_under_filled_block_borrows_or_merges() {
+    // still half full, nothing moves
+    let mut list = filled(4, 8);
+    assert_eq!(list.remove(0), Some(0));
+    assert_eq!(list.remove(0), Some(1));
+    assert_eq!(format!("{:?}", list), "⎩2,3⎭↪⎩4,5,6,7⎭↪✘");
+    // the two blocks would overflow one block, the first one borrows
+    assert_eq!(list.remove(0), Some(2));
+    assert_eq!(format!("{:?}", list), "⎩3,4⎭↪⎩5,6,7⎭↪✘");
+    // the two blocks fill one block exactly, they merge
+    assert_eq!(list.remove(1), Some(4));
+    assert_eq!(format!("{:?}", list), "⎩3,5,6,7⎭↪✘");
+    assert_eq!(list.blocks(), 1);
+    // the last block may fall under half full and disappears once empty
+    let mut list = filled(4, 5);
+    assert_eq!(list.remove(4), Some(4));
+    assert_eq!(format!("{:?}", list), "⎩0,1,2,3⎭↪✘");
+    assert_eq!(list.blocks(), 1);
+}
+
+#[test]
+fn both_ends_insert_and_remove() {
+    let mut list = filled(3, 7);
+    list.insert(0, 100);
+    list.insert(list.len(), 200);
+    assert_eq!(list.get(0), Some(&100));
+    assert_eq!(list.get(list.len() - 1), Some(&200));
+    assert_eq!(list.remove(list.len()), None);
+    assert_eq!(list.remove(0), Some(100));
+    assert_eq!(list.remove(list.len() - 1), Some(200));
+    assert_eq!(list.pop_front(), Some(0));
+    assert_eq!(list.pop_back(), Some(6));
+    assert!(list.iter().copied().eq(1..6));
+    while list.pop_front().is_some() {}
+    assert_eq!(list.blocks(), 0);
+    assert_eq!(list.pop_back(), None);
+    list.insert(0, 1);
+    assert_eq!(format!("{:?}", list), "⎩1⎭↪✘");
+}
+
+#[test]
+#[should_panic(expected = "Out of bounds access")]
+fn insert_past_the_end_panics() {
+    let mut list = filled(4, 3);
+    list.insert(4, 9);
+}
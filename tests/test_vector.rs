@@ -1,4 +1,5 @@
 use dsa_sport::datastruct::vec_struct::Vector;
+use std::rc::Rc;
 
 
 #[test]
@@ -9,3 +10,27 @@ fn vector_all() {
     assert_eq!(v.len(), 1);
 
 }
+
+#[test]
+fn drop_releases_the_live_elements() {
+    let counter = Rc::new(String::from("x"));
+    let mut v = Vector::new();
+    for _ in 0..6 {
+        v.push_back(Rc::clone(&counter));
+    }
+    // wrap the ring around the end of the buffer before it grows again
+    v.pop_front();
+    v.pop_front();
+    for _ in 0..4 {
+        v.push_back(Rc::clone(&counter));
+    }
+    v.pop_back();
+    assert_eq!(Rc::strong_count(&counter), 8);
+    // printing borrows the elements
+    assert_eq!(format!("{}", v).matches('x').count(), 7);
+    assert_eq!(Rc::strong_count(&counter), 8);
+    drop(v);
+    assert_eq!(Rc::strong_count(&counter), 1);
+    let never_allocated: Vector<String> = Vector::new();
+    drop(never_allocated);
+}
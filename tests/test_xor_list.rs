@@ -0,0 +1,48 @@
+//! kept small so the whole file can also run under `cargo +nightly miri test --test test_xor_list`
+use dsa_sport::datastruct::xor_list_struct::XorList;
+use std::rc::Rc;
+
+#[test]
+fn both_ends_and_reverse() {
+    let mut list = XorList::new();
+    for i in 0..5 {
+        list.push_back(i);
+        list.push_front(-i);
+    }
+    assert_eq!(list.len(), 10);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![-4, -3, -2, -1, 0, 0, 1, 2, 3, 4]);
+    list.reverse();
+    assert_eq!(list.pop_front(), Some(4));
+    assert_eq!(list.pop_back(), Some(-4));
+    list.push_back(9);
+    assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![9, -3, -2, -1, 0, 0, 1, 2, 3]);
+    assert_eq!(format!("{:?}", list), "⎩3⎭⇄⎩2⎭⇄⎩1⎭⇄⎩0⎭⇄⎩0⎭⇄⎩-1⎭⇄⎩-2⎭⇄⎩-3⎭⇄⎩9⎭⇄✘");
+}
+
+#[test]
+fn drop_releases_every_element() {
+    let counter = Rc::new(());
+    {
+        let mut list = XorList::new();
+        for _ in 0..4 {
+            list.push_back(Rc::clone(&counter));
+        }
+        list.pop_front();
+        list.reverse();
+        list.pop_front();
+        assert_eq!(Rc::strong_count(&counter), 3);
+    }
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn single_member_list() {
+    let mut list = XorList::new();
+    list.push_front("only");
+    list.reverse();
+    assert_eq!(list.front(), list.back());
+    assert_eq!(list.pop_back(), Some("only"));
+    assert!(list.is_empty());
+    assert_eq!(list.pop_front(), None);
+    assert_eq!(format!("{:?}", list), "✘");
+}